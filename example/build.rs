@@ -1,16 +1,27 @@
 use miette::{IntoDiagnostic, Result};
-use wgsl_bindgen::{WgslBindgenOptionBuilder, GlamWgslTypeMap, WgslShaderSourceOutputType, WgslTypeSerializeStrategy};
+use wgsl_bindgen::{WgslBindgenOptionBuilder, GlamWgslTypeMap, WgslShaderPermutation, WgslShaderSourceOutputType, WgslTypeSerializeStrategy};
 
 fn main() -> Result<()> {
     WgslBindgenOptionBuilder::default()
         .add_entry_point("src/shader/testbed.wgsl")
         .add_entry_point("src/shader/triangle.wgsl")
+        .add_entry_point("src/shader/brightness.wgsl")
+        .add_permutation(
+            WgslShaderPermutation::new("src/shader/triangle.wgsl", "grayscale")
+                .with_def("GRAYSCALE"),
+        )
+        .add_permutation(
+            WgslShaderPermutation::new("src/shader/brightness.wgsl", "clamped")
+                .with_def("CLAMPED"),
+        )
         .skip_hash_check(true)
         .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
         .wgsl_type_map(GlamWgslTypeMap)
         .derive_serde(false)
+        .typed_buffer_bindings(true)
         .output_file("src/shader.rs")
         .shader_source_output_type(WgslShaderSourceOutputType::Composer)
+        .hot_reload(true)
         .build()?
         .generate()
         .into_diagnostic()
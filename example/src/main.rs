@@ -20,6 +20,8 @@ struct State<'a> {
     size: winit::dpi::PhysicalSize<u32>,
     config: wgpu::SurfaceConfiguration,
     pipeline: wgpu::RenderPipeline,
+    #[cfg(debug_assertions)]
+    shader_watcher: shader::hot_reload::ShaderWatcher,
     bind_group0: shader::triangle::bind_groups::BindGroup0,
     bind_group1: shader::triangle::bind_groups::BindGroup1,
     vertex_buffer: wgpu::Buffer,
@@ -62,26 +64,11 @@ impl<'a> State<'a> {
         surface.configure(&device, &config);
 
         // Use the generated bindings to create the pipeline.
-        let shader = shader::triangle::create_shader_module(&device);
-        let render_pipeline_layout = shader::triangle::create_pipeline_layout(&device);
-
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(&render_pipeline_layout),
-            vertex: shader::triangle::vertex_state(
-                &shader,
-                &shader::triangle::vs_main_entry(wgpu::VertexStepMode::Vertex),
-            ),
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: shader::triangle::ENTRY_FS_MAIN,
-                targets: &[Some(surface_format.into())],
-            }),
-            primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
+        let shader = shader::triangle::create_shader_module(
+            &device,
+            &shader::triangle::ShaderDefs::default(),
+        );
+        let pipeline = create_pipeline(&device, &shader, surface_format);
 
         // Create a gradient texture.
         let texture = device.create_texture_with_data(
@@ -140,18 +127,21 @@ impl<'a> State<'a> {
             },
         );
 
-        let uniforms_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("uniforms"),
-            contents: bytemuck::cast_slice(&[shader::triangle::Uniforms::new(vec4(
-                1.0, 1.0, 1.0, 1.0,
-            ))]),
-            usage: wgpu::BufferUsages::UNIFORM,
-        });
+        // Tag the buffer with its contents so it can't be bound to the wrong binding.
+        let uniforms_buffer = shader::typed_buffer::TypedBuffer::<shader::triangle::Uniforms>::new(
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("uniforms"),
+                contents: bytemuck::cast_slice(&[shader::triangle::Uniforms::new(vec4(
+                    1.0, 1.0, 1.0, 1.0,
+                ))]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            }),
+        );
 
         let bind_group1 = shader::triangle::bind_groups::BindGroup1::from_bindings(
             &device,
             shader::triangle::bind_groups::BindGroupLayout1 {
-                uniforms: uniforms_buffer.as_entire_buffer_binding(),
+                uniforms: uniforms_buffer.as_entire_binding(),
             },
         );
 
@@ -180,6 +170,8 @@ impl<'a> State<'a> {
             size,
             config,
             pipeline,
+            #[cfg(debug_assertions)]
+            shader_watcher: shader::triangle::shader_watcher(),
             bind_group0,
             bind_group1,
             vertex_buffer,
@@ -195,6 +187,22 @@ impl<'a> State<'a> {
         }
     }
 
+    /// Recreates the pipeline if the shader files changed on disk.
+    #[cfg(debug_assertions)]
+    fn reload_shader(&mut self) {
+        if self.shader_watcher.changed() {
+            match shader::triangle::reload_shader_module(
+                &self.device,
+                &shader::triangle::ShaderDefs::default(),
+            ) {
+                Ok(shader) => {
+                    self.pipeline = create_pipeline(&self.device, &shader, self.config.format)
+                }
+                Err(e) => eprintln!("{e}"),
+            }
+        }
+    }
+
     fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
         let output = self.surface.get_current_texture()?;
         let output_view = output
@@ -228,7 +236,7 @@ impl<'a> State<'a> {
         self.bind_group0.set(&mut render_pass);
         self.bind_group1.set(&mut render_pass);
 
-        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        shader::triangle::set_vertex_buffers(&mut render_pass, self.vertex_buffer.slice(..));
         render_pass.draw(0..3, 0..1);
 
         drop(render_pass);
@@ -241,6 +249,32 @@ impl<'a> State<'a> {
     }
 }
 
+fn create_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    surface_format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    let render_pipeline_layout = shader::triangle::create_pipeline_layout(device);
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Render Pipeline"),
+        layout: Some(&render_pipeline_layout),
+        vertex: shader::triangle::vertex_state(
+            shader,
+            &shader::triangle::vs_main_entry(wgpu::VertexStepMode::Vertex),
+        ),
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: shader::triangle::ENTRY_FS_MAIN,
+            targets: &[Some(surface_format.into())],
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+    })
+}
+
 fn main() {
     let event_loop = EventLoop::new().unwrap();
     let window = WindowBuilder::new()
@@ -262,6 +296,9 @@ fn main() {
                 }
                 WindowEvent::ScaleFactorChanged { .. } => {}
                 WindowEvent::RedrawRequested => {
+                    #[cfg(debug_assertions)]
+                    state.reload_shader();
+
                     match state.render() {
                         Ok(_) => {}
                         Err(wgpu::SurfaceError::Lost) => state.resize(state.size),
@@ -2,7 +2,7 @@
 //
 // ^ wgsl_bindgen version 0.6.11
 // Changes made to this file will not be saved.
-// SourceHash: cf595a1f863090091448656b4027f6c953ea1ff608464fc167c7ddc784e4fc8f
+// SourceHash: 59b7f45c0e34d256f564ce1c72ea23ab3b84fd3055998f209e23494e25029c16
 
 #[allow(unused)]
 mod _root {
@@ -18,6 +18,550 @@ mod _root {
         assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
     };
 }
+pub mod typed_buffer {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    /// A buffer tagged with the type of its contents.
+    pub struct TypedBuffer<T: ?Sized> {
+        buffer: wgpu::Buffer,
+        _phantom: std::marker::PhantomData<T>,
+    }
+    impl<T: ?Sized> TypedBuffer<T> {
+        /// Tags `buffer` as containing values of type `T`.
+        pub fn new(buffer: wgpu::Buffer) -> Self {
+            Self {
+                buffer,
+                _phantom: std::marker::PhantomData,
+            }
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn into_inner(self) -> wgpu::Buffer {
+            self.buffer
+        }
+        pub fn as_entire_binding(&self) -> TypedBufferBinding<'_, T> {
+            self.binding(0, None)
+        }
+        pub fn binding(
+            &self,
+            offset: wgpu::BufferAddress,
+            size: Option<wgpu::BufferSize>,
+        ) -> TypedBufferBinding<'_, T> {
+            TypedBufferBinding {
+                binding: wgpu::BufferBinding {
+                    buffer: &self.buffer,
+                    offset,
+                    size,
+                },
+                _phantom: std::marker::PhantomData,
+            }
+        }
+    }
+    impl<T: ?Sized> std::fmt::Debug for TypedBuffer<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("TypedBuffer").field("buffer", &self.buffer).finish()
+        }
+    }
+    impl<T: ?Sized> std::ops::Deref for TypedBuffer<T> {
+        type Target = wgpu::Buffer;
+        fn deref(&self) -> &Self::Target {
+            &self.buffer
+        }
+    }
+    /// A buffer binding that can only be used for bindings of type `T`.
+    pub struct TypedBufferBinding<'a, T: ?Sized> {
+        binding: wgpu::BufferBinding<'a>,
+        _phantom: std::marker::PhantomData<T>,
+    }
+    impl<'a, T: ?Sized> Clone for TypedBufferBinding<'a, T> {
+        fn clone(&self) -> Self {
+            Self {
+                binding: self.binding.clone(),
+                _phantom: std::marker::PhantomData,
+            }
+        }
+    }
+    impl<'a, T: ?Sized> std::fmt::Debug for TypedBufferBinding<'a, T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("TypedBufferBinding").field("binding", &self.binding).finish()
+        }
+    }
+    impl<'a, T: ?Sized> From<TypedBufferBinding<'a, T>> for wgpu::BufferBinding<'a> {
+        fn from(binding: TypedBufferBinding<'a, T>) -> Self {
+            binding.binding
+        }
+    }
+}
+pub mod pass_ext {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    /// A render pass, compute pass, or render bundle encoder for setting bind groups.
+    pub trait SetBindGroup<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        );
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderPass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderPass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::ComputePass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::ComputePass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderBundleEncoder::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    /// A render pass, compute pass, or render bundle encoder for setting push constants.
+    /// Compute passes ignore the shader stages.
+    pub trait SetPushConstants {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        );
+    }
+    impl<'a> SetPushConstants for wgpu::RenderPass<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderPass::set_push_constants(self, stages, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::ComputePass<'a> {
+        fn set_push_constants(
+            &mut self,
+            _stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::ComputePass::set_push_constants(self, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::RenderBundleEncoder<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderBundleEncoder::set_push_constants(self, stages, offset, data);
+        }
+    }
+    /// A render pass or render bundle encoder for setting vertex buffers.
+    pub trait SetVertexBuffer<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>);
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderPass<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderPass::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderBundleEncoder::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+}
+pub mod module_layout {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    use wgpu::naga;
+    /// The type of a binding as it is used by the shader.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BindingKind {
+        Uniform { size: u32 },
+        Storage { read_only: bool, size: u32 },
+        Texture {
+            dim: naga::ImageDimension,
+            arrayed: bool,
+            sample_kind: naga::ScalarKind,
+            multisampled: bool,
+        },
+        DepthTexture { dim: naga::ImageDimension, arrayed: bool, multisampled: bool },
+        StorageTexture {
+            dim: naga::ImageDimension,
+            arrayed: bool,
+            format: naga::StorageFormat,
+            read: bool,
+            write: bool,
+        },
+        Sampler { comparison: bool },
+        AccelerationStructure,
+    }
+    /// The number of resources in a binding.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BindingCount {
+        Single,
+        /// A `binding_array` with a fixed size.
+        Array(u32),
+        /// A `binding_array` without a size.
+        RuntimeArray,
+    }
+    /// Describes the binding of a global variable.
+    /// Returns `None` for types that can't be used in bindings.
+    pub fn binding_kind(
+        module: &naga::Module,
+        global: &naga::GlobalVariable,
+    ) -> Option<(BindingKind, BindingCount)> {
+        let (inner, count) = match &module.types[global.ty].inner {
+            naga::TypeInner::BindingArray { base, size } => {
+                let count = match size {
+                    naga::ArraySize::Constant(size) => BindingCount::Array(size.get()),
+                    naga::ArraySize::Dynamic => BindingCount::RuntimeArray,
+                };
+                (&module.types[*base].inner, count)
+            }
+            inner => (inner, BindingCount::Single),
+        };
+        let kind = match (global.space, inner) {
+            (naga::AddressSpace::Uniform, _) => {
+                BindingKind::Uniform {
+                    size: inner.size(module.to_ctx()),
+                }
+            }
+            (naga::AddressSpace::Storage { access }, _) => {
+                BindingKind::Storage {
+                    read_only: !access.contains(naga::StorageAccess::STORE),
+                    size: inner.size(module.to_ctx()),
+                }
+            }
+            (_, naga::TypeInner::Image { dim, arrayed, class }) => {
+                match *class {
+                    naga::ImageClass::Sampled { kind, multi } => {
+                        BindingKind::Texture {
+                            dim: *dim,
+                            arrayed: *arrayed,
+                            sample_kind: kind,
+                            multisampled: multi,
+                        }
+                    }
+                    naga::ImageClass::Depth { multi } => {
+                        BindingKind::DepthTexture {
+                            dim: *dim,
+                            arrayed: *arrayed,
+                            multisampled: multi,
+                        }
+                    }
+                    naga::ImageClass::Storage { format, access } => {
+                        BindingKind::StorageTexture {
+                            dim: *dim,
+                            arrayed: *arrayed,
+                            format,
+                            read: access.contains(naga::StorageAccess::LOAD),
+                            write: access.contains(naga::StorageAccess::STORE),
+                        }
+                    }
+                }
+            }
+            (_, naga::TypeInner::Sampler { comparison }) => {
+                BindingKind::Sampler {
+                    comparison: *comparison,
+                }
+            }
+            (_, naga::TypeInner::AccelerationStructure) => {
+                BindingKind::AccelerationStructure
+            }
+            _ => return None,
+        };
+        Some((kind, count))
+    }
+    /// A binding recorded when generating the bindings.
+    #[derive(Debug)]
+    pub struct BindingLayout {
+        pub group: u32,
+        pub binding: u32,
+        pub name: &'static str,
+        pub kind: BindingKind,
+        pub count: BindingCount,
+    }
+    /// A struct recorded when generating the bindings.
+    #[derive(Debug)]
+    pub struct StructLayout {
+        pub name: &'static str,
+        /// The name in the composed module, which may be mangled by naga_oil.
+        pub naga_name: &'static str,
+        pub size: u32,
+        pub members: &'static [MemberLayout],
+    }
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct MemberLayout {
+        pub name: &'static str,
+        pub offset: u32,
+        pub size: u32,
+    }
+    /// A difference between a module and the layouts of the generated code.
+    #[derive(Debug)]
+    pub enum LayoutError {
+        MissingBinding { group: u32, binding: u32, name: &'static str },
+        UnexpectedBinding { group: u32, binding: u32, name: Option<String> },
+        BindingType {
+            group: u32,
+            binding: u32,
+            name: &'static str,
+            expected: (BindingKind, BindingCount),
+            actual: Option<(BindingKind, BindingCount)>,
+        },
+        MissingStruct { name: &'static str },
+        StructSize { name: &'static str, expected: u32, actual: u32 },
+        StructMembers { name: &'static str, expected: usize, actual: usize },
+        MemberLayout {
+            name: &'static str,
+            member: &'static str,
+            expected_offset: u32,
+            expected_size: u32,
+            actual: Option<(u32, u32)>,
+        },
+    }
+    impl std::fmt::Display for LayoutError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::MissingBinding { group, binding, name } => {
+                    write!(
+                        f,
+                        "binding `{name}` at @group({group}) @binding({binding}) is missing from the module"
+                    )
+                }
+                Self::UnexpectedBinding { group, binding, name } => {
+                    write!(
+                        f,
+                        "binding `{}` at @group({group}) @binding({binding}) is not in the generated bindings",
+                        name.as_deref().unwrap_or_default()
+                    )
+                }
+                Self::BindingType { group, binding, name, expected, actual } => {
+                    write!(
+                        f,
+                        "binding `{name}` at @group({group}) @binding({binding}) changed from {expected:?} to "
+                    )?;
+                    match actual {
+                        Some(actual) => write!(f, "{actual:?}"),
+                        None => write!(f, "an unsupported type"),
+                    }
+                }
+                Self::MissingStruct { name } => {
+                    write!(f, "struct `{name}` is missing from the module")
+                }
+                Self::StructSize { name, expected, actual } => {
+                    write!(
+                        f,
+                        "struct `{name}` changed size from {expected} to {actual} bytes"
+                    )
+                }
+                Self::StructMembers { name, expected, actual } => {
+                    write!(
+                        f, "struct `{name}` changed from {expected} to {actual} members"
+                    )
+                }
+                Self::MemberLayout {
+                    name,
+                    member,
+                    expected_offset,
+                    expected_size,
+                    actual,
+                } => {
+                    write!(
+                        f,
+                        "member `{name}.{member}` changed from offset {expected_offset} and size {expected_size} to "
+                    )?;
+                    match actual {
+                        Some((offset, size)) => {
+                            write!(f, "offset {offset} and size {size}")
+                        }
+                        None => write!(f, "missing"),
+                    }
+                }
+            }
+        }
+    }
+    impl std::error::Error for LayoutError {}
+    pub fn verify_module_layout(
+        module: &wgpu::naga::Module,
+        bindings: &[BindingLayout],
+        structs: &[StructLayout],
+    ) -> Result<(), LayoutError> {
+        for (_, global) in module.global_variables.iter() {
+            let Some(resource) = &global.binding else {
+                continue;
+            };
+            let (group, binding) = (resource.group, resource.binding);
+            let Some(expected) = bindings
+                .iter()
+                .find(|b| b.group == group && b.binding == binding) else {
+                return Err(LayoutError::UnexpectedBinding {
+                    group,
+                    binding,
+                    name: global.name.clone(),
+                });
+            };
+            let actual = binding_kind(module, global);
+            if actual != Some((expected.kind, expected.count)) {
+                return Err(LayoutError::BindingType {
+                    group,
+                    binding,
+                    name: expected.name,
+                    expected: (expected.kind, expected.count),
+                    actual,
+                });
+            }
+        }
+        for expected in bindings {
+            let found = module
+                .global_variables
+                .iter()
+                .any(|(_, g)| {
+                    g.binding
+                        .as_ref()
+                        .is_some_and(|b| {
+                            b.group == expected.group && b.binding == expected.binding
+                        })
+                });
+            if !found {
+                return Err(LayoutError::MissingBinding {
+                    group: expected.group,
+                    binding: expected.binding,
+                    name: expected.name,
+                });
+            }
+        }
+        for expected in structs {
+            let inner = module
+                .types
+                .iter()
+                .find(|(_, ty)| ty.name.as_deref() == Some(expected.naga_name))
+                .map(|(_, ty)| &ty.inner);
+            let Some(wgpu::naga::TypeInner::Struct { members, span }) = inner else {
+                return Err(LayoutError::MissingStruct {
+                    name: expected.name,
+                });
+            };
+            if *span != expected.size {
+                return Err(LayoutError::StructSize {
+                    name: expected.name,
+                    expected: expected.size,
+                    actual: *span,
+                });
+            }
+            if members.len() != expected.members.len() {
+                return Err(LayoutError::StructMembers {
+                    name: expected.name,
+                    expected: expected.members.len(),
+                    actual: members.len(),
+                });
+            }
+            for member in expected.members {
+                let actual = members
+                    .iter()
+                    .find(|m| m.name.as_deref() == Some(member.name))
+                    .map(|m| (m.offset, module.types[m.ty].inner.size(module.to_ctx())));
+                if actual != Some((member.offset, member.size)) {
+                    return Err(LayoutError::MemberLayout {
+                        name: expected.name,
+                        member: member.name,
+                        expected_offset: member.offset,
+                        expected_size: member.size,
+                        actual,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+pub mod hot_reload {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    /// An error from reloading a shader module from disk.
+    #[cfg(debug_assertions)]
+    #[derive(Debug)]
+    pub enum ShaderReloadError {
+        /// A shader source file could not be read.
+        Io { path: &'static str, error: std::io::Error },
+        /// The shader sources could not be composed.
+        /// The message is the formatted diagnostic from naga_oil.
+        Compose(String),
+        /// The composed module doesn't match the generated bindings.
+        Layout(_root::module_layout::LayoutError),
+    }
+    #[cfg(debug_assertions)]
+    impl std::fmt::Display for ShaderReloadError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Io { path, error } => write!(f, "failed to read {path}: {error}"),
+                Self::Compose(message) => f.write_str(message),
+                Self::Layout(error) => {
+                    write!(f, "bindings need to be regenerated: {error}")
+                }
+            }
+        }
+    }
+    #[cfg(debug_assertions)]
+    impl std::error::Error for ShaderReloadError {}
+    #[cfg(debug_assertions)]
+    pub fn read_shader_source(path: &'static str) -> Result<String, ShaderReloadError> {
+        std::fs::read_to_string(path)
+            .map_err(|error| ShaderReloadError::Io {
+                path,
+                error,
+            })
+    }
+    /// Checks the modified times of shader source files for changes.
+    #[cfg(debug_assertions)]
+    #[derive(Debug)]
+    pub struct ShaderWatcher {
+        paths: &'static [&'static str],
+        modified: Vec<Option<std::time::SystemTime>>,
+    }
+    #[cfg(debug_assertions)]
+    impl ShaderWatcher {
+        pub fn new(paths: &'static [&'static str]) -> Self {
+            Self {
+                paths,
+                modified: Self::modified_times(paths),
+            }
+        }
+        /// Returns `true` if any of the files changed since the last call.
+        pub fn changed(&mut self) -> bool {
+            let modified = Self::modified_times(self.paths);
+            let changed = modified != self.modified;
+            self.modified = modified;
+            changed
+        }
+        fn modified_times(paths: &[&str]) -> Vec<Option<std::time::SystemTime>> {
+            paths
+                .iter()
+                .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+                .collect()
+        }
+    }
+}
 pub mod reachme {
     #[allow(unused_imports)]
     use super::{_root, _root::*};
@@ -470,6 +1014,8 @@ pub mod testbed {
     unsafe impl bytemuck::Zeroable for VertexInput {}
     unsafe impl bytemuck::Pod for VertexInput {}
     pub mod bind_groups {
+        #[allow(unused_imports)]
+        use super::*;
         #[derive(Debug)]
         pub struct BindGroup0(wgpu::BindGroup);
         #[allow(non_snake_case)]
@@ -479,11 +1025,11 @@ pub mod testbed {
             pub color_sampler: &'a wgpu::Sampler,
         }
         const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
+            label: Some("testbed::BindGroup0::Layout"),
             entries: &[
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Texture {
                         sample_type: wgpu::TextureSampleType::Float {
                             filterable: true,
@@ -495,12 +1041,26 @@ pub mod testbed {
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
             ],
         };
+        /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout0(wgpu::BindGroupLayout);
+        impl Layout0 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup0::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout0 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
         impl BindGroup0 {
             pub fn get_bind_group_layout(
                 device: &wgpu::Device,
@@ -511,12 +1071,17 @@ pub mod testbed {
                 device: &wgpu::Device,
                 bindings: BindGroupLayout0,
             ) -> Self {
-                let bind_group_layout = device
-                    .create_bind_group_layout(&LAYOUT_DESCRIPTOR0);
+                Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout0,
+                bindings: BindGroupLayout0,
+            ) -> Self {
                 let bind_group = device
                     .create_bind_group(
                         &wgpu::BindGroupDescriptor {
-                            layout: &bind_group_layout,
+                            layout,
                             entries: &[
                                 wgpu::BindGroupEntry {
                                     binding: 0,
@@ -531,13 +1096,16 @@ pub mod testbed {
                                     ),
                                 },
                             ],
-                            label: None,
+                            label: Some("testbed::BindGroup0"),
                         },
                     );
                 Self(bind_group)
             }
-            pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                render_pass.set_bind_group(0, &self.0, &[]);
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(0, &self.0, &[]);
             }
         }
         #[derive(Debug)]
@@ -545,34 +1113,48 @@ pub mod testbed {
         #[allow(non_snake_case)]
         #[derive(Debug)]
         pub struct BindGroupLayout1<'a> {
-            pub ONE: wgpu::BufferBinding<'a>,
-            pub uniforms: wgpu::BufferBinding<'a>,
+            pub ONE: _root::typed_buffer::TypedBufferBinding<'a, f32>,
+            pub uniforms: _root::typed_buffer::TypedBufferBinding<'a, Uniforms>,
         }
         const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
+            label: Some("testbed::BindGroup1::Layout"),
             entries: &[
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::VERTEX,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(4),
                     },
                     count: None,
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(32),
                     },
                     count: None,
                 },
             ],
         };
+        /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout1(wgpu::BindGroupLayout);
+        impl Layout1 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup1::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout1 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
         impl BindGroup1 {
             pub fn get_bind_group_layout(
                 device: &wgpu::Device,
@@ -583,29 +1165,39 @@ pub mod testbed {
                 device: &wgpu::Device,
                 bindings: BindGroupLayout1,
             ) -> Self {
-                let bind_group_layout = device
-                    .create_bind_group_layout(&LAYOUT_DESCRIPTOR1);
+                Self::from_bindings_with_layout(device, &Layout1::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout1,
+                bindings: BindGroupLayout1,
+            ) -> Self {
                 let bind_group = device
                     .create_bind_group(
                         &wgpu::BindGroupDescriptor {
-                            layout: &bind_group_layout,
+                            layout,
                             entries: &[
                                 wgpu::BindGroupEntry {
                                     binding: 1,
-                                    resource: wgpu::BindingResource::Buffer(bindings.ONE),
+                                    resource: wgpu::BindingResource::Buffer(bindings.ONE.into()),
                                 },
                                 wgpu::BindGroupEntry {
                                     binding: 0,
-                                    resource: wgpu::BindingResource::Buffer(bindings.uniforms),
+                                    resource: wgpu::BindingResource::Buffer(
+                                        bindings.uniforms.into(),
+                                    ),
                                 },
                             ],
-                            label: None,
+                            label: Some("testbed::BindGroup1"),
                         },
                     );
                 Self(bind_group)
             }
-            pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                render_pass.set_bind_group(1, &self.0, &[]);
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(1, &self.0, &[]);
             }
         }
         #[derive(Debug)]
@@ -614,115 +1206,135 @@ pub mod testbed {
         #[derive(Debug)]
         pub struct BindGroupLayout2<'a> {
             pub rts: wgpu::BufferBinding<'a>,
-            pub a: wgpu::BufferBinding<'a>,
-            pub b: wgpu::BufferBinding<'a>,
-            pub c: wgpu::BufferBinding<'a>,
-            pub d: wgpu::BufferBinding<'a>,
-            pub f: wgpu::BufferBinding<'a>,
-            pub h: wgpu::BufferBinding<'a>,
-            pub i: wgpu::BufferBinding<'a>,
+            pub a: _root::typed_buffer::TypedBufferBinding<'a, _root::types::Scalars>,
+            pub b: _root::typed_buffer::TypedBufferBinding<'a, _root::types::VectorsU32>,
+            pub c: _root::typed_buffer::TypedBufferBinding<'a, _root::types::VectorsI32>,
+            pub d: _root::typed_buffer::TypedBufferBinding<'a, _root::types::VectorsF32>,
+            pub f: _root::typed_buffer::TypedBufferBinding<
+                'a,
+                _root::types::MatricesF32,
+            >,
+            pub h: _root::typed_buffer::TypedBufferBinding<
+                'a,
+                _root::types::StaticArrays,
+            >,
+            pub i: _root::typed_buffer::TypedBufferBinding<'a, _root::types::Nested>,
         }
         const LAYOUT_DESCRIPTOR2: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
+            label: Some("testbed::BindGroup2::Layout"),
             entries: &[
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::NONE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(8),
                     },
                     count: None,
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 2,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::NONE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(12),
                     },
                     count: None,
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 3,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::NONE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(48),
                     },
                     count: None,
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 4,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::NONE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(48),
                     },
                     count: None,
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 5,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::NONE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(48),
                     },
                     count: None,
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 6,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::NONE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(368),
                     },
                     count: None,
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 8,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::NONE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(32864),
                     },
                     count: None,
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 9,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::NONE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(416),
                     },
                     count: None,
                 },
             ],
         };
+        /// The layout for creating bind groups with [BindGroup2::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout2(wgpu::BindGroupLayout);
+        impl Layout2 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup2::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout2 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
         impl BindGroup2 {
             pub fn get_bind_group_layout(
                 device: &wgpu::Device,
@@ -733,53 +1345,78 @@ pub mod testbed {
                 device: &wgpu::Device,
                 bindings: BindGroupLayout2,
             ) -> Self {
-                let bind_group_layout = device
-                    .create_bind_group_layout(&LAYOUT_DESCRIPTOR2);
+                Self::from_bindings_with_layout(device, &Layout2::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout2,
+                bindings: BindGroupLayout2,
+            ) -> Self {
                 let bind_group = device
                     .create_bind_group(
                         &wgpu::BindGroupDescriptor {
-                            layout: &bind_group_layout,
+                            layout,
                             entries: &[
                                 wgpu::BindGroupEntry {
                                     binding: 1,
-                                    resource: wgpu::BindingResource::Buffer(bindings.rts),
+                                    resource: wgpu::BindingResource::Buffer(bindings.rts.into()),
                                 },
                                 wgpu::BindGroupEntry {
                                     binding: 2,
-                                    resource: wgpu::BindingResource::Buffer(bindings.a),
+                                    resource: wgpu::BindingResource::Buffer(bindings.a.into()),
                                 },
                                 wgpu::BindGroupEntry {
                                     binding: 3,
-                                    resource: wgpu::BindingResource::Buffer(bindings.b),
+                                    resource: wgpu::BindingResource::Buffer(bindings.b.into()),
                                 },
                                 wgpu::BindGroupEntry {
                                     binding: 4,
-                                    resource: wgpu::BindingResource::Buffer(bindings.c),
+                                    resource: wgpu::BindingResource::Buffer(bindings.c.into()),
                                 },
                                 wgpu::BindGroupEntry {
                                     binding: 5,
-                                    resource: wgpu::BindingResource::Buffer(bindings.d),
+                                    resource: wgpu::BindingResource::Buffer(bindings.d.into()),
                                 },
                                 wgpu::BindGroupEntry {
                                     binding: 6,
-                                    resource: wgpu::BindingResource::Buffer(bindings.f),
+                                    resource: wgpu::BindingResource::Buffer(bindings.f.into()),
                                 },
                                 wgpu::BindGroupEntry {
                                     binding: 8,
-                                    resource: wgpu::BindingResource::Buffer(bindings.h),
+                                    resource: wgpu::BindingResource::Buffer(bindings.h.into()),
                                 },
                                 wgpu::BindGroupEntry {
                                     binding: 9,
-                                    resource: wgpu::BindingResource::Buffer(bindings.i),
+                                    resource: wgpu::BindingResource::Buffer(bindings.i.into()),
                                 },
                             ],
-                            label: None,
+                            label: Some("testbed::BindGroup2"),
                         },
                     );
                 Self(bind_group)
             }
-            pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                render_pass.set_bind_group(2, &self.0, &[]);
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(2, &self.0, &[]);
+            }
+        }
+        /// The bind group layouts for this module.
+        /// Create these once and reuse them for bind groups and pipeline layouts.
+        #[derive(Debug)]
+        pub struct Layouts {
+            pub bind_group0: Layout0,
+            pub bind_group1: Layout1,
+            pub bind_group2: Layout2,
+        }
+        impl Layouts {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self {
+                    bind_group0: Layout0::new(device),
+                    bind_group1: Layout1::new(device),
+                    bind_group2: Layout2::new(device),
+                }
             }
         }
         #[derive(Debug, Copy, Clone)]
@@ -789,7 +1426,7 @@ pub mod testbed {
             pub bind_group2: &'a BindGroup2,
         }
         impl<'a> BindGroups<'a> {
-            pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
+            pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
                 self.bind_group0.set(pass);
                 self.bind_group1.set(pass);
                 self.bind_group2.set(pass);
@@ -797,7 +1434,7 @@ pub mod testbed {
         }
     }
     pub fn set_bind_groups<'a>(
-        pass: &mut wgpu::RenderPass<'a>,
+        pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
         bind_group0: &'a bind_groups::BindGroup0,
         bind_group1: &'a bind_groups::BindGroup1,
         bind_group2: &'a bind_groups::BindGroup2,
@@ -847,15 +1484,27 @@ pub mod testbed {
             buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
         }
     }
+    pub fn set_vertex_buffers<'a>(
+        pass: &mut impl _root::pass_ext::SetVertexBuffer<'a>,
+        vertex_input: wgpu::BufferSlice<'a>,
+    ) {
+        pass.set_vertex_buffer(0, vertex_input);
+    }
     pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+    }
+    pub fn create_pipeline_layout_with_layouts(
+        device: &wgpu::Device,
+        layouts: &bind_groups::Layouts,
+    ) -> wgpu::PipelineLayout {
         device
             .create_pipeline_layout(
                 &wgpu::PipelineLayoutDescriptor {
-                    label: None,
+                    label: Some("testbed::PipelineLayout"),
                     bind_group_layouts: &[
-                        &bind_groups::BindGroup0::get_bind_group_layout(device),
-                        &bind_groups::BindGroup1::get_bind_group_layout(device),
-                        &bind_groups::BindGroup2::get_bind_group_layout(device),
+                        &layouts.bind_group0,
+                        &layouts.bind_group1,
+                        &layouts.bind_group2,
                     ],
                     push_constant_ranges: &[],
                 },
@@ -863,7 +1512,8 @@ pub mod testbed {
     }
     pub fn init_composer() -> naga_oil::compose::Composer {
         #[allow(unused_mut)]
-        let mut composer = naga_oil::compose::Composer::default();
+        let mut composer = naga_oil::compose::Composer::default()
+            .with_capabilities(wgpu::naga::valid::Capabilities::all());
         composer
             .add_composable_module(naga_oil::compose::ComposableModuleDescriptor {
                 source: include_str!("shader/../more-shader-files/reachme.wgsl"),
@@ -916,10 +1566,438 @@ pub mod testbed {
         let source = std::borrow::Cow::Owned(source);
         device
             .create_shader_module(wgpu::ShaderModuleDescriptor {
-                label: None,
+                label: Some("testbed::ShaderModule"),
                 source: wgpu::ShaderSource::Wgsl(source),
             })
     }
+    /// Checks that the bindings and struct layouts of `module` match the generated code.
+    /// This catches changes to shaders composed at runtime that would require regenerating the bindings.
+    pub fn verify_module_layout(
+        module: &wgpu::naga::Module,
+    ) -> Result<(), _root::module_layout::LayoutError> {
+        const BINDINGS: &[_root::module_layout::BindingLayout] = &[
+            _root::module_layout::BindingLayout {
+                group: 0,
+                binding: 0,
+                name: "color_texture",
+                kind: _root::module_layout::BindingKind::Texture {
+                    dim: wgpu::naga::ImageDimension::D2,
+                    arrayed: false,
+                    sample_kind: wgpu::naga::ScalarKind::Float,
+                    multisampled: false,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 0,
+                binding: 1,
+                name: "color_sampler",
+                kind: _root::module_layout::BindingKind::Sampler {
+                    comparison: false,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 1,
+                binding: 0,
+                name: "uniforms",
+                kind: _root::module_layout::BindingKind::Uniform {
+                    size: 32,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 1,
+                binding: 1,
+                name: "reachme::ONE",
+                kind: _root::module_layout::BindingKind::Uniform {
+                    size: 4,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 2,
+                binding: 1,
+                name: "rts",
+                kind: _root::module_layout::BindingKind::Storage {
+                    read_only: true,
+                    size: 8,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 2,
+                binding: 2,
+                name: "a",
+                kind: _root::module_layout::BindingKind::Storage {
+                    read_only: true,
+                    size: 12,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 2,
+                binding: 3,
+                name: "b",
+                kind: _root::module_layout::BindingKind::Storage {
+                    read_only: true,
+                    size: 48,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 2,
+                binding: 4,
+                name: "c",
+                kind: _root::module_layout::BindingKind::Storage {
+                    read_only: true,
+                    size: 48,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 2,
+                binding: 5,
+                name: "d",
+                kind: _root::module_layout::BindingKind::Storage {
+                    read_only: true,
+                    size: 48,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 2,
+                binding: 6,
+                name: "f",
+                kind: _root::module_layout::BindingKind::Storage {
+                    read_only: true,
+                    size: 368,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 2,
+                binding: 8,
+                name: "h",
+                kind: _root::module_layout::BindingKind::Storage {
+                    read_only: true,
+                    size: 32864,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 2,
+                binding: 9,
+                name: "i",
+                kind: _root::module_layout::BindingKind::Storage {
+                    read_only: true,
+                    size: 416,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+        ];
+        const STRUCTS: &[_root::module_layout::StructLayout] = &[
+            _root::module_layout::StructLayout {
+                name: "reachme::RtsStruct",
+                naga_name: "RtsStructX_naga_oil_mod_XEIXC4L3NN5ZGKLLTNBQWIZLSFVTGS3DFOMXXEZLBMNUG2ZJCX",
+                size: 8,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "other_data",
+                        offset: 0,
+                        size: 4,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "the_array",
+                        offset: 4,
+                        size: 4,
+                    },
+                ],
+            },
+            _root::module_layout::StructLayout {
+                name: "types::Scalars",
+                naga_name: "ScalarsX_naga_oil_mod_XOR4XAZLTX",
+                size: 12,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "a",
+                        offset: 0,
+                        size: 4,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "b",
+                        offset: 4,
+                        size: 4,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "c",
+                        offset: 8,
+                        size: 4,
+                    },
+                ],
+            },
+            _root::module_layout::StructLayout {
+                name: "types::VectorsU32",
+                naga_name: "VectorsU32X_naga_oil_mod_XOR4XAZLTX",
+                size: 48,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "a",
+                        offset: 0,
+                        size: 8,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "b",
+                        offset: 16,
+                        size: 12,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "c",
+                        offset: 32,
+                        size: 16,
+                    },
+                ],
+            },
+            _root::module_layout::StructLayout {
+                name: "types::VectorsI32",
+                naga_name: "VectorsI32X_naga_oil_mod_XOR4XAZLTX",
+                size: 48,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "a",
+                        offset: 0,
+                        size: 8,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "b",
+                        offset: 16,
+                        size: 12,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "c",
+                        offset: 32,
+                        size: 16,
+                    },
+                ],
+            },
+            _root::module_layout::StructLayout {
+                name: "types::VectorsF32",
+                naga_name: "VectorsF32X_naga_oil_mod_XOR4XAZLTX",
+                size: 48,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "a",
+                        offset: 0,
+                        size: 8,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "b",
+                        offset: 16,
+                        size: 12,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "c",
+                        offset: 32,
+                        size: 16,
+                    },
+                ],
+            },
+            _root::module_layout::StructLayout {
+                name: "types::MatricesF32",
+                naga_name: "MatricesF32X_naga_oil_mod_XOR4XAZLTX",
+                size: 368,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "a",
+                        offset: 0,
+                        size: 64,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "b",
+                        offset: 64,
+                        size: 64,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "c",
+                        offset: 128,
+                        size: 32,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "d",
+                        offset: 160,
+                        size: 48,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "e",
+                        offset: 208,
+                        size: 48,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "f",
+                        offset: 256,
+                        size: 24,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "g",
+                        offset: 288,
+                        size: 32,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "h",
+                        offset: 320,
+                        size: 32,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "i",
+                        offset: 352,
+                        size: 16,
+                    },
+                ],
+            },
+            _root::module_layout::StructLayout {
+                name: "types::StaticArrays",
+                naga_name: "StaticArraysX_naga_oil_mod_XOR4XAZLTX",
+                size: 32864,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "a",
+                        offset: 0,
+                        size: 20,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "b",
+                        offset: 20,
+                        size: 12,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "c",
+                        offset: 32,
+                        size: 32768,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "d",
+                        offset: 32800,
+                        size: 64,
+                    },
+                ],
+            },
+            _root::module_layout::StructLayout {
+                name: "types::Nested",
+                naga_name: "NestedX_naga_oil_mod_XOR4XAZLTX",
+                size: 416,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "a",
+                        offset: 0,
+                        size: 368,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "b",
+                        offset: 368,
+                        size: 48,
+                    },
+                ],
+            },
+            _root::module_layout::StructLayout {
+                name: "Uniforms",
+                naga_name: "Uniforms",
+                size: 32,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "color_rgb",
+                        offset: 0,
+                        size: 16,
+                    },
+                    _root::module_layout::MemberLayout {
+                        name: "scalars",
+                        offset: 16,
+                        size: 12,
+                    },
+                ],
+            },
+        ];
+        _root::module_layout::verify_module_layout(module, BINDINGS, STRUCTS)
+    }
+    /// The paths of the entry and its dependencies for reloading the shader module.
+    #[cfg(debug_assertions)]
+    pub const SHADER_PATHS: &[&str] = &[
+        concat!(env!("CARGO_MANIFEST_DIR"), "/", "src/shader/testbed.wgsl"),
+        concat!(
+            env!("CARGO_MANIFEST_DIR"), "/",
+            "src/shader/../more-shader-files/reachme.wgsl"
+        ),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/", "src/shader/types.wgsl"),
+    ];
+    /// Creates a watcher for changes to the files in [SHADER_PATHS].
+    #[cfg(debug_assertions)]
+    pub fn shader_watcher() -> _root::hot_reload::ShaderWatcher {
+        _root::hot_reload::ShaderWatcher::new(SHADER_PATHS)
+    }
+    /// Creates the shader module from the current sources on disk instead of the
+    /// sources included at compile time.
+    /// The module is checked with [verify_module_layout] before creating the shader module.
+    #[cfg(debug_assertions)]
+    pub fn reload_shader_module(
+        device: &wgpu::Device,
+    ) -> Result<wgpu::ShaderModule, _root::hot_reload::ShaderReloadError> {
+        let mut composer = naga_oil::compose::Composer::default()
+            .with_capabilities(wgpu::naga::valid::Capabilities::all());
+        let path = SHADER_PATHS[1];
+        let source = _root::hot_reload::read_shader_source(path)?;
+        if let Err(e) = composer
+            .add_composable_module(naga_oil::compose::ComposableModuleDescriptor {
+                source: &source,
+                file_path: path,
+                language: naga_oil::compose::ShaderLanguage::Wgsl,
+                as_name: Some("\"../more-shader-files/reachme\"".into()),
+                ..Default::default()
+            })
+        {
+            return Err(
+                _root::hot_reload::ShaderReloadError::Compose(
+                    e.emit_to_string(&composer),
+                ),
+            );
+        }
+        let path = SHADER_PATHS[2];
+        let source = _root::hot_reload::read_shader_source(path)?;
+        if let Err(e) = composer
+            .add_composable_module(naga_oil::compose::ComposableModuleDescriptor {
+                source: &source,
+                file_path: path,
+                language: naga_oil::compose::ShaderLanguage::Wgsl,
+                as_name: Some("types".into()),
+                ..Default::default()
+            })
+        {
+            return Err(
+                _root::hot_reload::ShaderReloadError::Compose(
+                    e.emit_to_string(&composer),
+                ),
+            );
+        }
+        let path = SHADER_PATHS[0];
+        let source = _root::hot_reload::read_shader_source(path)?;
+        let module = composer
+            .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                source: &source,
+                file_path: path,
+                ..Default::default()
+            })
+            .map_err(|e| _root::hot_reload::ShaderReloadError::Compose(
+                e.emit_to_string(&composer),
+            ))?;
+        verify_module_layout(&module)
+            .map_err(_root::hot_reload::ShaderReloadError::Layout)?;
+        let source = naga_module_to_string(&module);
+        Ok(
+            device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("testbed::ShaderModule"),
+                    source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(source)),
+                }),
+        )
+    }
 }
 pub mod triangle {
     #[allow(unused_imports)]
@@ -954,6 +2032,8 @@ pub mod triangle {
     unsafe impl bytemuck::Zeroable for VertexInput {}
     unsafe impl bytemuck::Pod for VertexInput {}
     pub mod bind_groups {
+        #[allow(unused_imports)]
+        use super::*;
         #[derive(Debug)]
         pub struct BindGroup0(wgpu::BindGroup);
         #[allow(non_snake_case)]
@@ -963,11 +2043,11 @@ pub mod triangle {
             pub color_sampler: &'a wgpu::Sampler,
         }
         const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
+            label: Some("triangle::BindGroup0::Layout"),
             entries: &[
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Texture {
                         sample_type: wgpu::TextureSampleType::Float {
                             filterable: true,
@@ -979,12 +2059,26 @@ pub mod triangle {
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
             ],
         };
+        /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout0(wgpu::BindGroupLayout);
+        impl Layout0 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup0::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout0 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
         impl BindGroup0 {
             pub fn get_bind_group_layout(
                 device: &wgpu::Device,
@@ -995,12 +2089,17 @@ pub mod triangle {
                 device: &wgpu::Device,
                 bindings: BindGroupLayout0,
             ) -> Self {
-                let bind_group_layout = device
-                    .create_bind_group_layout(&LAYOUT_DESCRIPTOR0);
+                Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout0,
+                bindings: BindGroupLayout0,
+            ) -> Self {
                 let bind_group = device
                     .create_bind_group(
                         &wgpu::BindGroupDescriptor {
-                            layout: &bind_group_layout,
+                            layout,
                             entries: &[
                                 wgpu::BindGroupEntry {
                                     binding: 0,
@@ -1015,13 +2114,16 @@ pub mod triangle {
                                     ),
                                 },
                             ],
-                            label: None,
+                            label: Some("triangle::BindGroup0"),
                         },
                     );
                 Self(bind_group)
             }
-            pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                render_pass.set_bind_group(0, &self.0, &[]);
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(0, &self.0, &[]);
             }
         }
         #[derive(Debug)]
@@ -1029,23 +2131,37 @@ pub mod triangle {
         #[allow(non_snake_case)]
         #[derive(Debug)]
         pub struct BindGroupLayout1<'a> {
-            pub uniforms: wgpu::BufferBinding<'a>,
+            pub uniforms: _root::typed_buffer::TypedBufferBinding<'a, Uniforms>,
         }
         const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
+            label: Some("triangle::BindGroup1::Layout"),
             entries: &[
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(16),
                     },
                     count: None,
                 },
             ],
         };
+        /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout1(wgpu::BindGroupLayout);
+        impl Layout1 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup1::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout1 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
         impl BindGroup1 {
             pub fn get_bind_group_layout(
                 device: &wgpu::Device,
@@ -1056,25 +2172,50 @@ pub mod triangle {
                 device: &wgpu::Device,
                 bindings: BindGroupLayout1,
             ) -> Self {
-                let bind_group_layout = device
-                    .create_bind_group_layout(&LAYOUT_DESCRIPTOR1);
+                Self::from_bindings_with_layout(device, &Layout1::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout1,
+                bindings: BindGroupLayout1,
+            ) -> Self {
                 let bind_group = device
                     .create_bind_group(
                         &wgpu::BindGroupDescriptor {
-                            layout: &bind_group_layout,
+                            layout,
                             entries: &[
                                 wgpu::BindGroupEntry {
                                     binding: 0,
-                                    resource: wgpu::BindingResource::Buffer(bindings.uniforms),
+                                    resource: wgpu::BindingResource::Buffer(
+                                        bindings.uniforms.into(),
+                                    ),
                                 },
                             ],
-                            label: None,
+                            label: Some("triangle::BindGroup1"),
                         },
                     );
                 Self(bind_group)
             }
-            pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                render_pass.set_bind_group(1, &self.0, &[]);
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(1, &self.0, &[]);
+            }
+        }
+        /// The bind group layouts for this module.
+        /// Create these once and reuse them for bind groups and pipeline layouts.
+        #[derive(Debug)]
+        pub struct Layouts {
+            pub bind_group0: Layout0,
+            pub bind_group1: Layout1,
+        }
+        impl Layouts {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self {
+                    bind_group0: Layout0::new(device),
+                    bind_group1: Layout1::new(device),
+                }
             }
         }
         #[derive(Debug, Copy, Clone)]
@@ -1083,14 +2224,14 @@ pub mod triangle {
             pub bind_group1: &'a BindGroup1,
         }
         impl<'a> BindGroups<'a> {
-            pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
+            pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
                 self.bind_group0.set(pass);
                 self.bind_group1.set(pass);
             }
         }
     }
     pub fn set_bind_groups<'a>(
-        pass: &mut wgpu::RenderPass<'a>,
+        pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
         bind_group0: &'a bind_groups::BindGroup0,
         bind_group1: &'a bind_groups::BindGroup1,
     ) {
@@ -1138,31 +2279,62 @@ pub mod triangle {
             buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
         }
     }
+    pub fn set_vertex_buffers<'a>(
+        pass: &mut impl _root::pass_ext::SetVertexBuffer<'a>,
+        vertex_input: wgpu::BufferSlice<'a>,
+    ) {
+        pass.set_vertex_buffer(0, vertex_input);
+    }
     pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+    }
+    pub fn create_pipeline_layout_with_layouts(
+        device: &wgpu::Device,
+        layouts: &bind_groups::Layouts,
+    ) -> wgpu::PipelineLayout {
         device
             .create_pipeline_layout(
                 &wgpu::PipelineLayoutDescriptor {
-                    label: None,
-                    bind_group_layouts: &[
-                        &bind_groups::BindGroup0::get_bind_group_layout(device),
-                        &bind_groups::BindGroup1::get_bind_group_layout(device),
-                    ],
+                    label: Some("triangle::PipelineLayout"),
+                    bind_group_layouts: &[&layouts.bind_group0, &layouts.bind_group1],
                     push_constant_ranges: &[],
                 },
             )
     }
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct ShaderDefs {
+        pub grayscale: bool,
+    }
+    impl ShaderDefs {
+        pub fn to_shader_defs(
+            &self,
+        ) -> std::collections::HashMap<String, naga_oil::compose::ShaderDefValue> {
+            let mut shader_defs = std::collections::HashMap::new();
+            if self.grayscale {
+                shader_defs
+                    .insert(
+                        "GRAYSCALE".to_owned(),
+                        naga_oil::compose::ShaderDefValue::Bool(true),
+                    );
+            }
+            shader_defs
+        }
+    }
     pub fn init_composer() -> naga_oil::compose::Composer {
         #[allow(unused_mut)]
-        let mut composer = naga_oil::compose::Composer::default();
+        let mut composer = naga_oil::compose::Composer::default()
+            .with_capabilities(wgpu::naga::valid::Capabilities::all());
         composer
     }
     pub fn make_naga_module(
         composer: &mut naga_oil::compose::Composer,
+        shader_defs: &ShaderDefs,
     ) -> wgpu::naga::Module {
         composer
             .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
                 source: include_str!("shader/triangle.wgsl"),
                 file_path: "shader/triangle.wgsl",
+                shader_defs: shader_defs.to_shader_defs(),
                 ..Default::default()
             })
             .expect("failed to build naga module")
@@ -1181,15 +2353,1223 @@ pub mod triangle {
             )
             .expect("failed to convert naga module to source")
     }
-    pub fn create_shader_module(device: &wgpu::Device) -> wgpu::ShaderModule {
+    /// Composes the shader module with the shader defs.
+    ///
+    /// # Panics
+    /// In debug builds, if the shader defs change the bindings or struct layouts
+    /// of the generated code, which are generated from `ShaderDefs::default()`.
+    pub fn create_shader_module(
+        device: &wgpu::Device,
+        shader_defs: &ShaderDefs,
+    ) -> wgpu::ShaderModule {
         let mut composer = init_composer();
-        let module = make_naga_module(&mut composer);
+        let module = make_naga_module(&mut composer, shader_defs);
+        #[cfg(debug_assertions)]
+        if *shader_defs != ShaderDefs::default() {
+            if let Err(e) = verify_module_layout(&module) {
+                panic!(
+                    "shader defs {shader_defs:?} don't match the generated bindings: {e}"
+                );
+            }
+        }
         let source = naga_module_to_string(&module);
         let source = std::borrow::Cow::Owned(source);
         device
             .create_shader_module(wgpu::ShaderModuleDescriptor {
-                label: None,
+                label: Some("triangle::ShaderModule"),
                 source: wgpu::ShaderSource::Wgsl(source),
             })
     }
+    /// Checks that the bindings and struct layouts of `module` match the generated code.
+    /// This catches changes to shaders composed at runtime that would require regenerating the bindings.
+    pub fn verify_module_layout(
+        module: &wgpu::naga::Module,
+    ) -> Result<(), _root::module_layout::LayoutError> {
+        const BINDINGS: &[_root::module_layout::BindingLayout] = &[
+            _root::module_layout::BindingLayout {
+                group: 0,
+                binding: 0,
+                name: "color_texture",
+                kind: _root::module_layout::BindingKind::Texture {
+                    dim: wgpu::naga::ImageDimension::D2,
+                    arrayed: false,
+                    sample_kind: wgpu::naga::ScalarKind::Float,
+                    multisampled: false,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 0,
+                binding: 1,
+                name: "color_sampler",
+                kind: _root::module_layout::BindingKind::Sampler {
+                    comparison: false,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 1,
+                binding: 0,
+                name: "uniforms",
+                kind: _root::module_layout::BindingKind::Uniform {
+                    size: 16,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+        ];
+        const STRUCTS: &[_root::module_layout::StructLayout] = &[
+            _root::module_layout::StructLayout {
+                name: "Uniforms",
+                naga_name: "Uniforms",
+                size: 16,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "color_rgb",
+                        offset: 0,
+                        size: 16,
+                    },
+                ],
+            },
+        ];
+        _root::module_layout::verify_module_layout(module, BINDINGS, STRUCTS)
+    }
+    /// The paths of the entry and its dependencies for reloading the shader module.
+    #[cfg(debug_assertions)]
+    pub const SHADER_PATHS: &[&str] = &[
+        concat!(env!("CARGO_MANIFEST_DIR"), "/", "src/shader/triangle.wgsl"),
+    ];
+    /// Creates a watcher for changes to the files in [SHADER_PATHS].
+    #[cfg(debug_assertions)]
+    pub fn shader_watcher() -> _root::hot_reload::ShaderWatcher {
+        _root::hot_reload::ShaderWatcher::new(SHADER_PATHS)
+    }
+    /// Creates the shader module from the current sources on disk instead of the
+    /// sources included at compile time.
+    /// The module is checked with [verify_module_layout] before creating the shader module.
+    #[cfg(debug_assertions)]
+    pub fn reload_shader_module(
+        device: &wgpu::Device,
+        shader_defs: &ShaderDefs,
+    ) -> Result<wgpu::ShaderModule, _root::hot_reload::ShaderReloadError> {
+        let mut composer = naga_oil::compose::Composer::default()
+            .with_capabilities(wgpu::naga::valid::Capabilities::all());
+        let path = SHADER_PATHS[0];
+        let source = _root::hot_reload::read_shader_source(path)?;
+        let module = composer
+            .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                source: &source,
+                file_path: path,
+                shader_defs: shader_defs.to_shader_defs(),
+                ..Default::default()
+            })
+            .map_err(|e| _root::hot_reload::ShaderReloadError::Compose(
+                e.emit_to_string(&composer),
+            ))?;
+        verify_module_layout(&module)
+            .map_err(_root::hot_reload::ShaderReloadError::Layout)?;
+        let source = naga_module_to_string(&module);
+        Ok(
+            device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("triangle::ShaderModule"),
+                    source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(source)),
+                }),
+        )
+    }
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Permutation {
+        Default,
+        Grayscale,
+    }
+    impl Permutation {
+        pub const ALL: [Self; 2] = [Self::Default, Self::Grayscale];
+        pub fn create_shader_module(
+            self,
+            device: &wgpu::Device,
+            shader_defs: &ShaderDefs,
+        ) -> wgpu::ShaderModule {
+            match self {
+                Self::Default => create_shader_module(device, shader_defs),
+                Self::Grayscale => grayscale::create_shader_module(device, shader_defs),
+            }
+        }
+        pub fn create_pipeline_layout(
+            self,
+            device: &wgpu::Device,
+        ) -> wgpu::PipelineLayout {
+            match self {
+                Self::Default => create_pipeline_layout(device),
+                Self::Grayscale => grayscale::create_pipeline_layout(device),
+            }
+        }
+    }
+    pub mod grayscale {
+        #[allow(unused_imports)]
+        use super::{_root, _root::*};
+        pub use super::Uniforms;
+        pub use super::VertexInput;
+        pub mod bind_groups {
+            #[allow(unused_imports)]
+            use super::*;
+            #[derive(Debug)]
+            pub struct BindGroup0(wgpu::BindGroup);
+            #[allow(non_snake_case)]
+            #[derive(Debug)]
+            pub struct BindGroupLayout0<'a> {
+                pub color_texture: &'a wgpu::TextureView,
+                pub color_sampler: &'a wgpu::Sampler,
+            }
+            const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                label: Some("triangle::grayscale::BindGroup0::Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float {
+                                filterable: true,
+                            },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(
+                            wgpu::SamplerBindingType::Filtering,
+                        ),
+                        count: None,
+                    },
+                ],
+            };
+            /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+            #[derive(Debug)]
+            pub struct Layout0(wgpu::BindGroupLayout);
+            impl Layout0 {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self(BindGroup0::get_bind_group_layout(device))
+                }
+            }
+            impl std::ops::Deref for Layout0 {
+                type Target = wgpu::BindGroupLayout;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+            impl BindGroup0 {
+                pub fn get_bind_group_layout(
+                    device: &wgpu::Device,
+                ) -> wgpu::BindGroupLayout {
+                    device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+                }
+                pub fn from_bindings(
+                    device: &wgpu::Device,
+                    bindings: BindGroupLayout0,
+                ) -> Self {
+                    Self::from_bindings_with_layout(
+                        device,
+                        &Layout0::new(device),
+                        bindings,
+                    )
+                }
+                pub fn from_bindings_with_layout(
+                    device: &wgpu::Device,
+                    layout: &Layout0,
+                    bindings: BindGroupLayout0,
+                ) -> Self {
+                    let bind_group = device
+                        .create_bind_group(
+                            &wgpu::BindGroupDescriptor {
+                                layout,
+                                entries: &[
+                                    wgpu::BindGroupEntry {
+                                        binding: 0,
+                                        resource: wgpu::BindingResource::TextureView(
+                                            bindings.color_texture,
+                                        ),
+                                    },
+                                    wgpu::BindGroupEntry {
+                                        binding: 1,
+                                        resource: wgpu::BindingResource::Sampler(
+                                            bindings.color_sampler,
+                                        ),
+                                    },
+                                ],
+                                label: Some("triangle::grayscale::BindGroup0"),
+                            },
+                        );
+                    Self(bind_group)
+                }
+                pub fn set<'a>(
+                    &'a self,
+                    pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                ) {
+                    pass.set_bind_group(0, &self.0, &[]);
+                }
+            }
+            #[derive(Debug)]
+            pub struct BindGroup1(wgpu::BindGroup);
+            #[allow(non_snake_case)]
+            #[derive(Debug)]
+            pub struct BindGroupLayout1<'a> {
+                pub uniforms: _root::typed_buffer::TypedBufferBinding<'a, Uniforms>,
+            }
+            const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                label: Some("triangle::grayscale::BindGroup1::Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(16),
+                        },
+                        count: None,
+                    },
+                ],
+            };
+            /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+            #[derive(Debug)]
+            pub struct Layout1(wgpu::BindGroupLayout);
+            impl Layout1 {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self(BindGroup1::get_bind_group_layout(device))
+                }
+            }
+            impl std::ops::Deref for Layout1 {
+                type Target = wgpu::BindGroupLayout;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+            impl BindGroup1 {
+                pub fn get_bind_group_layout(
+                    device: &wgpu::Device,
+                ) -> wgpu::BindGroupLayout {
+                    device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
+                }
+                pub fn from_bindings(
+                    device: &wgpu::Device,
+                    bindings: BindGroupLayout1,
+                ) -> Self {
+                    Self::from_bindings_with_layout(
+                        device,
+                        &Layout1::new(device),
+                        bindings,
+                    )
+                }
+                pub fn from_bindings_with_layout(
+                    device: &wgpu::Device,
+                    layout: &Layout1,
+                    bindings: BindGroupLayout1,
+                ) -> Self {
+                    let bind_group = device
+                        .create_bind_group(
+                            &wgpu::BindGroupDescriptor {
+                                layout,
+                                entries: &[
+                                    wgpu::BindGroupEntry {
+                                        binding: 0,
+                                        resource: wgpu::BindingResource::Buffer(
+                                            bindings.uniforms.into(),
+                                        ),
+                                    },
+                                ],
+                                label: Some("triangle::grayscale::BindGroup1"),
+                            },
+                        );
+                    Self(bind_group)
+                }
+                pub fn set<'a>(
+                    &'a self,
+                    pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                ) {
+                    pass.set_bind_group(1, &self.0, &[]);
+                }
+            }
+            /// The bind group layouts for this module.
+            /// Create these once and reuse them for bind groups and pipeline layouts.
+            #[derive(Debug)]
+            pub struct Layouts {
+                pub bind_group0: Layout0,
+                pub bind_group1: Layout1,
+            }
+            impl Layouts {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self {
+                        bind_group0: Layout0::new(device),
+                        bind_group1: Layout1::new(device),
+                    }
+                }
+            }
+            #[derive(Debug, Copy, Clone)]
+            pub struct BindGroups<'a> {
+                pub bind_group0: &'a BindGroup0,
+                pub bind_group1: &'a BindGroup1,
+            }
+            impl<'a> BindGroups<'a> {
+                pub fn set(
+                    &self,
+                    pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                ) {
+                    self.bind_group0.set(pass);
+                    self.bind_group1.set(pass);
+                }
+            }
+        }
+        pub fn set_bind_groups<'a>(
+            pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+            bind_group0: &'a bind_groups::BindGroup0,
+            bind_group1: &'a bind_groups::BindGroup1,
+        ) {
+            bind_group0.set(pass);
+            bind_group1.set(pass);
+        }
+        pub const ENTRY_VS_MAIN: &str = "vs_main";
+        pub const ENTRY_FS_MAIN: &str = "fs_main";
+        #[derive(Debug)]
+        pub struct VertexEntry<const N: usize> {
+            entry_point: &'static str,
+            buffers: [wgpu::VertexBufferLayout<'static>; N],
+        }
+        pub fn vertex_state<'a, const N: usize>(
+            module: &'a wgpu::ShaderModule,
+            entry: &'a VertexEntry<N>,
+        ) -> wgpu::VertexState<'a> {
+            wgpu::VertexState {
+                module,
+                entry_point: entry.entry_point,
+                buffers: &entry.buffers,
+            }
+        }
+        pub fn vs_main_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<1> {
+            VertexEntry {
+                entry_point: ENTRY_VS_MAIN,
+                buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
+            }
+        }
+        pub fn set_vertex_buffers<'a>(
+            pass: &mut impl _root::pass_ext::SetVertexBuffer<'a>,
+            vertex_input: wgpu::BufferSlice<'a>,
+        ) {
+            pass.set_vertex_buffer(0, vertex_input);
+        }
+        pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+            create_pipeline_layout_with_layouts(
+                device,
+                &bind_groups::Layouts::new(device),
+            )
+        }
+        pub fn create_pipeline_layout_with_layouts(
+            device: &wgpu::Device,
+            layouts: &bind_groups::Layouts,
+        ) -> wgpu::PipelineLayout {
+            device
+                .create_pipeline_layout(
+                    &wgpu::PipelineLayoutDescriptor {
+                        label: Some("triangle::grayscale::PipelineLayout"),
+                        bind_group_layouts: &[
+                            &layouts.bind_group0,
+                            &layouts.bind_group1,
+                        ],
+                        push_constant_ranges: &[],
+                    },
+                )
+        }
+        pub fn init_composer() -> naga_oil::compose::Composer {
+            #[allow(unused_mut)]
+            let mut composer = naga_oil::compose::Composer::default()
+                .with_capabilities(wgpu::naga::valid::Capabilities::all());
+            composer
+        }
+        pub fn make_naga_module(
+            composer: &mut naga_oil::compose::Composer,
+            shader_defs: &super::ShaderDefs,
+        ) -> wgpu::naga::Module {
+            composer
+                .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                    source: include_str!("shader/triangle.wgsl"),
+                    file_path: "shader/triangle.wgsl",
+                    shader_defs: {
+                        let mut shader_defs = shader_defs.to_shader_defs();
+                        shader_defs
+                            .insert(
+                                "GRAYSCALE".to_owned(),
+                                naga_oil::compose::ShaderDefValue::Bool(true),
+                            );
+                        shader_defs
+                    },
+                    ..Default::default()
+                })
+                .expect("failed to build naga module")
+        }
+        pub fn naga_module_to_string(module: &wgpu::naga::Module) -> String {
+            let info = wgpu::naga::valid::Validator::new(
+                    wgpu::naga::valid::ValidationFlags::empty(),
+                    wgpu::naga::valid::Capabilities::all(),
+                )
+                .validate(&module);
+            let info = info.unwrap();
+            wgpu::naga::back::wgsl::write_string(
+                    &module,
+                    &info,
+                    wgpu::naga::back::wgsl::WriterFlags::empty(),
+                )
+                .expect("failed to convert naga module to source")
+        }
+        /// Composes the shader module with the shader defs.
+        ///
+        /// # Panics
+        /// In debug builds, if the shader defs change the bindings or struct layouts
+        /// of the generated code, which are generated from `ShaderDefs::default()`.
+        pub fn create_shader_module(
+            device: &wgpu::Device,
+            shader_defs: &super::ShaderDefs,
+        ) -> wgpu::ShaderModule {
+            let mut composer = init_composer();
+            let module = make_naga_module(&mut composer, shader_defs);
+            #[cfg(debug_assertions)]
+            if *shader_defs != super::ShaderDefs::default() {
+                if let Err(e) = verify_module_layout(&module) {
+                    panic!(
+                        "shader defs {shader_defs:?} don't match the generated bindings: {e}"
+                    );
+                }
+            }
+            let source = naga_module_to_string(&module);
+            let source = std::borrow::Cow::Owned(source);
+            device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("triangle::grayscale::ShaderModule"),
+                    source: wgpu::ShaderSource::Wgsl(source),
+                })
+        }
+        /// Checks that the bindings and struct layouts of `module` match the generated code.
+        /// This catches changes to shaders composed at runtime that would require regenerating the bindings.
+        pub fn verify_module_layout(
+            module: &wgpu::naga::Module,
+        ) -> Result<(), _root::module_layout::LayoutError> {
+            const BINDINGS: &[_root::module_layout::BindingLayout] = &[
+                _root::module_layout::BindingLayout {
+                    group: 0,
+                    binding: 0,
+                    name: "color_texture",
+                    kind: _root::module_layout::BindingKind::Texture {
+                        dim: wgpu::naga::ImageDimension::D2,
+                        arrayed: false,
+                        sample_kind: wgpu::naga::ScalarKind::Float,
+                        multisampled: false,
+                    },
+                    count: _root::module_layout::BindingCount::Single,
+                },
+                _root::module_layout::BindingLayout {
+                    group: 0,
+                    binding: 1,
+                    name: "color_sampler",
+                    kind: _root::module_layout::BindingKind::Sampler {
+                        comparison: false,
+                    },
+                    count: _root::module_layout::BindingCount::Single,
+                },
+                _root::module_layout::BindingLayout {
+                    group: 1,
+                    binding: 0,
+                    name: "uniforms",
+                    kind: _root::module_layout::BindingKind::Uniform {
+                        size: 16,
+                    },
+                    count: _root::module_layout::BindingCount::Single,
+                },
+            ];
+            const STRUCTS: &[_root::module_layout::StructLayout] = &[
+                _root::module_layout::StructLayout {
+                    name: "Uniforms",
+                    naga_name: "Uniforms",
+                    size: 16,
+                    members: &[
+                        _root::module_layout::MemberLayout {
+                            name: "color_rgb",
+                            offset: 0,
+                            size: 16,
+                        },
+                    ],
+                },
+            ];
+            _root::module_layout::verify_module_layout(module, BINDINGS, STRUCTS)
+        }
+        /// The paths of the entry and its dependencies for reloading the shader module.
+        #[cfg(debug_assertions)]
+        pub const SHADER_PATHS: &[&str] = &[
+            concat!(env!("CARGO_MANIFEST_DIR"), "/", "src/shader/triangle.wgsl"),
+        ];
+        /// Creates a watcher for changes to the files in [SHADER_PATHS].
+        #[cfg(debug_assertions)]
+        pub fn shader_watcher() -> _root::hot_reload::ShaderWatcher {
+            _root::hot_reload::ShaderWatcher::new(SHADER_PATHS)
+        }
+        /// Creates the shader module from the current sources on disk instead of the
+        /// sources included at compile time.
+        /// The module is checked with [verify_module_layout] before creating the shader module.
+        #[cfg(debug_assertions)]
+        pub fn reload_shader_module(
+            device: &wgpu::Device,
+            shader_defs: &super::ShaderDefs,
+        ) -> Result<wgpu::ShaderModule, _root::hot_reload::ShaderReloadError> {
+            let mut composer = naga_oil::compose::Composer::default()
+                .with_capabilities(wgpu::naga::valid::Capabilities::all());
+            let path = SHADER_PATHS[0];
+            let source = _root::hot_reload::read_shader_source(path)?;
+            let module = composer
+                .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                    source: &source,
+                    file_path: path,
+                    shader_defs: {
+                        let mut shader_defs = shader_defs.to_shader_defs();
+                        shader_defs
+                            .insert(
+                                "GRAYSCALE".to_owned(),
+                                naga_oil::compose::ShaderDefValue::Bool(true),
+                            );
+                        shader_defs
+                    },
+                    ..Default::default()
+                })
+                .map_err(|e| _root::hot_reload::ShaderReloadError::Compose(
+                    e.emit_to_string(&composer),
+                ))?;
+            verify_module_layout(&module)
+                .map_err(_root::hot_reload::ShaderReloadError::Layout)?;
+            let source = naga_module_to_string(&module);
+            Ok(
+                device
+                    .create_shader_module(wgpu::ShaderModuleDescriptor {
+                        label: Some("triangle::grayscale::ShaderModule"),
+                        source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(source)),
+                    }),
+            )
+        }
+    }
+}
+pub mod brightness {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    pub mod bind_groups {
+        #[allow(unused_imports)]
+        use super::*;
+        #[derive(Debug)]
+        pub struct BindGroup0(wgpu::BindGroup);
+        #[allow(non_snake_case)]
+        #[derive(Debug)]
+        pub struct BindGroupLayout0<'a> {
+            pub values: _root::typed_buffer::TypedBufferBinding<'a, [f32]>,
+        }
+        const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some("brightness::BindGroup0::Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage {
+                            read_only: false,
+                        },
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(4),
+                    },
+                    count: None,
+                },
+            ],
+        };
+        /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout0(wgpu::BindGroupLayout);
+        impl Layout0 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup0::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout0 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        impl BindGroup0 {
+            pub fn get_bind_group_layout(
+                device: &wgpu::Device,
+            ) -> wgpu::BindGroupLayout {
+                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+            }
+            pub fn from_bindings(
+                device: &wgpu::Device,
+                bindings: BindGroupLayout0,
+            ) -> Self {
+                Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout0,
+                bindings: BindGroupLayout0,
+            ) -> Self {
+                let bind_group = device
+                    .create_bind_group(
+                        &wgpu::BindGroupDescriptor {
+                            layout,
+                            entries: &[
+                                wgpu::BindGroupEntry {
+                                    binding: 0,
+                                    resource: wgpu::BindingResource::Buffer(
+                                        bindings.values.into(),
+                                    ),
+                                },
+                            ],
+                            label: Some("brightness::BindGroup0"),
+                        },
+                    );
+                Self(bind_group)
+            }
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(0, &self.0, &[]);
+            }
+        }
+        /// The bind group layouts for this module.
+        /// Create these once and reuse them for bind groups and pipeline layouts.
+        #[derive(Debug)]
+        pub struct Layouts {
+            pub bind_group0: Layout0,
+        }
+        impl Layouts {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self {
+                    bind_group0: Layout0::new(device),
+                }
+            }
+        }
+        #[derive(Debug, Copy, Clone)]
+        pub struct BindGroups<'a> {
+            pub bind_group0: &'a BindGroup0,
+        }
+        impl<'a> BindGroups<'a> {
+            pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                self.bind_group0.set(pass);
+            }
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+        bind_group0: &'a bind_groups::BindGroup0,
+    ) {
+        bind_group0.set(pass);
+    }
+    pub mod compute {
+        pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [64, 1, 1];
+        pub fn create_main_pipeline(
+            device: &wgpu::Device,
+            shader_defs: &super::ShaderDefs,
+        ) -> wgpu::ComputePipeline {
+            let module = super::create_shader_module(device, shader_defs);
+            let layout = super::create_pipeline_layout(device);
+            device
+                .create_compute_pipeline(
+                    &wgpu::ComputePipelineDescriptor {
+                        label: Some("brightness::ComputePipeline::main"),
+                        layout: Some(&layout),
+                        module: &module,
+                        entry_point: "main",
+                    },
+                )
+        }
+    }
+    pub const ENTRY_MAIN: &str = "main";
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+    }
+    pub fn create_pipeline_layout_with_layouts(
+        device: &wgpu::Device,
+        layouts: &bind_groups::Layouts,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("brightness::PipelineLayout"),
+                    bind_group_layouts: &[&layouts.bind_group0],
+                    push_constant_ranges: &[],
+                },
+            )
+    }
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct ShaderDefs {
+        pub clamped: bool,
+    }
+    impl ShaderDefs {
+        pub fn to_shader_defs(
+            &self,
+        ) -> std::collections::HashMap<String, naga_oil::compose::ShaderDefValue> {
+            let mut shader_defs = std::collections::HashMap::new();
+            if self.clamped {
+                shader_defs
+                    .insert(
+                        "CLAMPED".to_owned(),
+                        naga_oil::compose::ShaderDefValue::Bool(true),
+                    );
+            }
+            shader_defs
+        }
+    }
+    pub fn init_composer() -> naga_oil::compose::Composer {
+        #[allow(unused_mut)]
+        let mut composer = naga_oil::compose::Composer::default()
+            .with_capabilities(wgpu::naga::valid::Capabilities::all());
+        composer
+    }
+    pub fn make_naga_module(
+        composer: &mut naga_oil::compose::Composer,
+        shader_defs: &ShaderDefs,
+    ) -> wgpu::naga::Module {
+        composer
+            .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                source: include_str!("shader/brightness.wgsl"),
+                file_path: "shader/brightness.wgsl",
+                shader_defs: shader_defs.to_shader_defs(),
+                ..Default::default()
+            })
+            .expect("failed to build naga module")
+    }
+    pub fn naga_module_to_string(module: &wgpu::naga::Module) -> String {
+        let info = wgpu::naga::valid::Validator::new(
+                wgpu::naga::valid::ValidationFlags::empty(),
+                wgpu::naga::valid::Capabilities::all(),
+            )
+            .validate(&module);
+        let info = info.unwrap();
+        wgpu::naga::back::wgsl::write_string(
+                &module,
+                &info,
+                wgpu::naga::back::wgsl::WriterFlags::empty(),
+            )
+            .expect("failed to convert naga module to source")
+    }
+    /// Composes the shader module with the shader defs.
+    ///
+    /// # Panics
+    /// In debug builds, if the shader defs change the bindings or struct layouts
+    /// of the generated code, which are generated from `ShaderDefs::default()`.
+    pub fn create_shader_module(
+        device: &wgpu::Device,
+        shader_defs: &ShaderDefs,
+    ) -> wgpu::ShaderModule {
+        let mut composer = init_composer();
+        let module = make_naga_module(&mut composer, shader_defs);
+        #[cfg(debug_assertions)]
+        if *shader_defs != ShaderDefs::default() {
+            if let Err(e) = verify_module_layout(&module) {
+                panic!(
+                    "shader defs {shader_defs:?} don't match the generated bindings: {e}"
+                );
+            }
+        }
+        let source = naga_module_to_string(&module);
+        let source = std::borrow::Cow::Owned(source);
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("brightness::ShaderModule"),
+                source: wgpu::ShaderSource::Wgsl(source),
+            })
+    }
+    /// Checks that the bindings and struct layouts of `module` match the generated code.
+    /// This catches changes to shaders composed at runtime that would require regenerating the bindings.
+    pub fn verify_module_layout(
+        module: &wgpu::naga::Module,
+    ) -> Result<(), _root::module_layout::LayoutError> {
+        const BINDINGS: &[_root::module_layout::BindingLayout] = &[
+            _root::module_layout::BindingLayout {
+                group: 0,
+                binding: 0,
+                name: "values",
+                kind: _root::module_layout::BindingKind::Storage {
+                    read_only: false,
+                    size: 4,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+        ];
+        const STRUCTS: &[_root::module_layout::StructLayout] = &[];
+        _root::module_layout::verify_module_layout(module, BINDINGS, STRUCTS)
+    }
+    /// The paths of the entry and its dependencies for reloading the shader module.
+    #[cfg(debug_assertions)]
+    pub const SHADER_PATHS: &[&str] = &[
+        concat!(env!("CARGO_MANIFEST_DIR"), "/", "src/shader/brightness.wgsl"),
+    ];
+    /// Creates a watcher for changes to the files in [SHADER_PATHS].
+    #[cfg(debug_assertions)]
+    pub fn shader_watcher() -> _root::hot_reload::ShaderWatcher {
+        _root::hot_reload::ShaderWatcher::new(SHADER_PATHS)
+    }
+    /// Creates the shader module from the current sources on disk instead of the
+    /// sources included at compile time.
+    /// The module is checked with [verify_module_layout] before creating the shader module.
+    #[cfg(debug_assertions)]
+    pub fn reload_shader_module(
+        device: &wgpu::Device,
+        shader_defs: &ShaderDefs,
+    ) -> Result<wgpu::ShaderModule, _root::hot_reload::ShaderReloadError> {
+        let mut composer = naga_oil::compose::Composer::default()
+            .with_capabilities(wgpu::naga::valid::Capabilities::all());
+        let path = SHADER_PATHS[0];
+        let source = _root::hot_reload::read_shader_source(path)?;
+        let module = composer
+            .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                source: &source,
+                file_path: path,
+                shader_defs: shader_defs.to_shader_defs(),
+                ..Default::default()
+            })
+            .map_err(|e| _root::hot_reload::ShaderReloadError::Compose(
+                e.emit_to_string(&composer),
+            ))?;
+        verify_module_layout(&module)
+            .map_err(_root::hot_reload::ShaderReloadError::Layout)?;
+        let source = naga_module_to_string(&module);
+        Ok(
+            device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("brightness::ShaderModule"),
+                    source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(source)),
+                }),
+        )
+    }
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Permutation {
+        Default,
+        Clamped,
+    }
+    impl Permutation {
+        pub const ALL: [Self; 2] = [Self::Default, Self::Clamped];
+        pub fn create_shader_module(
+            self,
+            device: &wgpu::Device,
+            shader_defs: &ShaderDefs,
+        ) -> wgpu::ShaderModule {
+            match self {
+                Self::Default => create_shader_module(device, shader_defs),
+                Self::Clamped => clamped::create_shader_module(device, shader_defs),
+            }
+        }
+        pub fn create_pipeline_layout(
+            self,
+            device: &wgpu::Device,
+        ) -> wgpu::PipelineLayout {
+            match self {
+                Self::Default => create_pipeline_layout(device),
+                Self::Clamped => clamped::create_pipeline_layout(device),
+            }
+        }
+    }
+    pub mod clamped {
+        #[allow(unused_imports)]
+        use super::{_root, _root::*};
+        pub mod bind_groups {
+            #[allow(unused_imports)]
+            use super::*;
+            #[derive(Debug)]
+            pub struct BindGroup0(wgpu::BindGroup);
+            #[allow(non_snake_case)]
+            #[derive(Debug)]
+            pub struct BindGroupLayout0<'a> {
+                pub values: _root::typed_buffer::TypedBufferBinding<'a, [f32]>,
+            }
+            const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                label: Some("brightness::clamped::BindGroup0::Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage {
+                                read_only: false,
+                            },
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(4),
+                        },
+                        count: None,
+                    },
+                ],
+            };
+            /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+            #[derive(Debug)]
+            pub struct Layout0(wgpu::BindGroupLayout);
+            impl Layout0 {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self(BindGroup0::get_bind_group_layout(device))
+                }
+            }
+            impl std::ops::Deref for Layout0 {
+                type Target = wgpu::BindGroupLayout;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+            impl BindGroup0 {
+                pub fn get_bind_group_layout(
+                    device: &wgpu::Device,
+                ) -> wgpu::BindGroupLayout {
+                    device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+                }
+                pub fn from_bindings(
+                    device: &wgpu::Device,
+                    bindings: BindGroupLayout0,
+                ) -> Self {
+                    Self::from_bindings_with_layout(
+                        device,
+                        &Layout0::new(device),
+                        bindings,
+                    )
+                }
+                pub fn from_bindings_with_layout(
+                    device: &wgpu::Device,
+                    layout: &Layout0,
+                    bindings: BindGroupLayout0,
+                ) -> Self {
+                    let bind_group = device
+                        .create_bind_group(
+                            &wgpu::BindGroupDescriptor {
+                                layout,
+                                entries: &[
+                                    wgpu::BindGroupEntry {
+                                        binding: 0,
+                                        resource: wgpu::BindingResource::Buffer(
+                                            bindings.values.into(),
+                                        ),
+                                    },
+                                ],
+                                label: Some("brightness::clamped::BindGroup0"),
+                            },
+                        );
+                    Self(bind_group)
+                }
+                pub fn set<'a>(
+                    &'a self,
+                    pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                ) {
+                    pass.set_bind_group(0, &self.0, &[]);
+                }
+            }
+            /// The bind group layouts for this module.
+            /// Create these once and reuse them for bind groups and pipeline layouts.
+            #[derive(Debug)]
+            pub struct Layouts {
+                pub bind_group0: Layout0,
+            }
+            impl Layouts {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self {
+                        bind_group0: Layout0::new(device),
+                    }
+                }
+            }
+            #[derive(Debug, Copy, Clone)]
+            pub struct BindGroups<'a> {
+                pub bind_group0: &'a BindGroup0,
+            }
+            impl<'a> BindGroups<'a> {
+                pub fn set(
+                    &self,
+                    pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                ) {
+                    self.bind_group0.set(pass);
+                }
+            }
+        }
+        pub fn set_bind_groups<'a>(
+            pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+            bind_group0: &'a bind_groups::BindGroup0,
+        ) {
+            bind_group0.set(pass);
+        }
+        pub mod compute {
+            pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [64, 1, 1];
+            pub fn create_main_pipeline(
+                device: &wgpu::Device,
+                shader_defs: &super::super::ShaderDefs,
+            ) -> wgpu::ComputePipeline {
+                let module = super::create_shader_module(device, shader_defs);
+                let layout = super::create_pipeline_layout(device);
+                device
+                    .create_compute_pipeline(
+                        &wgpu::ComputePipelineDescriptor {
+                            label: Some("brightness::clamped::ComputePipeline::main"),
+                            layout: Some(&layout),
+                            module: &module,
+                            entry_point: "main",
+                        },
+                    )
+            }
+        }
+        pub const ENTRY_MAIN: &str = "main";
+        pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+            create_pipeline_layout_with_layouts(
+                device,
+                &bind_groups::Layouts::new(device),
+            )
+        }
+        pub fn create_pipeline_layout_with_layouts(
+            device: &wgpu::Device,
+            layouts: &bind_groups::Layouts,
+        ) -> wgpu::PipelineLayout {
+            device
+                .create_pipeline_layout(
+                    &wgpu::PipelineLayoutDescriptor {
+                        label: Some("brightness::clamped::PipelineLayout"),
+                        bind_group_layouts: &[&layouts.bind_group0],
+                        push_constant_ranges: &[],
+                    },
+                )
+        }
+        pub fn init_composer() -> naga_oil::compose::Composer {
+            #[allow(unused_mut)]
+            let mut composer = naga_oil::compose::Composer::default()
+                .with_capabilities(wgpu::naga::valid::Capabilities::all());
+            composer
+        }
+        pub fn make_naga_module(
+            composer: &mut naga_oil::compose::Composer,
+            shader_defs: &super::ShaderDefs,
+        ) -> wgpu::naga::Module {
+            composer
+                .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                    source: include_str!("shader/brightness.wgsl"),
+                    file_path: "shader/brightness.wgsl",
+                    shader_defs: {
+                        let mut shader_defs = shader_defs.to_shader_defs();
+                        shader_defs
+                            .insert(
+                                "CLAMPED".to_owned(),
+                                naga_oil::compose::ShaderDefValue::Bool(true),
+                            );
+                        shader_defs
+                    },
+                    ..Default::default()
+                })
+                .expect("failed to build naga module")
+        }
+        pub fn naga_module_to_string(module: &wgpu::naga::Module) -> String {
+            let info = wgpu::naga::valid::Validator::new(
+                    wgpu::naga::valid::ValidationFlags::empty(),
+                    wgpu::naga::valid::Capabilities::all(),
+                )
+                .validate(&module);
+            let info = info.unwrap();
+            wgpu::naga::back::wgsl::write_string(
+                    &module,
+                    &info,
+                    wgpu::naga::back::wgsl::WriterFlags::empty(),
+                )
+                .expect("failed to convert naga module to source")
+        }
+        /// Composes the shader module with the shader defs.
+        ///
+        /// # Panics
+        /// In debug builds, if the shader defs change the bindings or struct layouts
+        /// of the generated code, which are generated from `ShaderDefs::default()`.
+        pub fn create_shader_module(
+            device: &wgpu::Device,
+            shader_defs: &super::ShaderDefs,
+        ) -> wgpu::ShaderModule {
+            let mut composer = init_composer();
+            let module = make_naga_module(&mut composer, shader_defs);
+            #[cfg(debug_assertions)]
+            if *shader_defs != super::ShaderDefs::default() {
+                if let Err(e) = verify_module_layout(&module) {
+                    panic!(
+                        "shader defs {shader_defs:?} don't match the generated bindings: {e}"
+                    );
+                }
+            }
+            let source = naga_module_to_string(&module);
+            let source = std::borrow::Cow::Owned(source);
+            device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("brightness::clamped::ShaderModule"),
+                    source: wgpu::ShaderSource::Wgsl(source),
+                })
+        }
+        /// Checks that the bindings and struct layouts of `module` match the generated code.
+        /// This catches changes to shaders composed at runtime that would require regenerating the bindings.
+        pub fn verify_module_layout(
+            module: &wgpu::naga::Module,
+        ) -> Result<(), _root::module_layout::LayoutError> {
+            const BINDINGS: &[_root::module_layout::BindingLayout] = &[
+                _root::module_layout::BindingLayout {
+                    group: 0,
+                    binding: 0,
+                    name: "values",
+                    kind: _root::module_layout::BindingKind::Storage {
+                        read_only: false,
+                        size: 4,
+                    },
+                    count: _root::module_layout::BindingCount::Single,
+                },
+            ];
+            const STRUCTS: &[_root::module_layout::StructLayout] = &[];
+            _root::module_layout::verify_module_layout(module, BINDINGS, STRUCTS)
+        }
+        /// The paths of the entry and its dependencies for reloading the shader module.
+        #[cfg(debug_assertions)]
+        pub const SHADER_PATHS: &[&str] = &[
+            concat!(env!("CARGO_MANIFEST_DIR"), "/", "src/shader/brightness.wgsl"),
+        ];
+        /// Creates a watcher for changes to the files in [SHADER_PATHS].
+        #[cfg(debug_assertions)]
+        pub fn shader_watcher() -> _root::hot_reload::ShaderWatcher {
+            _root::hot_reload::ShaderWatcher::new(SHADER_PATHS)
+        }
+        /// Creates the shader module from the current sources on disk instead of the
+        /// sources included at compile time.
+        /// The module is checked with [verify_module_layout] before creating the shader module.
+        #[cfg(debug_assertions)]
+        pub fn reload_shader_module(
+            device: &wgpu::Device,
+            shader_defs: &super::ShaderDefs,
+        ) -> Result<wgpu::ShaderModule, _root::hot_reload::ShaderReloadError> {
+            let mut composer = naga_oil::compose::Composer::default()
+                .with_capabilities(wgpu::naga::valid::Capabilities::all());
+            let path = SHADER_PATHS[0];
+            let source = _root::hot_reload::read_shader_source(path)?;
+            let module = composer
+                .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                    source: &source,
+                    file_path: path,
+                    shader_defs: {
+                        let mut shader_defs = shader_defs.to_shader_defs();
+                        shader_defs
+                            .insert(
+                                "CLAMPED".to_owned(),
+                                naga_oil::compose::ShaderDefValue::Bool(true),
+                            );
+                        shader_defs
+                    },
+                    ..Default::default()
+                })
+                .map_err(|e| _root::hot_reload::ShaderReloadError::Compose(
+                    e.emit_to_string(&composer),
+                ))?;
+            verify_module_layout(&module)
+                .map_err(_root::hot_reload::ShaderReloadError::Layout)?;
+            let source = naga_module_to_string(&module);
+            Ok(
+                device
+                    .create_shader_module(wgpu::ShaderModuleDescriptor {
+                        label: Some("brightness::clamped::ShaderModule"),
+                        source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(source)),
+                    }),
+            )
+        }
+    }
 }
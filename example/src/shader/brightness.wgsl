@@ -0,0 +1,12 @@
+@group(0) @binding(0)
+var<storage, read_write> values: array<f32>;
+
+@compute
+@workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+#ifdef CLAMPED
+  values[id.x] = clamp(values[id.x] * 2.0, 0.0, 1.0);
+#else
+  values[id.x] = values[id.x] * 2.0;
+#endif
+}
@@ -30,6 +30,9 @@ fn vs_main(in: VertexInput) -> VertexOutput {
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-  let color = textureSample(color_texture, color_sampler, in.tex_coords).rgb;
+  var color = textureSample(color_texture, color_sampler, in.tex_coords).rgb;
+#ifdef GRAYSCALE
+  color = vec3(dot(color, vec3(0.299, 0.587, 0.114)));
+#endif
   return vec4(color * uniforms.color_rgb.rgb, 1.0);
 }
@@ -1,4 +1,3 @@
-use colored::*;
 use indexmap::map::Entry;
 use miette::{Diagnostic, NamedSource, SourceSpan};
 use smallvec::SmallVec;
@@ -29,55 +28,38 @@ pub enum DependencyTreeError {
     #[label("Import statement")]
     import_bit: SourceSpan,
   },
+  #[error("Circular import: {}", .cycle.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(" -> "))]
+  #[diagnostic(help("Move the shared items into a separate module without imports from the cycle."))]
+  CircularImport {
+    /// The files in the cycle. The first file is repeated at the end.
+    cycle: Vec<SourceFilePath>,
+
+    #[related]
+    imports: Vec<CircularImportStatement>,
+  },
 }
 
-#[derive(Default)]
-struct MaxRecursionLimiter {
-  files_visited: Vec<(String, usize, String)>, // (file_path, line_number, import_str)
-}
+/// An import statement that is part of a circular import.
+#[derive(Debug, Error, Diagnostic)]
+#[error("`{importer}` imports `{path}`")]
+pub struct CircularImportStatement {
+  pub importer: SourceFilePath,
+  pub path: String,
 
-impl MaxRecursionLimiter {
-  const MAX_RECURSION_DEPTH: usize = 16;
+  #[source_code]
+  pub src: NamedSource<String>,
 
-  fn push(&mut self, import_stmt: &ImportStatement, source: &SourceFile) -> &mut Self {
-    let import_str = &source.content[import_stmt.range()];
-    self.files_visited.push((
-      source.file_path.to_string(),
-      import_stmt.source_location.line_number,
-      import_str.to_string(),
-    ));
-    self
-  }
-
-  fn pop(&mut self) -> &mut Self {
-    self.files_visited.pop();
-    self
-  }
+  #[label("Import statement")]
+  pub import_bit: SourceSpan,
+}
 
-  fn check_depth(&self) {
-    if self.files_visited.len() > Self::MAX_RECURSION_DEPTH {
-      let visited_files = self
-        .files_visited
-        .iter()
-        .map(|(path, line, import)| {
-          format!(
-            "\n{}:{}: {}",
-            path.to_string().cyan(),
-            line.to_string().purple(),
-            import.to_string().yellow()
-          )
-        })
-        .rev()
-        .collect::<String>();
-
-      panic!(
-        "{}\n{}\n{}\n",
-        "Recursion limit exceeded".red(),
-        "This error may be due to a circular dependency. The files visited during the recursion were:".red(),
-        visited_files
-       );
-    }
-  }
+/// Tracks the files that are currently being crawled to detect circular imports.
+#[derive(Default)]
+struct ImportStack {
+  /// The files being crawled, starting from the entry point.
+  files: FxIndexSet<SourceFilePath>,
+  /// The import statements that lead from each file in `files` to the next one.
+  imports: Vec<(ImportStatement, ImportedPath)>,
 }
 
 #[derive(Debug, Clone)]
@@ -135,7 +117,7 @@ impl DependencyTree {
         &entry_point.dir(),
         entry_point,
         None,
-        &mut MaxRecursionLimiter::default(),
+        &mut ImportStack::default(),
       )?
     }
 
@@ -149,7 +131,7 @@ impl DependencyTree {
     parent_source_path: &SourceFilePath,
     import_stmt: &ImportStatement,
     imported_path: &ImportedPath,
-    limiter: &mut MaxRecursionLimiter,
+    stack: &mut ImportStack,
   ) -> Result<(), DependencyTreeError> {
     let possible_source_path = self
       .resolver
@@ -176,27 +158,51 @@ impl DependencyTree {
     // add self as a dependency to the parent
     parent_source.add_direct_dependency(source_path.clone());
 
-    limiter.push(import_stmt, parent_source).check_depth();
+    if let Some(index) = stack.files.get_index_of(&source_path) {
+      stack.imports.push((import_stmt.clone(), imported_path.clone()));
+      return Err(self.circular_import_error(stack, index));
+    }
 
     // if not crawled, crawl this import file
     if !self.parsed_sources.contains_key(&source_path) {
-      self
-        .crawl_source(entry_dir, source_path, Some(module_name), limiter)
-        .expect("failed to crawl import path");
+      stack.imports.push((import_stmt.clone(), imported_path.clone()));
+      self.crawl_source(entry_dir, source_path, Some(module_name), stack)?;
+      stack.imports.pop();
     }
 
-    limiter.pop();
-
     Ok(())
   }
 
+  /// Creates the error for the cycle starting at the file with `index` in the stack.
+  /// The last import in the stack is the one that closes the cycle.
+  fn circular_import_error(&self, stack: &ImportStack, index: usize) -> DependencyTreeError {
+    let mut cycle: Vec<_> = stack.files.iter().skip(index).cloned().collect();
+    cycle.push(cycle[0].clone());
+
+    let imports = cycle
+      .iter()
+      .zip(&stack.imports[index..])
+      .map(|(importer, (import_stmt, imported_path))| {
+        let source = &self.parsed_sources[importer];
+        CircularImportStatement {
+          importer: importer.clone(),
+          path: imported_path.to_string(),
+          src: NamedSource::new(importer.to_string(), source.content.clone()),
+          import_bit: (&import_stmt.source_location).into(),
+        }
+      })
+      .collect();
+
+    CircularImport { cycle, imports }
+  }
+
   /// Crawls a source file and its dependencies.
   fn crawl_source(
     &mut self,
     entry_dir: &SourceFileDir,
     source_path: SourceFilePath,
     module_name: Option<SourceModuleName>,
-    limiter: &mut MaxRecursionLimiter,
+    stack: &mut ImportStack,
   ) -> Result<(), DependencyTreeError> {
     match self.parsed_sources.entry(source_path.clone()) {
       Entry::Occupied(_) => {} // do nothing
@@ -212,6 +218,7 @@ impl DependencyTree {
     };
 
     let source_file = self.parsed_sources.get(&source_path).unwrap();
+    stack.files.insert(source_path.clone());
 
     for import_stmt in &source_file.imports.clone() {
       for imported_path in import_stmt.get_imported_paths() {
@@ -220,11 +227,12 @@ impl DependencyTree {
           &source_path,
           &import_stmt,
           &imported_path,
-          limiter,
+          stack,
         )?
       }
     }
 
+    stack.files.pop();
     Ok(())
   }
 
@@ -1,3 +1,5 @@
+use miette::SourceSpan;
+use regex::Regex;
 use smallvec::SmallVec;
 
 use super::parse_imports;
@@ -47,6 +49,33 @@ impl SourceFile {
       .flat_map(|import_stmt| import_stmt.get_imported_paths())
       .collect()
   }
+
+  /// Finds the name in the declaration of a global variable, constant, function or struct.
+  /// If `member` is set, the member name in the struct declaration is found instead.
+  pub fn find_declaration(&self, item: &str, member: Option<&str>) -> Option<SourceSpan> {
+    let declaration = Regex::new(&format!(
+      r"\b(?:struct|var(?:\s*<[^>]*>)?|const|override|fn)\s+({})\b",
+      regex_syntax::escape(item)
+    ))
+    .unwrap();
+    let item_match = declaration.captures(&self.content)?.get(1)?;
+
+    let Some(member) = member else {
+      return Some((item_match.start(), item_match.len()).into());
+    };
+
+    // Only search the struct body for the member.
+    let body_start = item_match.end();
+    let body_end = self.content[body_start..]
+      .find('}')
+      .map_or(self.content.len(), |end| body_start + end);
+    let member_declaration =
+      Regex::new(&format!(r"\b({})\s*:", regex_syntax::escape(member))).unwrap();
+    let member_match = member_declaration
+      .captures(&self.content[body_start..body_end])?
+      .get(1)?;
+    Some((body_start + member_match.start(), member_match.len()).into())
+  }
 }
 
 #[cfg(test)]
@@ -63,7 +92,8 @@ mod tests {
     let source = SourceFile::create(
       source_path,
       module_name,
-      include_str!("../../tests/shaders/bevy_pbr_wgsl/mesh_view_bindings.wgsl").to_owned(),
+      include_str!("../../tests/shaders/bevy_pbr_wgsl/mesh_view_bindings.wgsl")
+        .to_owned(),
     );
     let actual = source.get_imported_paths();
 
@@ -74,4 +104,29 @@ mod tests {
       }
     );
   }
+
+  #[test]
+  fn find_declaration_global_and_struct_member() {
+    let source = SourceFile::create(
+      SourceFilePath::new("shader.wgsl"),
+      None,
+      indoc::indoc! {r#"
+        struct Uniforms {
+            color: vec4<f32>,
+            scale: f32,
+        }
+        @group(0) @binding(0) var<uniform> uniforms: Uniforms;
+      "#}
+      .to_owned(),
+    );
+
+    let span = |offset: usize, len: usize| Some(SourceSpan::from((offset, len)));
+    let uniforms_offset = source.content.find("uniforms:").unwrap();
+    let scale_offset = source.content.find("scale").unwrap();
+    assert_eq!(span(7, 8), source.find_declaration("Uniforms", None));
+    assert_eq!(span(uniforms_offset, 8), source.find_declaration("uniforms", None));
+    assert_eq!(span(scale_offset, 5), source.find_declaration("Uniforms", Some("scale")));
+    assert_eq!(None, source.find_declaration("Uniforms", Some("uniforms")));
+    assert_eq!(None, source.find_declaration("missing", None));
+  }
 }
@@ -1,86 +1,289 @@
 use std::collections::BTreeMap;
+use std::num::NonZeroU32;
 
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{Ident, Index};
 
-use crate::{indexed_name_to_ident, wgsl::buffer_binding_type, CreateModuleError};
-use crate::bevy_util::demangle_splitting_mod_path_and_item;
+use crate::bevy_util::{demangle, demangle_splitting_mod_path_and_item};
+use crate::quote_gen::rust_type;
+use crate::wgsl::{
+  buffer_binding_type, global_shader_stages, module_layouter, non_filtering_globals,
+  type_name,
+};
+use crate::{indexed_name_to_ident, object_label, CreateModuleError, WgslBindgenOption};
 
+#[derive(Clone)]
 pub struct GroupData<'a> {
   pub bindings: Vec<GroupBinding<'a>>,
+  /// The imported module path if this group is generated in a shared module.
+  pub shared_module: Option<String>,
 }
 
+#[derive(Clone)]
 pub struct GroupBinding<'a> {
   pub name: Option<String>,
   pub binding_index: u32,
+  /// The type of the binding or the element type for binding arrays.
   pub binding_type: &'a naga::Type,
+  /// The number of elements for binding arrays.
+  pub count: Option<NonZeroU32>,
+  /// The size in bytes of the binding type or element type.
+  pub size: u32,
   pub address_space: naga::AddressSpace,
+  /// The stages of the entry points that actually use this binding.
+  pub visibility: wgpu::ShaderStages,
+  /// Textures that aren't filterable or samplers that must not filter.
+  pub non_filtering: bool,
+}
+
+/// A bind group generated once in the bind_groups module of an imported module.
+pub struct SharedGroup<'a> {
+  pub mod_path: String,
+  pub group_no: u32,
+  pub group: GroupData<'a>,
+  pub module: &'a naga::Module,
+  /// The index of the first entry using this group.
+  pub entry: usize,
+}
+
+/// The bind group data for a single entry.
+pub struct EntryGroups<'a> {
+  pub module: &'a naga::Module,
+  pub bind_group_data: BTreeMap<u32, GroupData<'a>>,
+}
+
+/// Find groups whose bindings all come from the same imported module.
+/// Each group is shared if every entry using it has the same bindings.
+/// The layout visibility and filtering is merged to work with all entries.
+pub fn share_bind_groups<'a>(entries: &mut [EntryGroups<'a>]) -> Vec<SharedGroup<'a>> {
+  let mut candidates = BTreeMap::<(String, u32), Vec<usize>>::new();
+  for (i, entry) in entries.iter().enumerate() {
+    for (group_no, group) in &entry.bind_group_data {
+      if let Some(mod_path) = group_mod_path(group) {
+        candidates.entry((mod_path, *group_no)).or_default().push(i);
+      }
+    }
+  }
+
+  let mut shared_groups = Vec::new();
+  for ((mod_path, group_no), users) in candidates {
+    let mut group = entries[users[0]].bind_group_data[&group_no].clone();
+
+    let compatible = users
+      .iter()
+      .all(|i| same_bindings(&group, &entries[*i].bind_group_data[&group_no]));
+    if !compatible {
+      continue;
+    }
+
+    for i in &users[1..] {
+      let other = &entries[*i].bind_group_data[&group_no];
+      for (binding, other) in group.bindings.iter_mut().zip(&other.bindings) {
+        binding.visibility |= other.visibility;
+        binding.non_filtering &= other.non_filtering;
+      }
+    }
+    group.shared_module = Some(mod_path.clone());
+
+    for i in &users {
+      entries[*i].bind_group_data.insert(group_no, group.clone());
+    }
+
+    shared_groups.push(SharedGroup {
+      mod_path,
+      group_no,
+      group,
+      module: entries[users[0]].module,
+      entry: users[0],
+    });
+  }
+
+  shared_groups
+}
+
+fn group_mod_path(group: &GroupData) -> Option<String> {
+  let mut mod_paths = group.bindings.iter().map(|binding| {
+    binding
+      .name
+      .as_ref()
+      .and_then(|name| demangle_splitting_mod_path_and_item(name).0)
+  });
+  let first = mod_paths.next()??;
+  mod_paths
+    .all(|p| p.as_ref() == Some(&first))
+    .then_some(first)
+}
+
+fn same_bindings(a: &GroupData, b: &GroupData) -> bool {
+  a.bindings.len() == b.bindings.len()
+    && a.bindings.iter().zip(&b.bindings).all(|(a, b)| {
+      // Handles for struct members and array elements differ between modules.
+      let same_type = match (&a.binding_type.inner, &b.binding_type.inner) {
+        (naga::TypeInner::Struct { .. }, naga::TypeInner::Struct { .. })
+        | (naga::TypeInner::Array { .. }, naga::TypeInner::Array { .. }) => {
+          a.binding_type.name == b.binding_type.name
+        }
+        (a, b) => a == b,
+      };
+      a.name == b.name
+        && a.binding_index == b.binding_index
+        && a.count == b.count
+        && a.size == b.size
+        && a.address_space == b.address_space
+        && same_type
+    })
+}
+
+/// The bind group, layout, and dynamic offset items for a single group.
+pub fn bind_group_items(
+  mod_name: &str,
+  module: &naga::Module,
+  group_no: u32,
+  group: &GroupData,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let group_name = indexed_name_to_ident("BindGroup", group_no);
+
+  let layout = bind_group_layout(module, group_no, group, options)?;
+  let layout_descriptor =
+    bind_group_layout_descriptor(mod_name, module, group_no, group, options)?;
+  let dynamic_offsets = dynamic_offsets(mod_name, module, group_no, group, options)?;
+  let group_impl = bind_group(mod_name, module, group_no, group, options)?;
+
+  Ok(quote! {
+      #[derive(Debug)]
+      pub struct #group_name(wgpu::BindGroup);
+      #layout
+      #layout_descriptor
+      #dynamic_offsets
+      #group_impl
+  })
+}
+
+/// Reexport the items for a group from the bind_groups module of the imported module.
+fn shared_bind_group_items(
+  mod_path: &str,
+  group_no: u32,
+  group: &GroupData,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let mut names = vec![
+    indexed_name_to_ident("BindGroup", group_no),
+    indexed_name_to_ident("BindGroupLayout", group_no),
+    indexed_name_to_ident("Layout", group_no),
+  ];
+  if has_dynamic_offsets(mod_path, group, options) {
+    names.push(indexed_name_to_ident("DynamicOffsets", group_no));
+  }
+
+  let mod_path: syn::Path =
+    syn::parse_str(mod_path).map_err(|_| CreateModuleError::InvalidModulePath {
+      name: mod_path.to_string(),
+      src: None,
+      span: None,
+    })?;
+  Ok(quote! {
+      pub use super::_root::#mod_path::bind_groups::{#(#names),*};
+  })
 }
 
 // TODO: Take an iterator instead?
 pub fn bind_groups_module(
+  mod_name: &str,
+  module: &naga::Module,
   bind_group_data: &BTreeMap<u32, GroupData>,
-  shader_stages: wgpu::ShaderStages,
-) -> TokenStream {
-  let bind_groups: Vec<_> = bind_group_data
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let bind_groups = bind_group_data
     .iter()
-    .map(|(group_no, group)| {
-      let group_name = indexed_name_to_ident("BindGroup", *group_no);
-
-      let layout = bind_group_layout(*group_no, group);
-      let layout_descriptor =
-        bind_group_layout_descriptor(*group_no, group, shader_stages);
-      let group_impl = bind_group(*group_no, group, shader_stages);
+    .map(|(group_no, group)| match &group.shared_module {
+      Some(mod_path) => shared_bind_group_items(mod_path, *group_no, group, options),
+      None => bind_group_items(mod_name, module, *group_no, group, options),
+    })
+    .collect::<Result<Vec<_>, _>>()?;
 
-      quote! {
-          #[derive(Debug)]
-          pub struct #group_name(wgpu::BindGroup);
-          #layout
-          #layout_descriptor
-          #group_impl
-      }
+  let layout_fields: Vec<_> = bind_group_data
+    .keys()
+    .map(|group_no| {
+      let layout_name = indexed_name_to_ident("Layout", *group_no);
+      let field = indexed_name_to_ident("bind_group", *group_no);
+      quote!(pub #field: #layout_name)
     })
     .collect();
 
-  let bind_group_fields: Vec<_> = bind_group_data
+  let create_layouts: Vec<_> = bind_group_data
     .keys()
     .map(|group_no| {
-      let group_name = indexed_name_to_ident("BindGroup", *group_no);
+      let layout_name = indexed_name_to_ident("Layout", *group_no);
       let field = indexed_name_to_ident("bind_group", *group_no);
-      quote!(pub #field: &'a #group_name)
+      quote!(#field: #layout_name::new(device))
     })
     .collect();
 
-  // TODO: Support compute shader with vertex/fragment in the same module?
-  let is_compute = shader_stages == wgpu::ShaderStages::COMPUTE;
-  let render_pass = if is_compute {
-    quote!(wgpu::ComputePass<'a>)
-  } else {
-    quote!(wgpu::RenderPass<'a>)
-  };
+  let bind_group_fields: Vec<_> = bind_group_data
+    .iter()
+    .map(|(group_no, group)| {
+      let group_name = indexed_name_to_ident("BindGroup", *group_no);
+      let field = indexed_name_to_ident("bind_group", *group_no);
+      if has_dynamic_offsets(mod_name, group, options) {
+        let offsets_name = indexed_name_to_ident("DynamicOffsets", *group_no);
+        let offsets_field = indexed_name_to_ident("dynamic_offsets", *group_no);
+        quote!(pub #field: &'a #group_name, pub #offsets_field: #offsets_name)
+      } else {
+        quote!(pub #field: &'a #group_name)
+      }
+    })
+    .collect();
 
   let group_parameters: Vec<_> = bind_group_data
-    .keys()
-    .map(|group_no| {
-      let group = indexed_name_to_ident("bind_group", *group_no);
+    .iter()
+    .map(|(group_no, group)| {
+      let group_param = indexed_name_to_ident("bind_group", *group_no);
       let group_type = indexed_name_to_ident("BindGroup", *group_no);
-      quote!(#group: &'a bind_groups::#group_type)
+      if has_dynamic_offsets(mod_name, group, options) {
+        let offsets_param = indexed_name_to_ident("dynamic_offsets", *group_no);
+        let offsets_type = indexed_name_to_ident("DynamicOffsets", *group_no);
+        quote! {
+            #group_param: &'a bind_groups::#group_type,
+            #offsets_param: bind_groups::#offsets_type
+        }
+      } else {
+        quote!(#group_param: &'a bind_groups::#group_type)
+      }
     })
     .collect();
 
   // The set function for each bind group already sets the index.
   let set_groups: Vec<_> = bind_group_data
-    .keys()
-    .map(|group_no| {
-      let group = indexed_name_to_ident("bind_group", *group_no);
-      quote!(#group.set(pass);)
+    .iter()
+    .map(|(group_no, group)| {
+      let group_param = indexed_name_to_ident("bind_group", *group_no);
+      if has_dynamic_offsets(mod_name, group, options) {
+        let offsets_param = indexed_name_to_ident("dynamic_offsets", *group_no);
+        quote!(#group_param.set(pass, #offsets_param);)
+      } else {
+        quote!(#group_param.set(pass);)
+      }
+    })
+    .collect();
+
+  let set_groups_from_fields: Vec<_> = bind_group_data
+    .iter()
+    .map(|(group_no, group)| {
+      let group_field = indexed_name_to_ident("bind_group", *group_no);
+      if has_dynamic_offsets(mod_name, group, options) {
+        let offsets_field = indexed_name_to_ident("dynamic_offsets", *group_no);
+        quote!(self.#group_field.set(pass, self.#offsets_field);)
+      } else {
+        quote!(self.#group_field.set(pass);)
+      }
     })
     .collect();
 
   let set_bind_groups = quote! {
       pub fn set_bind_groups<'a>(
-          pass: &mut #render_pass,
+          pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
           #(#group_parameters),*
       ) {
           #(#set_groups)*
@@ -89,108 +292,203 @@ pub fn bind_groups_module(
 
   if bind_groups.is_empty() {
     // Don't include empty modules.
-    quote!()
+    Ok(quote!())
   } else {
+    // Typed buffer bindings refer to types from the parent and root modules.
+    let parent_import = if options.typed_buffer_bindings {
+      quote! {
+          #[allow(unused_imports)]
+          use super::*;
+      }
+    } else {
+      quote!()
+    };
+
     // Create a module to avoid name conflicts with user structs.
-    quote! {
+    Ok(quote! {
         pub mod bind_groups {
+            #parent_import
             #(#bind_groups)*
 
+            /// The bind group layouts for this module.
+            /// Create these once and reuse them for bind groups and pipeline layouts.
+            #[derive(Debug)]
+            pub struct Layouts {
+                #(#layout_fields),*
+            }
+
+            impl Layouts {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self {
+                        #(#create_layouts),*
+                    }
+                }
+            }
+
             #[derive(Debug, Copy, Clone)]
             pub struct BindGroups<'a> {
                 #(#bind_group_fields),*
             }
 
             impl<'a> BindGroups<'a> {
-                pub fn set(&self, pass: &mut #render_pass) {
-                    #(self.#set_groups)*
+                pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                    #(#set_groups_from_fields)*
                 }
             }
         }
         #set_bind_groups
-    }
+    })
   }
 }
 
-fn bind_group_layout(group_no: u32, group: &GroupData) -> TokenStream {
-  let fields: Vec<_> = group
+fn bind_group_layout(
+  module: &naga::Module,
+  group_no: u32,
+  group: &GroupData,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let fields = group
     .bindings
     .iter()
     .map(|binding| {
-      let (_, demangled_name) = demangle_splitting_mod_path_and_item(binding.name.as_ref().unwrap());
+      let (_, demangled_name) =
+        demangle_splitting_mod_path_and_item(binding.name.as_ref().unwrap());
       let field_name = Ident::new(&demangled_name, Span::call_site());
       // TODO: Support more types.
-      let field_type = match binding.binding_type.inner {
-        naga::TypeInner::Struct { .. } => quote!(wgpu::BufferBinding<'a>),
-        naga::TypeInner::Image { .. } => quote!(&'a wgpu::TextureView),
-        naga::TypeInner::Sampler { .. } => quote!(&'a wgpu::Sampler),
-        naga::TypeInner::Array { .. } => quote!(wgpu::BufferBinding<'a>),
-        naga::TypeInner::Scalar(_) => quote!(wgpu::BufferBinding<'a>),
-        _ => panic!("Unsupported type for binding fields."),
+      let field_type = match (&binding.binding_type.inner, binding.count) {
+        (
+          naga::TypeInner::Struct { .. }
+          | naga::TypeInner::Array { .. }
+          | naga::TypeInner::Scalar(_),
+          None,
+        ) => buffer_binding_type_tokens(module, binding, options)?,
+        (naga::TypeInner::Image { .. }, None) => quote!(&'a wgpu::TextureView),
+        (naga::TypeInner::Sampler { .. }, None) => quote!(&'a wgpu::Sampler),
+        (naga::TypeInner::Struct { .. }, Some(_)) => {
+          quote!(&'a [wgpu::BufferBinding<'a>])
+        }
+        (naga::TypeInner::Image { .. }, Some(_)) => quote!(&'a [&'a wgpu::TextureView]),
+        (naga::TypeInner::Sampler { .. }, Some(_)) => quote!(&'a [&'a wgpu::Sampler]),
+        (naga::TypeInner::AccelerationStructure, None) => {
+          quote!(_root::acceleration_structure::AccelerationStructureBinding)
+        }
+        _ => return Err(unsupported_binding_type(module, binding)),
       };
-      quote!(pub #field_name: #field_type)
+      Ok(quote!(pub #field_name: #field_type))
     })
-    .collect();
+    .collect::<Result<Vec<_>, _>>()?;
 
   let name = indexed_name_to_ident("BindGroupLayout", group_no);
-  quote! {
+  Ok(quote! {
       #[allow(non_snake_case)]
       #[derive(Debug)]
       pub struct #name<'a> {
           #(#fields),*
       }
+  })
+}
+
+fn buffer_binding_type_tokens(
+  module: &naga::Module,
+  binding: &GroupBinding,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  if !options.typed_buffer_bindings {
+    return Ok(quote!(wgpu::BufferBinding<'a>));
   }
+
+  let ty = binding.binding_type;
+  let rust_type = |ty: &naga::Type| {
+    rust_type(module, ty, options).ok_or_else(|| CreateModuleError::UnsupportedType {
+      name: binding_name(binding),
+      ty: type_name(module, binding.binding_type),
+      src: None,
+      span: None,
+    })
+  };
+
+  let rust_type = match &ty.inner {
+    naga::TypeInner::Array {
+      base,
+      size: naga::ArraySize::Dynamic,
+      ..
+    } => {
+      let element_type = rust_type(&module.types[*base])?;
+      quote!([#element_type])
+    }
+    // Bytemuck structs with runtime-sized arrays are generic over the array length.
+    naga::TypeInner::Struct { members, .. }
+      if !options.serialization_strategy.is_encase()
+        && members
+          .last()
+          .is_some_and(|m| is_runtime_sized_array(module, m.ty)) =>
+    {
+      return Ok(quote!(wgpu::BufferBinding<'a>));
+    }
+    _ => {
+      let rust_type = rust_type(ty)?;
+      quote!(#rust_type)
+    }
+  };
+  Ok(quote!(_root::typed_buffer::TypedBufferBinding<'a, #rust_type>))
 }
 
 fn bind_group_layout_descriptor(
+  mod_name: &str,
+  module: &naga::Module,
   group_no: u32,
   group: &GroupData,
-  shader_stages: wgpu::ShaderStages,
-) -> TokenStream {
-  let entries: Vec<_> = group
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let entries = group
     .bindings
     .iter()
-    .map(|binding| bind_group_layout_entry(binding, shader_stages))
-    .collect();
+    .map(|binding| bind_group_layout_entry(mod_name, module, binding, options))
+    .collect::<Result<Vec<_>, _>>()?;
 
   let name = indexed_name_to_ident("LAYOUT_DESCRIPTOR", group_no);
-  quote! {
+  let label = object_label(mod_name, &format!("BindGroup{group_no}::Layout"), options);
+  Ok(quote! {
       const #name: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-          label: None,
+          label: Some(#label),
           entries: &[
               #(#entries),*
           ],
       };
-  }
+  })
 }
 
 fn bind_group_layout_entry(
+  mod_name: &str,
+  module: &naga::Module,
   binding: &GroupBinding,
-  shader_stages: wgpu::ShaderStages,
-) -> TokenStream {
-  // TODO: Assume storage is only used for compute?
-  // TODO: Support just vertex or fragment?
-  // TODO: Visible from all stages?
-  let stages = match shader_stages {
-    wgpu::ShaderStages::VERTEX_FRAGMENT => quote!(wgpu::ShaderStages::VERTEX_FRAGMENT),
-    wgpu::ShaderStages::COMPUTE => quote!(wgpu::ShaderStages::COMPUTE),
-    wgpu::ShaderStages::VERTEX => quote!(wgpu::ShaderStages::VERTEX),
-    wgpu::ShaderStages::FRAGMENT => quote!(wgpu::ShaderStages::FRAGMENT),
-    _ => todo!(),
-  };
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let stages = shader_stages_tokens(binding.visibility);
 
   let binding_index = Index::from(binding.binding_index as usize);
+  let count = match binding.count {
+    Some(count) => {
+      let count = Index::from(count.get() as usize);
+      quote!(std::num::NonZeroU32::new(#count))
+    }
+    None => quote!(None),
+  };
   // TODO: Support more types.
   let binding_type = match binding.binding_type.inner {
     naga::TypeInner::Scalar(_)
     | naga::TypeInner::Struct { .. }
     | naga::TypeInner::Array { .. } => {
-      let buffer_binding_type = buffer_binding_type(binding.address_space);
+      let buffer_binding_type = buffer_binding_type(binding.address_space)
+        .ok_or_else(|| unsupported_binding_type(module, binding))?;
+      let has_dynamic_offset = has_dynamic_offset(mod_name, binding, options);
+      // Runtime-sized arrays already count a single element in the naga layout.
+      let min_binding_size = Index::from(binding.size as usize);
 
       quote!(wgpu::BindingType::Buffer {
           ty: #buffer_binding_type,
-          has_dynamic_offset: false,
-          min_binding_size: None,
+          has_dynamic_offset: #has_dynamic_offset,
+          min_binding_size: std::num::NonZeroU64::new(#min_binding_size),
       })
     }
     naga::TypeInner::Image { dim, class, .. } => {
@@ -202,10 +500,17 @@ fn bind_group_layout_entry(
       };
 
       match class {
-        naga::ImageClass::Sampled { kind: _, multi } => {
-          // TODO: Don't assume all textures are filterable.
+        naga::ImageClass::Sampled { kind, multi } => {
+          let sample_type = match kind {
+            naga::ScalarKind::Sint => quote!(wgpu::TextureSampleType::Sint),
+            naga::ScalarKind::Uint => quote!(wgpu::TextureSampleType::Uint),
+            _ => {
+              let filterable = !binding.non_filtering;
+              quote!(wgpu::TextureSampleType::Float { filterable: #filterable })
+            }
+          };
           quote!(wgpu::BindingType::Texture {
-              sample_type: wgpu::TextureSampleType::Float { filterable: true },
+              sample_type: #sample_type,
               view_dimension: #view_dim,
               multisampled: #multi,
           })
@@ -221,7 +526,8 @@ fn bind_group_layout_entry(
           // TODO: Will the debug implementation always work with the macro?
           // Assume texture format variants are the same as storage formats.
           let format = syn::Ident::new(&format!("{format:?}"), Span::call_site());
-          let storage_access = storage_access(access);
+          let storage_access = storage_access(access)
+            .ok_or_else(|| unsupported_binding_type(module, binding))?;
 
           quote!(wgpu::BindingType::StorageTexture {
               access: #storage_access,
@@ -234,164 +540,547 @@ fn bind_group_layout_entry(
     naga::TypeInner::Sampler { comparison } => {
       let sampler_type = if comparison {
         quote!(wgpu::SamplerBindingType::Comparison)
+      } else if binding.non_filtering {
+        quote!(wgpu::SamplerBindingType::NonFiltering)
       } else {
         quote!(wgpu::SamplerBindingType::Filtering)
       };
       quote!(wgpu::BindingType::Sampler(#sampler_type))
     }
-    // TODO: Better error handling.
-    _ => panic!("Failed to generate BindingType."),
+    naga::TypeInner::AccelerationStructure => {
+      quote!(wgpu::BindingType::AccelerationStructure)
+    }
+    _ => return Err(unsupported_binding_type(module, binding)),
   };
 
-  quote! {
+  Ok(quote! {
       wgpu::BindGroupLayoutEntry {
           binding: #binding_index,
           visibility: #stages,
           ty: #binding_type,
-          count: None,
+          count: #count,
+      }
+  })
+}
+
+/// The name of the binding in `dynamic_offset_bindings`.
+/// Imported bindings use their import path and entry bindings use the entry module.
+fn qualified_binding_name(mod_name: &str, binding: &GroupBinding) -> String {
+  let name = binding_name(binding);
+  if name.contains("::") {
+    name
+  } else {
+    // Permutations share the dynamic offset bindings of their entry.
+    let entry_mod_name = mod_name.split("::").next().unwrap_or(mod_name);
+    format!("{entry_mod_name}::{name}")
+  }
+}
+
+fn has_dynamic_offset(
+  mod_name: &str,
+  binding: &GroupBinding,
+  options: &WgslBindgenOption,
+) -> bool {
+  binding.count.is_none()
+    && matches!(
+      binding.address_space,
+      naga::AddressSpace::Uniform | naga::AddressSpace::Storage { .. }
+    )
+    && options
+      .dynamic_offset_bindings
+      .contains(&qualified_binding_name(mod_name, binding))
+}
+
+fn has_dynamic_offsets(
+  mod_name: &str,
+  group: &GroupData,
+  options: &WgslBindgenOption,
+) -> bool {
+  group
+    .bindings
+    .iter()
+    .any(|b| has_dynamic_offset(mod_name, b, options))
+}
+
+/// wgpu expects dynamic offsets in order of increasing binding index.
+fn dynamic_offset_bindings<'a, 'b>(
+  mod_name: &str,
+  group: &'b GroupData<'a>,
+  options: &WgslBindgenOption,
+) -> Vec<&'b GroupBinding<'a>> {
+  let mut bindings: Vec<_> = group
+    .bindings
+    .iter()
+    .filter(|b| has_dynamic_offset(mod_name, b, options))
+    .collect();
+  bindings.sort_by_key(|b| b.binding_index);
+  bindings
+}
+
+fn dynamic_offsets(
+  mod_name: &str,
+  module: &naga::Module,
+  group_no: u32,
+  group: &GroupData,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let bindings = dynamic_offset_bindings(mod_name, group, options);
+  if bindings.is_empty() {
+    return Ok(quote!());
+  }
+
+  // The stride of a runtime-sized binding depends on the length of the array.
+  if let Some(binding) = bindings.iter().find(|b| is_runtime_sized(module, b)) {
+    return Err(CreateModuleError::RuntimeSizedDynamicOffset {
+      name: binding_name(binding),
+      src: None,
+      span: None,
+    });
+  }
+
+  let fields: Vec<_> = bindings
+    .iter()
+    .map(|binding| {
+      let (_, demangled_name) =
+        demangle_splitting_mod_path_and_item(binding.name.as_ref().unwrap());
+      let field_name = Ident::new(&demangled_name, Span::call_site());
+      quote!(pub #field_name: wgpu::DynamicOffset)
+    })
+    .collect();
+
+  // Offsets must be multiples of the alignment limit for the buffer type.
+  let strides: Vec<_> = bindings
+    .iter()
+    .map(|binding| {
+      let (_, demangled_name) =
+        demangle_splitting_mod_path_and_item(binding.name.as_ref().unwrap());
+      let stride_name =
+        Ident::new(&format!("{demangled_name}_stride"), Span::call_site());
+      let size = Index::from(binding.size as usize);
+      let alignment = match binding.address_space {
+        naga::AddressSpace::Uniform => quote!(limits.min_uniform_buffer_offset_alignment),
+        _ => quote!(limits.min_storage_buffer_offset_alignment),
+      };
+      quote! {
+          pub fn #stride_name(limits: &wgpu::Limits) -> wgpu::DynamicOffset {
+              wgpu::util::align_to(#size, #alignment)
+          }
+      }
+    })
+    .collect();
+
+  let name = indexed_name_to_ident("DynamicOffsets", group_no);
+  Ok(quote! {
+      #[derive(Debug, Default, Copy, Clone)]
+      pub struct #name {
+          #(#fields),*
+      }
+
+      impl #name {
+          #(#strides)*
       }
+  })
+}
+
+pub fn shader_stages_tokens(stages: wgpu::ShaderStages) -> TokenStream {
+  match stages {
+    wgpu::ShaderStages::NONE => quote!(wgpu::ShaderStages::NONE),
+    wgpu::ShaderStages::VERTEX_FRAGMENT => quote!(wgpu::ShaderStages::VERTEX_FRAGMENT),
+    _ => {
+      // The layout descriptors are constants, so combine the flags with the const union.
+      let mut flags = stages
+        .iter_names()
+        .map(|(name, _)| Ident::new(name, Span::call_site()));
+      let first = flags.next().unwrap();
+      flags.fold(
+        quote!(wgpu::ShaderStages::#first),
+        |stages, flag| quote!(#stages.union(wgpu::ShaderStages::#flag)),
+      )
+    }
   }
 }
 
-fn storage_access(access: naga::StorageAccess) -> TokenStream {
+fn storage_access(access: naga::StorageAccess) -> Option<TokenStream> {
   let is_read = access.contains(naga::StorageAccess::LOAD);
   let is_write = access.contains(naga::StorageAccess::STORE);
   match (is_read, is_write) {
-    (true, true) => quote!(wgpu::StorageTextureAccess::ReadWrite),
-    (true, false) => quote!(wgpu::StorageTextureAccess::ReadOnly),
-    (false, true) => quote!(wgpu::StorageTextureAccess::WriteOnly),
-    _ => todo!(), // shouldn't be possible
+    (true, true) => Some(quote!(wgpu::StorageTextureAccess::ReadWrite)),
+    (true, false) => Some(quote!(wgpu::StorageTextureAccess::ReadOnly)),
+    (false, true) => Some(quote!(wgpu::StorageTextureAccess::WriteOnly)),
+    (false, false) => None,
   }
 }
 
 fn bind_group(
+  mod_name: &str,
+  module: &naga::Module,
   group_no: u32,
   group: &GroupData,
-  shader_stages: wgpu::ShaderStages,
-) -> TokenStream {
-  let entries: Vec<_> = group
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let entries = group
     .bindings
     .iter()
     .map(|binding| {
       let binding_index = Index::from(binding.binding_index as usize);
-      let (_, demangled_name) = demangle_splitting_mod_path_and_item(binding.name.as_ref().unwrap());
+      let (_, demangled_name) =
+        demangle_splitting_mod_path_and_item(binding.name.as_ref().unwrap());
       let binding_name = Ident::new(&demangled_name, Span::call_site());
-      let resource_type = match binding.binding_type.inner {
-        naga::TypeInner::Scalar(_)
-        | naga::TypeInner::Struct { .. }
-        | naga::TypeInner::Array { .. } => {
-          quote!(wgpu::BindingResource::Buffer(bindings.#binding_name))
+      let resource_type = match (&binding.binding_type.inner, binding.count) {
+        (
+          naga::TypeInner::Scalar(_)
+          | naga::TypeInner::Struct { .. }
+          | naga::TypeInner::Array { .. },
+          None,
+        ) => {
+          let buffer_binding = if options.typed_buffer_bindings {
+            quote!(bindings.#binding_name.into())
+          } else {
+            quote!(bindings.#binding_name)
+          };
+          quote!(wgpu::BindingResource::Buffer(#buffer_binding))
         }
-        naga::TypeInner::Image { .. } => {
+        (naga::TypeInner::Image { .. }, None) => {
           quote!(wgpu::BindingResource::TextureView(bindings.#binding_name))
         }
-        naga::TypeInner::Sampler { .. } => {
+        (naga::TypeInner::Sampler { .. }, None) => {
           quote!(wgpu::BindingResource::Sampler(bindings.#binding_name))
         }
-        // TODO: Better error handling.
-        _ => panic!("Failed to generate BindingType."),
+        (naga::TypeInner::Struct { .. }, Some(_)) => {
+          quote!(wgpu::BindingResource::BufferArray(bindings.#binding_name))
+        }
+        (naga::TypeInner::Image { .. }, Some(_)) => {
+          quote!(wgpu::BindingResource::TextureViewArray(bindings.#binding_name))
+        }
+        (naga::TypeInner::Sampler { .. }, Some(_)) => {
+          quote!(wgpu::BindingResource::SamplerArray(bindings.#binding_name))
+        }
+        (naga::TypeInner::AccelerationStructure, None) => {
+          quote!(bindings.#binding_name.into())
+        }
+        _ => return Err(unsupported_binding_type(module, binding)),
       };
 
-      quote! {
+      Ok(quote! {
           wgpu::BindGroupEntry {
               binding: #binding_index,
               resource: #resource_type,
           }
-      }
+      })
     })
-    .collect();
-
-  // TODO: Support compute shader with vertex/fragment in the same module?
-  let is_compute = shader_stages == wgpu::ShaderStages::COMPUTE;
-
-  let render_pass = if is_compute {
-    quote!(wgpu::ComputePass<'a>)
-  } else {
-    quote!(wgpu::RenderPass<'a>)
-  };
+    .collect::<Result<Vec<_>, _>>()?;
 
   let bind_group_name = indexed_name_to_ident("BindGroup", group_no);
   let bind_group_layout_name = indexed_name_to_ident("BindGroupLayout", group_no);
 
+  let layout_name = indexed_name_to_ident("Layout", group_no);
+  let layout_doc = format!(
+    " The layout for creating bind groups with [{bind_group_name}::from_bindings_with_layout]."
+  );
   let layout_descriptor_name = indexed_name_to_ident("LAYOUT_DESCRIPTOR", group_no);
 
+  let offsets_name = indexed_name_to_ident("DynamicOffsets", group_no);
+  let label = object_label(mod_name, &format!("BindGroup{group_no}"), options);
   let group_no = Index::from(group_no as usize);
 
-  quote! {
+  let dynamic_offsets: Vec<_> = dynamic_offset_bindings(mod_name, group, options)
+    .iter()
+    .map(|binding| {
+      let (_, demangled_name) =
+        demangle_splitting_mod_path_and_item(binding.name.as_ref().unwrap());
+      let field_name = Ident::new(&demangled_name, Span::call_site());
+      quote!(offsets.#field_name)
+    })
+    .collect();
+
+  let set = if dynamic_offsets.is_empty() {
+    quote! {
+        pub fn set<'a>(&'a self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+            pass.set_bind_group(#group_no, &self.0, &[]);
+        }
+    }
+  } else {
+    quote! {
+        pub fn set<'a>(
+            &'a self,
+            pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            offsets: #offsets_name,
+        ) {
+            pass.set_bind_group(#group_no, &self.0, &[#(#dynamic_offsets),*]);
+        }
+    }
+  };
+
+  Ok(quote! {
+      #[doc = #layout_doc]
+      #[derive(Debug)]
+      pub struct #layout_name(wgpu::BindGroupLayout);
+
+      impl #layout_name {
+          pub fn new(device: &wgpu::Device) -> Self {
+              Self(#bind_group_name::get_bind_group_layout(device))
+          }
+      }
+
+      impl std::ops::Deref for #layout_name {
+          type Target = wgpu::BindGroupLayout;
+
+          fn deref(&self) -> &Self::Target {
+              &self.0
+          }
+      }
+
       impl #bind_group_name {
           pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
               device.create_bind_group_layout(&#layout_descriptor_name)
           }
 
           pub fn from_bindings(device: &wgpu::Device, bindings: #bind_group_layout_name) -> Self {
-              let bind_group_layout = device.create_bind_group_layout(&#layout_descriptor_name);
+              Self::from_bindings_with_layout(device, &#layout_name::new(device), bindings)
+          }
+
+          pub fn from_bindings_with_layout(
+              device: &wgpu::Device,
+              layout: &#layout_name,
+              bindings: #bind_group_layout_name,
+          ) -> Self {
               let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                  layout: &bind_group_layout,
+                  layout,
                   entries: &[
                       #(#entries),*
                   ],
-                  label: None,
+                  label: Some(#label),
               });
               Self(bind_group)
           }
 
-          pub fn set<'a>(&'a self, render_pass: &mut #render_pass) {
-              render_pass.set_bind_group(#group_no, &self.0, &[]);
-          }
+          #set
       }
-  }
+  })
 }
 
-pub fn get_bind_group_data(
-  module: &naga::Module,
-) -> Result<BTreeMap<u32, GroupData>, CreateModuleError> {
-  // Use a BTree to sort type and field names by group index.
-  // This isn't strictly necessary but makes the generated code cleaner.
-  let mut groups = BTreeMap::new();
-
-  for global_handle in module.global_variables.iter() {
-    let global = &module.global_variables[global_handle.0];
-    if let Some(binding) = &global.binding {
-      let group = groups.entry(binding.group).or_insert(GroupData {
-        bindings: Vec::new(),
-      });
-      let binding_type = &module.types[module.global_variables[global_handle.0].ty];
-
-      let group_binding = GroupBinding {
-        name: global.name.clone(),
-        binding_index: binding.binding,
-        binding_type,
-        address_space: global.space,
-      };
-      // Repeated bindings will probably cause a compile error.
-      // We'll still check for it here just in case.
-      if group
-        .bindings
-        .iter()
-        .any(|g| g.binding_index == binding.binding)
-      {
-        return Err(CreateModuleError::DuplicateBinding {
-          binding: binding.binding,
-        });
-      }
-      group.bindings.push(group_binding);
+fn is_runtime_sized_array(module: &naga::Module, ty: naga::Handle<naga::Type>) -> bool {
+  matches!(
+    module.types[ty].inner,
+    naga::TypeInner::Array {
+      size: naga::ArraySize::Dynamic,
+      ..
     }
-  }
+  )
+}
 
-  // wgpu expects bind groups to be consecutive starting from 0.
-  if groups.keys().map(|i| *i as usize).eq(0..groups.len()) {
-    Ok(groups)
-  } else {
-    Err(CreateModuleError::NonConsecutiveBindGroups)
+/// Runtime-sized arrays or structs ending in a runtime-sized array.
+fn is_runtime_sized(module: &naga::Module, binding: &GroupBinding) -> bool {
+  match &binding.binding_type.inner {
+    naga::TypeInner::Array {
+      size: naga::ArraySize::Dynamic,
+      ..
+    } => true,
+    naga::TypeInner::Struct { members, .. } => members
+      .last()
+      .is_some_and(|m| is_runtime_sized_array(module, m.ty)),
+    _ => false,
   }
 }
 
-#[cfg(test)]
-mod tests {
-  use indoc::indoc;
-
-  use super::*;
-  use crate::assert_tokens_eq;
+/// The demangled name of the binding's global variable for errors.
+fn binding_name(binding: &GroupBinding) -> String {
+  demangle(binding.name.as_deref().unwrap_or_default()).into()
+}
+
+fn unsupported_binding_type(
+  module: &naga::Module,
+  binding: &GroupBinding,
+) -> CreateModuleError {
+  CreateModuleError::UnsupportedBindingType {
+    name: binding_name(binding),
+    ty: type_name(module, binding.binding_type),
+    src: None,
+    span: None,
+  }
+}
+
+/// The binding type for acceleration structures.
+pub fn acceleration_structure_module() -> TokenStream {
+  quote! {
+      /// wgpu doesn't have a binding resource for acceleration structures yet.
+      /// This type has no values, so bind groups with acceleration structures can't be
+      /// created with `from_bindings` until wgpu supports them.
+      #[derive(Debug, Clone, Copy)]
+      pub enum AccelerationStructureBinding {}
+
+      impl<'a> From<AccelerationStructureBinding> for wgpu::BindingResource<'a> {
+          fn from(binding: AccelerationStructureBinding) -> Self {
+              match binding {}
+          }
+      }
+  }
+}
+
+/// Types for tagging buffers with the WGSL type they contain.
+pub fn typed_buffer_module() -> TokenStream {
+  quote! {
+      /// A buffer tagged with the type of its contents.
+      pub struct TypedBuffer<T: ?Sized> {
+          buffer: wgpu::Buffer,
+          _phantom: std::marker::PhantomData<T>,
+      }
+
+      impl<T: ?Sized> TypedBuffer<T> {
+          /// Tags `buffer` as containing values of type `T`.
+          pub fn new(buffer: wgpu::Buffer) -> Self {
+              Self {
+                  buffer,
+                  _phantom: std::marker::PhantomData,
+              }
+          }
+
+          pub fn buffer(&self) -> &wgpu::Buffer {
+              &self.buffer
+          }
+
+          pub fn into_inner(self) -> wgpu::Buffer {
+              self.buffer
+          }
+
+          pub fn as_entire_binding(&self) -> TypedBufferBinding<'_, T> {
+              self.binding(0, None)
+          }
+
+          pub fn binding(
+              &self,
+              offset: wgpu::BufferAddress,
+              size: Option<wgpu::BufferSize>,
+          ) -> TypedBufferBinding<'_, T> {
+              TypedBufferBinding {
+                  binding: wgpu::BufferBinding {
+                      buffer: &self.buffer,
+                      offset,
+                      size,
+                  },
+                  _phantom: std::marker::PhantomData,
+              }
+          }
+      }
+
+      impl<T: ?Sized> std::fmt::Debug for TypedBuffer<T> {
+          fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+              f.debug_struct("TypedBuffer").field("buffer", &self.buffer).finish()
+          }
+      }
+
+      impl<T: ?Sized> std::ops::Deref for TypedBuffer<T> {
+          type Target = wgpu::Buffer;
+
+          fn deref(&self) -> &Self::Target {
+              &self.buffer
+          }
+      }
+
+      /// A buffer binding that can only be used for bindings of type `T`.
+      pub struct TypedBufferBinding<'a, T: ?Sized> {
+          binding: wgpu::BufferBinding<'a>,
+          _phantom: std::marker::PhantomData<T>,
+      }
+
+      impl<'a, T: ?Sized> Clone for TypedBufferBinding<'a, T> {
+          fn clone(&self) -> Self {
+              Self {
+                  binding: self.binding.clone(),
+                  _phantom: std::marker::PhantomData,
+              }
+          }
+      }
+
+      impl<'a, T: ?Sized> std::fmt::Debug for TypedBufferBinding<'a, T> {
+          fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+              f.debug_struct("TypedBufferBinding")
+                  .field("binding", &self.binding)
+                  .finish()
+          }
+      }
+
+      impl<'a, T: ?Sized> From<TypedBufferBinding<'a, T>> for wgpu::BufferBinding<'a> {
+          fn from(binding: TypedBufferBinding<'a, T>) -> Self {
+              binding.binding
+          }
+      }
+  }
+}
+
+pub fn get_bind_group_data<'a>(
+  module: &'a naga::Module,
+  info: &naga::valid::ModuleInfo,
+) -> Result<BTreeMap<u32, GroupData<'a>>, CreateModuleError> {
+  // Use a BTree to sort type and field names by group index.
+  // This isn't strictly necessary but makes the generated code cleaner.
+  let mut groups = BTreeMap::new();
+  let global_stages = global_shader_stages(module, info);
+  let non_filtering = non_filtering_globals(module, info);
+  let layouter = module_layouter(module)?;
+
+  for global_handle in module.global_variables.iter() {
+    let global = &module.global_variables[global_handle.0];
+    if let Some(binding) = &global.binding {
+      let group = groups.entry(binding.group).or_insert(GroupData {
+        bindings: Vec::new(),
+        shared_module: None,
+      });
+      let (binding_type, count) = match module.types[global.ty].inner {
+        naga::TypeInner::BindingArray { base, size } => match size {
+          naga::ArraySize::Constant(count) => (base, Some(count)),
+          naga::ArraySize::Dynamic => {
+            return Err(CreateModuleError::RuntimeSizedBindingArray {
+              name: demangle(global.name.as_deref().unwrap_or_default()).into(),
+              src: None,
+              span: None,
+            })
+          }
+        },
+        _ => (global.ty, None),
+      };
+
+      let group_binding = GroupBinding {
+        name: global.name.clone(),
+        binding_index: binding.binding,
+        binding_type: &module.types[binding_type],
+        count,
+        size: layouter[binding_type].size,
+        address_space: global.space,
+        visibility: global_stages
+          .get(&global_handle.0)
+          .copied()
+          .unwrap_or(wgpu::ShaderStages::NONE),
+        non_filtering: non_filtering.contains(&global_handle.0),
+      };
+      // Repeated bindings will probably cause a compile error.
+      // We'll still check for it here just in case.
+      if group
+        .bindings
+        .iter()
+        .any(|g| g.binding_index == binding.binding)
+      {
+        return Err(CreateModuleError::DuplicateBinding {
+          binding: binding.binding,
+          name: binding_name(&group_binding),
+          src: None,
+          span: None,
+        });
+      }
+      group.bindings.push(group_binding);
+    }
+  }
+
+  Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+
+  use super::*;
+  use crate::wgsl::module_info;
+  use crate::{assert_tokens_eq, WgslTypeSerializeStrategy};
 
   #[test]
   fn bind_group_data_consecutive_bind_groups() {
@@ -405,7 +1094,12 @@ mod tests {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    assert_eq!(3, get_bind_group_data(&module).unwrap().len());
+    assert_eq!(
+      3,
+      get_bind_group_data(&module, &module_info(&module))
+        .unwrap()
+        .len()
+    );
   }
 
   #[test]
@@ -418,10 +1112,8 @@ mod tests {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    assert!(matches!(
-      get_bind_group_data(&module),
-      Err(CreateModuleError::NonConsecutiveBindGroups)
-    ));
+    let groups = get_bind_group_data(&module, &module_info(&module)).unwrap();
+    assert_eq!(vec![1], groups.keys().copied().collect::<Vec<_>>());
   }
 
   #[test]
@@ -436,20 +1128,18 @@ mod tests {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    assert!(matches!(
-      get_bind_group_data(&module),
-      Err(CreateModuleError::NonConsecutiveBindGroups)
-    ));
+    let groups = get_bind_group_data(&module, &module_info(&module)).unwrap();
+    assert_eq!(vec![0, 1, 3], groups.keys().copied().collect::<Vec<_>>());
   }
 
   #[test]
   fn bind_groups_module_compute() {
     let source = indoc! {r#"
-            struct VertexInput0 {};
-            struct VertexWeight {};
-            struct Vertices {};
-            struct VertexWeights {};
-            struct Transforms {};
+            struct VertexInput0 { f: f32 };
+            struct VertexWeight { f: f32 };
+            struct Vertices { f: f32 };
+            struct VertexWeights { f: f32 };
+            struct Transforms { f: f32 };
 
             @group(0) @binding(0) var<storage, read> src: array<vec4<f32>>;
             @group(0) @binding(1) var<storage, read> vertex_weights: VertexWeights;
@@ -459,13 +1149,21 @@ mod tests {
 
             @compute
             @workgroup_size(64)
-            fn main() {}
+            fn main() {
+                dst.f = src[0].x * vertex_weights.f * transforms.f;
+            }
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let bind_group_data = get_bind_group_data(&module).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
 
-    let actual = bind_groups_module(&bind_group_data, wgpu::ShaderStages::COMPUTE);
+    let actual = bind_groups_module(
+      "test",
+      &module,
+      &bind_group_data,
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -480,7 +1178,7 @@ mod tests {
                   pub dst: wgpu::BufferBinding<'a>,
               }
               const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-                  label: None,
+                  label: Some("test::BindGroup0::Layout"),
                   entries: &[
                       wgpu::BindGroupLayoutEntry {
                           binding: 0,
@@ -490,7 +1188,7 @@ mod tests {
                                   read_only: true,
                               },
                               has_dynamic_offset: false,
-                              min_binding_size: None,
+                              min_binding_size: std::num::NonZeroU64::new(16),
                           },
                           count: None,
                       },
@@ -502,7 +1200,7 @@ mod tests {
                                   read_only: true,
                               },
                               has_dynamic_offset: false,
-                              min_binding_size: None,
+                              min_binding_size: std::num::NonZeroU64::new(4),
                           },
                           count: None,
                       },
@@ -514,22 +1212,42 @@ mod tests {
                                   read_only: false,
                               },
                               has_dynamic_offset: false,
-                              min_binding_size: None,
+                              min_binding_size: std::num::NonZeroU64::new(4),
                           },
                           count: None,
                       },
                   ],
               };
+              /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout0(wgpu::BindGroupLayout);
+              impl Layout0 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup0::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout0 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
               impl BindGroup0 {
                   pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
                       device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
                   }
                   pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout0) -> Self {
-                      let bind_group_layout = device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0);
+                      Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout0,
+                      bindings: BindGroupLayout0,
+                  ) -> Self {
                       let bind_group = device
                           .create_bind_group(
                               &wgpu::BindGroupDescriptor {
-                                  layout: &bind_group_layout,
+                                  layout,
                                   entries: &[
                                       wgpu::BindGroupEntry {
                                           binding: 0,
@@ -546,13 +1264,16 @@ mod tests {
                                           resource: wgpu::BindingResource::Buffer(bindings.dst),
                                       },
                                   ],
-                                  label: None,
+                                  label: Some("test::BindGroup0"),
                               },
                           );
                       Self(bind_group)
                   }
-                  pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
-                      render_pass.set_bind_group(0, &self.0, &[]);
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(0, &self.0, &[]);
                   }
               }
               #[derive(Debug)]
@@ -563,7 +1284,7 @@ mod tests {
                   pub transforms: wgpu::BufferBinding<'a>,
               }
               const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-                  label: None,
+                  label: Some("test::BindGroup1::Layout"),
                   entries: &[
                       wgpu::BindGroupLayoutEntry {
                           binding: 0,
@@ -571,35 +1292,73 @@ mod tests {
                           ty: wgpu::BindingType::Buffer {
                               ty: wgpu::BufferBindingType::Uniform,
                               has_dynamic_offset: false,
-                              min_binding_size: None,
+                              min_binding_size: std::num::NonZeroU64::new(4),
                           },
                           count: None,
                       },
                   ],
               };
+              /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout1(wgpu::BindGroupLayout);
+              impl Layout1 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup1::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout1 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
               impl BindGroup1 {
                   pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
                       device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
                   }
                   pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout1) -> Self {
-                      let bind_group_layout = device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1);
+                      Self::from_bindings_with_layout(device, &Layout1::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout1,
+                      bindings: BindGroupLayout1,
+                  ) -> Self {
                       let bind_group = device
                           .create_bind_group(
                               &wgpu::BindGroupDescriptor {
-                                  layout: &bind_group_layout,
+                                  layout,
                                   entries: &[
                                       wgpu::BindGroupEntry {
                                           binding: 0,
                                           resource: wgpu::BindingResource::Buffer(bindings.transforms),
                                       },
                                   ],
-                                  label: None,
+                                  label: Some("test::BindGroup1"),
                               },
                           );
                       Self(bind_group)
                   }
-                  pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
-                      render_pass.set_bind_group(1, &self.0, &[]);
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(1, &self.0, &[]);
+                  }
+              }
+              /// The bind group layouts for this module.
+              /// Create these once and reuse them for bind groups and pipeline layouts.
+              #[derive(Debug)]
+              pub struct Layouts {
+                  pub bind_group0: Layout0,
+                  pub bind_group1: Layout1,
+              }
+              impl Layouts {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self {
+                          bind_group0: Layout0::new(device),
+                          bind_group1: Layout1::new(device),
+                      }
                   }
               }
               #[derive(Debug, Copy, Clone)]
@@ -608,14 +1367,14 @@ mod tests {
                   pub bind_group1: &'a BindGroup1,
               }
               impl<'a> BindGroups<'a> {
-                  pub fn set(&self, pass: &mut wgpu::ComputePass<'a>) {
+                  pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
                       self.bind_group0.set(pass);
                       self.bind_group1.set(pass);
                   }
               }
           }
           pub fn set_bind_groups<'a>(
-              pass: &mut wgpu::ComputePass<'a>,
+              pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
               bind_group0: &'a bind_groups::BindGroup0,
               bind_group1: &'a bind_groups::BindGroup1,
           ) {
@@ -632,7 +1391,7 @@ mod tests {
     // Test different texture and sampler types.
     // TODO: Storage textures.
     let source = indoc! {r#"
-            struct Transforms {};
+            struct Transforms { f: f32 };
 
             @group(0) @binding(0)
             var color_texture: texture_2d<f32>;
@@ -659,17 +1418,33 @@ mod tests {
             @group(1) @binding(1) var<uniform> one: f32;
 
             @vertex
-            fn vs_main() {}
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return vec4(transforms.f, one, 0.0, 1.0);
+            }
 
             @fragment
-            fn fs_main() {}
+            fn fs_main() -> @location(0) vec4<f32> {
+                let color = textureSample(color_texture, color_sampler, vec2(0.0));
+                let depth = textureSampleCompare(depth_texture, comparison_sampler, vec2(0.0), 0.0);
+                let storage = textureLoad(storage_tex_read, vec2(0));
+                textureStore(storage_tex_write, vec2(0), vec4(0));
+                textureStore(storage_tex_read_write, vec2(0), textureLoad(storage_tex_read_write, vec2(0)));
+                let color_msaa = textureLoad(color_texture_msaa, vec2(0), 0);
+                let depth_msaa = textureLoad(depth_texture_msaa, vec2(0), 0);
+                return color * depth * storage * color_msaa * depth_msaa * one;
+            }
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let bind_group_data = get_bind_group_data(&module).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
 
-    let actual =
-      bind_groups_module(&bind_group_data, wgpu::ShaderStages::VERTEX_FRAGMENT);
+    let actual = bind_groups_module(
+      "test",
+      &module,
+      &bind_group_data,
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
 
     // TODO: Are storage buffers valid for vertex/fragment?
     assert_tokens_eq!(
@@ -691,11 +1466,11 @@ mod tests {
                   pub depth_texture_msaa: &'a wgpu::TextureView,
               }
               const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-                  label: None,
+                  label: Some("test::BindGroup0::Layout"),
                   entries: &[
                       wgpu::BindGroupLayoutEntry {
                           binding: 0,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
                           ty: wgpu::BindingType::Texture {
                               sample_type: wgpu::TextureSampleType::Float {
                                   filterable: true,
@@ -707,13 +1482,13 @@ mod tests {
                       },
                       wgpu::BindGroupLayoutEntry {
                           binding: 1,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
                           ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                           count: None,
                       },
                       wgpu::BindGroupLayoutEntry {
                           binding: 2,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
                           ty: wgpu::BindingType::Texture {
                               sample_type: wgpu::TextureSampleType::Depth,
                               view_dimension: wgpu::TextureViewDimension::D2,
@@ -723,13 +1498,13 @@ mod tests {
                       },
                       wgpu::BindGroupLayoutEntry {
                           binding: 3,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
                           ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
                           count: None,
                       },
                       wgpu::BindGroupLayoutEntry {
                           binding: 4,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
                           ty: wgpu::BindingType::StorageTexture {
                               access: wgpu::StorageTextureAccess::ReadOnly,
                               format: wgpu::TextureFormat::R32Float,
@@ -738,206 +1513,1515 @@ mod tests {
                           count: None,
                       },
                       wgpu::BindGroupLayoutEntry {
-                          binding: 5,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                          ty: wgpu::BindingType::StorageTexture {
-                              access: wgpu::StorageTextureAccess::WriteOnly,
-                              format: wgpu::TextureFormat::Rg32Sint,
-                              view_dimension: wgpu::TextureViewDimension::D2,
+                          binding: 5,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
+                          ty: wgpu::BindingType::StorageTexture {
+                              access: wgpu::StorageTextureAccess::WriteOnly,
+                              format: wgpu::TextureFormat::Rg32Sint,
+                              view_dimension: wgpu::TextureViewDimension::D2,
+                          },
+                          count: None,
+                      },
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 6,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
+                          ty: wgpu::BindingType::StorageTexture {
+                              access: wgpu::StorageTextureAccess::ReadWrite,
+                              format: wgpu::TextureFormat::Rgba8Uint,
+                              view_dimension: wgpu::TextureViewDimension::D2,
+                          },
+                          count: None,
+                      },
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 7,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
+                          ty: wgpu::BindingType::Texture {
+                              sample_type: wgpu::TextureSampleType::Float {
+                                  filterable: false,
+                              },
+                              view_dimension: wgpu::TextureViewDimension::D2,
+                              multisampled: true,
+                          },
+                          count: None,
+                      },
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 8,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
+                          ty: wgpu::BindingType::Texture {
+                              sample_type: wgpu::TextureSampleType::Depth,
+                              view_dimension: wgpu::TextureViewDimension::D2,
+                              multisampled: true,
+                          },
+                          count: None,
+                      },
+                  ],
+              };
+              /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout0(wgpu::BindGroupLayout);
+              impl Layout0 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup0::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout0 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
+              impl BindGroup0 {
+                  pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+                      device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+                  }
+                  pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout0) -> Self {
+                      Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout0,
+                      bindings: BindGroupLayout0,
+                  ) -> Self {
+                      let bind_group = device
+                          .create_bind_group(
+                              &wgpu::BindGroupDescriptor {
+                                  layout,
+                                  entries: &[
+                                      wgpu::BindGroupEntry {
+                                          binding: 0,
+                                          resource: wgpu::BindingResource::TextureView(
+                                              bindings.color_texture,
+                                          ),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 1,
+                                          resource: wgpu::BindingResource::Sampler(
+                                              bindings.color_sampler,
+                                          ),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 2,
+                                          resource: wgpu::BindingResource::TextureView(
+                                              bindings.depth_texture,
+                                          ),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 3,
+                                          resource: wgpu::BindingResource::Sampler(
+                                              bindings.comparison_sampler,
+                                          ),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 4,
+                                          resource: wgpu::BindingResource::TextureView(
+                                              bindings.storage_tex_read,
+                                          ),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 5,
+                                          resource: wgpu::BindingResource::TextureView(
+                                              bindings.storage_tex_write,
+                                          ),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 6,
+                                          resource: wgpu::BindingResource::TextureView(
+                                              bindings.storage_tex_read_write,
+                                          ),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 7,
+                                          resource: wgpu::BindingResource::TextureView(
+                                              bindings.color_texture_msaa,
+                                          ),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 8,
+                                          resource: wgpu::BindingResource::TextureView(
+                                              bindings.depth_texture_msaa,
+                                          ),
+                                      },
+                                  ],
+                                  label: Some("test::BindGroup0"),
+                              },
+                          );
+                      Self(bind_group)
+                  }
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(0, &self.0, &[]);
+                  }
+              }
+              #[derive(Debug)]
+              pub struct BindGroup1(wgpu::BindGroup);
+              #[allow(non_snake_case)]
+              #[derive(Debug)]
+              pub struct BindGroupLayout1<'a> {
+                  pub transforms: wgpu::BufferBinding<'a>,
+                  pub one: wgpu::BufferBinding<'a>,
+              }
+              const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                  label: Some("test::BindGroup1::Layout"),
+                  entries: &[
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 0,
+                          visibility: wgpu::ShaderStages::VERTEX,
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Uniform,
+                              has_dynamic_offset: false,
+                              min_binding_size: std::num::NonZeroU64::new(4),
+                          },
+                          count: None,
+                      },
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 1,
+                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Uniform,
+                              has_dynamic_offset: false,
+                              min_binding_size: std::num::NonZeroU64::new(4),
+                          },
+                          count: None,
+                      },
+                  ],
+              };
+              /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout1(wgpu::BindGroupLayout);
+              impl Layout1 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup1::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout1 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
+              impl BindGroup1 {
+                  pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+                      device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
+                  }
+                  pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout1) -> Self {
+                      Self::from_bindings_with_layout(device, &Layout1::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout1,
+                      bindings: BindGroupLayout1,
+                  ) -> Self {
+                      let bind_group = device
+                          .create_bind_group(
+                              &wgpu::BindGroupDescriptor {
+                                  layout,
+                                  entries: &[
+                                      wgpu::BindGroupEntry {
+                                          binding: 0,
+                                          resource: wgpu::BindingResource::Buffer(bindings.transforms),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 1,
+                                          resource: wgpu::BindingResource::Buffer(bindings.one),
+                                      },
+                                  ],
+                                  label: Some("test::BindGroup1"),
+                              },
+                          );
+                      Self(bind_group)
+                  }
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(1, &self.0, &[]);
+                  }
+              }
+              /// The bind group layouts for this module.
+              /// Create these once and reuse them for bind groups and pipeline layouts.
+              #[derive(Debug)]
+              pub struct Layouts {
+                  pub bind_group0: Layout0,
+                  pub bind_group1: Layout1,
+              }
+              impl Layouts {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self {
+                          bind_group0: Layout0::new(device),
+                          bind_group1: Layout1::new(device),
+                      }
+                  }
+              }
+              #[derive(Debug, Copy, Clone)]
+              pub struct BindGroups<'a> {
+                  pub bind_group0: &'a BindGroup0,
+                  pub bind_group1: &'a BindGroup1,
+              }
+              impl<'a> BindGroups<'a> {
+                  pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                      self.bind_group0.set(pass);
+                      self.bind_group1.set(pass);
+                  }
+              }
+          }
+          pub fn set_bind_groups<'a>(
+              pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+              bind_group0: &'a bind_groups::BindGroup0,
+              bind_group1: &'a bind_groups::BindGroup1,
+          ) {
+              bind_group0.set(pass);
+              bind_group1.set(pass);
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn shared_bind_group_items_reexport_layout() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> camera: f32;
+
+            @vertex
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return vec4(camera);
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+
+    let actual = shared_bind_group_items(
+      "camera",
+      0,
+      &bind_group_data[&0],
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          pub use super::_root::camera::bind_groups::{BindGroup0, BindGroupLayout0, Layout0};
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn bind_group_layout_descriptor_per_binding_visibility() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> vertex_only: f32;
+            @group(0) @binding(1) var<storage, read_write> vertex_compute: f32;
+            @group(0) @binding(2) var<uniform> unused: f32;
+
+            @vertex
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return vec4(vertex_only + vertex_compute);
+            }
+
+            @compute
+            @workgroup_size(64)
+            fn cs_main() {
+                vertex_compute = 1.0;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+
+    let actual = bind_group_layout_descriptor(
+      "test",
+      &module,
+      0,
+      &bind_group_data[&0],
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+              label: Some("test::BindGroup0::Layout"),
+              entries: &[
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 0,
+                      visibility: wgpu::ShaderStages::VERTEX,
+                      ty: wgpu::BindingType::Buffer {
+                          ty: wgpu::BufferBindingType::Uniform,
+                          has_dynamic_offset: false,
+                          min_binding_size: std::num::NonZeroU64::new(4),
+                      },
+                      count: None,
+                  },
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 1,
+                      visibility: wgpu::ShaderStages::VERTEX.union(wgpu::ShaderStages::COMPUTE),
+                      ty: wgpu::BindingType::Buffer {
+                          ty: wgpu::BufferBindingType::Storage {
+                              read_only: false,
+                          },
+                          has_dynamic_offset: false,
+                          min_binding_size: std::num::NonZeroU64::new(4),
+                      },
+                      count: None,
+                  },
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 2,
+                      visibility: wgpu::ShaderStages::NONE,
+                      ty: wgpu::BindingType::Buffer {
+                          ty: wgpu::BufferBindingType::Uniform,
+                          has_dynamic_offset: false,
+                          min_binding_size: std::num::NonZeroU64::new(4),
+                      },
+                      count: None,
+                  },
+              ],
+          };
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn bind_group_layout_descriptor_runtime_sized_min_binding_size() {
+    let source = indoc! {r#"
+            struct Lights { count: u32, data: array<vec4<f32>> };
+
+            @group(0) @binding(0) var<storage, read> lights: Lights;
+            @group(0) @binding(1) var<storage, read> weights: array<vec2<f32>>;
+
+            @compute
+            @workgroup_size(64)
+            fn cs_main() {
+                let x = lights.data[0].x + weights[0].x;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+
+    let actual = bind_group_layout_descriptor(
+      "test",
+      &module,
+      0,
+      &bind_group_data[&0],
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
+
+    // The fixed size prefix is padded to the array alignment and followed by one element.
+    assert_tokens_eq!(
+      quote! {
+          const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+              label: Some("test::BindGroup0::Layout"),
+              entries: &[
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 0,
+                      visibility: wgpu::ShaderStages::COMPUTE,
+                      ty: wgpu::BindingType::Buffer {
+                          ty: wgpu::BufferBindingType::Storage {
+                              read_only: true,
+                          },
+                          has_dynamic_offset: false,
+                          min_binding_size: std::num::NonZeroU64::new(32),
+                      },
+                      count: None,
+                  },
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 1,
+                      visibility: wgpu::ShaderStages::COMPUTE,
+                      ty: wgpu::BindingType::Buffer {
+                          ty: wgpu::BufferBindingType::Storage {
+                              read_only: true,
+                          },
+                          has_dynamic_offset: false,
+                          min_binding_size: std::num::NonZeroU64::new(8),
+                      },
+                      count: None,
+                  },
+              ],
+          };
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn bind_group_layout_descriptor_texture_sample_types() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var color: texture_2d<f32>;
+            @group(0) @binding(1) var depth_r32: texture_2d<f32>;
+            @group(0) @binding(2) var ids: texture_2d<u32>;
+            @group(0) @binding(3) var offsets: texture_2d<i32>;
+            @group(0) @binding(4) var color_sampler: sampler;
+            @group(0) @binding(5) var id_sampler: sampler;
+
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> {
+                let color = textureSample(color, color_sampler, vec2(0.0));
+                let depth = textureLoad(depth_r32, vec2(0), 0);
+                let id = textureGather(0, ids, id_sampler, vec2(0.0));
+                let offset = textureLoad(offsets, vec2(0), 0);
+                return color * depth * vec4<f32>(id) * vec4<f32>(offset);
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+
+    let actual = bind_group_layout_descriptor(
+      "test",
+      &module,
+      0,
+      &bind_group_data[&0],
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+              label: Some("test::BindGroup0::Layout"),
+              entries: &[
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 0,
+                      visibility: wgpu::ShaderStages::FRAGMENT,
+                      ty: wgpu::BindingType::Texture {
+                          sample_type: wgpu::TextureSampleType::Float {
+                              filterable: true,
+                          },
+                          view_dimension: wgpu::TextureViewDimension::D2,
+                          multisampled: false,
+                      },
+                      count: None,
+                  },
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 1,
+                      visibility: wgpu::ShaderStages::FRAGMENT,
+                      ty: wgpu::BindingType::Texture {
+                          sample_type: wgpu::TextureSampleType::Float {
+                              filterable: false,
+                          },
+                          view_dimension: wgpu::TextureViewDimension::D2,
+                          multisampled: false,
+                      },
+                      count: None,
+                  },
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 2,
+                      visibility: wgpu::ShaderStages::FRAGMENT,
+                      ty: wgpu::BindingType::Texture {
+                          sample_type: wgpu::TextureSampleType::Uint,
+                          view_dimension: wgpu::TextureViewDimension::D2,
+                          multisampled: false,
+                      },
+                      count: None,
+                  },
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 3,
+                      visibility: wgpu::ShaderStages::FRAGMENT,
+                      ty: wgpu::BindingType::Texture {
+                          sample_type: wgpu::TextureSampleType::Sint,
+                          view_dimension: wgpu::TextureViewDimension::D2,
+                          multisampled: false,
+                      },
+                      count: None,
+                  },
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 4,
+                      visibility: wgpu::ShaderStages::FRAGMENT,
+                      ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                      count: None,
+                  },
+                  wgpu::BindGroupLayoutEntry {
+                      binding: 5,
+                      visibility: wgpu::ShaderStages::FRAGMENT,
+                      ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
+                      count: None,
+                  },
+              ],
+          };
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn bind_groups_module_binding_arrays() {
+    let source = indoc! {r#"
+            struct Material { f: f32 };
+
+            @group(0) @binding(0) var textures: binding_array<texture_2d<f32>, 4>;
+            @group(0) @binding(1) var samplers: binding_array<sampler, 2>;
+            @group(0) @binding(2) var<storage, read> materials: binding_array<Material, 8>;
+
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> {
+                let color = textureSample(textures[1], samplers[0], vec2(0.0));
+                return color * materials[2].f;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+
+    let actual = bind_groups_module(
+      "test",
+      &module,
+      &bind_group_data,
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          pub mod bind_groups {
+              #[derive(Debug)]
+              pub struct BindGroup0(wgpu::BindGroup);
+              #[allow(non_snake_case)]
+              #[derive(Debug)]
+              pub struct BindGroupLayout0<'a> {
+                  pub textures: &'a [&'a wgpu::TextureView],
+                  pub samplers: &'a [&'a wgpu::Sampler],
+                  pub materials: &'a [wgpu::BufferBinding<'a>],
+              }
+              const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                  label: Some("test::BindGroup0::Layout"),
+                  entries: &[
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 0,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
+                          ty: wgpu::BindingType::Texture {
+                              sample_type: wgpu::TextureSampleType::Float {
+                                  filterable: true,
+                              },
+                              view_dimension: wgpu::TextureViewDimension::D2,
+                              multisampled: false,
+                          },
+                          count: std::num::NonZeroU32::new(4),
+                      },
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 1,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
+                          ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                          count: std::num::NonZeroU32::new(2),
+                      },
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 2,
+                          visibility: wgpu::ShaderStages::FRAGMENT,
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Storage {
+                                  read_only: true,
+                              },
+                              has_dynamic_offset: false,
+                              min_binding_size: std::num::NonZeroU64::new(4),
+                          },
+                          count: std::num::NonZeroU32::new(8),
+                      },
+                  ],
+              };
+              /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout0(wgpu::BindGroupLayout);
+              impl Layout0 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup0::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout0 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
+              impl BindGroup0 {
+                  pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+                      device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+                  }
+                  pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout0) -> Self {
+                      Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout0,
+                      bindings: BindGroupLayout0,
+                  ) -> Self {
+                      let bind_group = device
+                          .create_bind_group(
+                              &wgpu::BindGroupDescriptor {
+                                  layout,
+                                  entries: &[
+                                      wgpu::BindGroupEntry {
+                                          binding: 0,
+                                          resource: wgpu::BindingResource::TextureViewArray(
+                                              bindings.textures,
+                                          ),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 1,
+                                          resource: wgpu::BindingResource::SamplerArray(
+                                              bindings.samplers,
+                                          ),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 2,
+                                          resource: wgpu::BindingResource::BufferArray(
+                                              bindings.materials,
+                                          ),
+                                      },
+                                  ],
+                                  label: Some("test::BindGroup0"),
+                              },
+                          );
+                      Self(bind_group)
+                  }
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(0, &self.0, &[]);
+                  }
+              }
+              /// The bind group layouts for this module.
+              /// Create these once and reuse them for bind groups and pipeline layouts.
+              #[derive(Debug)]
+              pub struct Layouts {
+                  pub bind_group0: Layout0,
+              }
+              impl Layouts {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self {
+                          bind_group0: Layout0::new(device),
+                      }
+                  }
+              }
+              #[derive(Debug, Copy, Clone)]
+              pub struct BindGroups<'a> {
+                  pub bind_group0: &'a BindGroup0,
+              }
+              impl<'a> BindGroups<'a> {
+                  pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                      self.bind_group0.set(pass);
+                  }
+              }
+          }
+          pub fn set_bind_groups<'a>(
+              pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+              bind_group0: &'a bind_groups::BindGroup0,
+          ) {
+              bind_group0.set(pass);
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn bind_groups_module_compute_and_render_stages() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<storage, read_write> params: f32;
+
+            @vertex
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return vec4(params);
+            }
+
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> {
+                return vec4(params);
+            }
+
+            @compute
+            @workgroup_size(64)
+            fn cs_main() {
+                params = 1.0;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+    assert_eq!(
+      wgpu::ShaderStages::VERTEX_FRAGMENT | wgpu::ShaderStages::COMPUTE,
+      bind_group_data[&0].bindings[0].visibility
+    );
+
+    let actual = bind_groups_module(
+      "test",
+      &module,
+      &bind_group_data,
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          pub mod bind_groups {
+              #[derive(Debug)]
+              pub struct BindGroup0(wgpu::BindGroup);
+              #[allow(non_snake_case)]
+              #[derive(Debug)]
+              pub struct BindGroupLayout0<'a> {
+                  pub params: wgpu::BufferBinding<'a>,
+              }
+              const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                  label: Some("test::BindGroup0::Layout"),
+                  entries: &[
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 0,
+                          visibility: wgpu::ShaderStages::VERTEX
+                              .union(wgpu::ShaderStages::FRAGMENT)
+                              .union(wgpu::ShaderStages::COMPUTE),
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Storage {
+                                  read_only: false,
+                              },
+                              has_dynamic_offset: false,
+                              min_binding_size: std::num::NonZeroU64::new(4),
+                          },
+                          count: None,
+                      },
+                  ],
+              };
+              /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout0(wgpu::BindGroupLayout);
+              impl Layout0 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup0::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout0 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
+              impl BindGroup0 {
+                  pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+                      device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+                  }
+                  pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout0) -> Self {
+                      Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout0,
+                      bindings: BindGroupLayout0,
+                  ) -> Self {
+                      let bind_group = device
+                          .create_bind_group(
+                              &wgpu::BindGroupDescriptor {
+                                  layout,
+                                  entries: &[
+                                      wgpu::BindGroupEntry {
+                                          binding: 0,
+                                          resource: wgpu::BindingResource::Buffer(bindings.params),
+                                      },
+                                  ],
+                                  label: Some("test::BindGroup0"),
+                              },
+                          );
+                      Self(bind_group)
+                  }
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(0, &self.0, &[]);
+                  }
+              }
+              /// The bind group layouts for this module.
+              /// Create these once and reuse them for bind groups and pipeline layouts.
+              #[derive(Debug)]
+              pub struct Layouts {
+                  pub bind_group0: Layout0,
+              }
+              impl Layouts {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self {
+                          bind_group0: Layout0::new(device),
+                      }
+                  }
+              }
+              #[derive(Debug, Copy, Clone)]
+              pub struct BindGroups<'a> {
+                  pub bind_group0: &'a BindGroup0,
+              }
+              impl<'a> BindGroups<'a> {
+                  pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                      self.bind_group0.set(pass);
+                  }
+              }
+          }
+          pub fn set_bind_groups<'a>(
+              pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+              bind_group0: &'a bind_groups::BindGroup0,
+          ) {
+              bind_group0.set(pass);
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn bind_groups_module_dynamic_offsets() {
+    let source = indoc! {r#"
+            struct Transforms { m: mat4x4<f32> };
+            struct Instance { f: f32 };
+
+            @group(0) @binding(0) var<uniform> camera: Transforms;
+            @group(1) @binding(1) var<storage, read> instances: array<Instance, 16>;
+            @group(1) @binding(0) var<uniform> transforms: Transforms;
+
+            @vertex
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return camera.m * transforms.m * vec4(instances[0].f);
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+
+    let actual = bind_groups_module(
+      "test",
+      &module,
+      &bind_group_data,
+      &WgslBindgenOption {
+        dynamic_offset_bindings: vec![
+          "test::instances".to_string(),
+          "test::transforms".to_string(),
+        ],
+        ..Default::default()
+      },
+    )
+    .unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          pub mod bind_groups {
+              #[derive(Debug)]
+              pub struct BindGroup0(wgpu::BindGroup);
+              #[allow(non_snake_case)]
+              #[derive(Debug)]
+              pub struct BindGroupLayout0<'a> {
+                  pub camera: wgpu::BufferBinding<'a>,
+              }
+              const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                  label: Some("test::BindGroup0::Layout"),
+                  entries: &[
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 0,
+                          visibility: wgpu::ShaderStages::VERTEX,
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Uniform,
+                              has_dynamic_offset: false,
+                              min_binding_size: std::num::NonZeroU64::new(64),
+                          },
+                          count: None,
+                      },
+                  ],
+              };
+              /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout0(wgpu::BindGroupLayout);
+              impl Layout0 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup0::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout0 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
+              impl BindGroup0 {
+                  pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+                      device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+                  }
+                  pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout0) -> Self {
+                      Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout0,
+                      bindings: BindGroupLayout0,
+                  ) -> Self {
+                      let bind_group = device
+                          .create_bind_group(
+                              &wgpu::BindGroupDescriptor {
+                                  layout,
+                                  entries: &[
+                                      wgpu::BindGroupEntry {
+                                          binding: 0,
+                                          resource: wgpu::BindingResource::Buffer(bindings.camera),
+                                      },
+                                  ],
+                                  label: Some("test::BindGroup0"),
+                              },
+                          );
+                      Self(bind_group)
+                  }
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(0, &self.0, &[]);
+                  }
+              }
+              #[derive(Debug)]
+              pub struct BindGroup1(wgpu::BindGroup);
+              #[allow(non_snake_case)]
+              #[derive(Debug)]
+              pub struct BindGroupLayout1<'a> {
+                  pub instances: wgpu::BufferBinding<'a>,
+                  pub transforms: wgpu::BufferBinding<'a>,
+              }
+              const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                  label: Some("test::BindGroup1::Layout"),
+                  entries: &[
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 1,
+                          visibility: wgpu::ShaderStages::VERTEX,
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Storage {
+                                  read_only: true,
+                              },
+                              has_dynamic_offset: true,
+                              min_binding_size: std::num::NonZeroU64::new(64),
+                          },
+                          count: None,
+                      },
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 0,
+                          visibility: wgpu::ShaderStages::VERTEX,
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Uniform,
+                              has_dynamic_offset: true,
+                              min_binding_size: std::num::NonZeroU64::new(64),
+                          },
+                          count: None,
+                      },
+                  ],
+              };
+              #[derive(Debug, Default, Copy, Clone)]
+              pub struct DynamicOffsets1 {
+                  pub transforms: wgpu::DynamicOffset,
+                  pub instances: wgpu::DynamicOffset,
+              }
+              impl DynamicOffsets1 {
+                  pub fn transforms_stride(limits: &wgpu::Limits) -> wgpu::DynamicOffset {
+                      wgpu::util::align_to(64, limits.min_uniform_buffer_offset_alignment)
+                  }
+                  pub fn instances_stride(limits: &wgpu::Limits) -> wgpu::DynamicOffset {
+                      wgpu::util::align_to(64, limits.min_storage_buffer_offset_alignment)
+                  }
+              }
+              /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout1(wgpu::BindGroupLayout);
+              impl Layout1 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup1::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout1 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
+              impl BindGroup1 {
+                  pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+                      device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
+                  }
+                  pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout1) -> Self {
+                      Self::from_bindings_with_layout(device, &Layout1::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout1,
+                      bindings: BindGroupLayout1,
+                  ) -> Self {
+                      let bind_group = device
+                          .create_bind_group(
+                              &wgpu::BindGroupDescriptor {
+                                  layout,
+                                  entries: &[
+                                      wgpu::BindGroupEntry {
+                                          binding: 1,
+                                          resource: wgpu::BindingResource::Buffer(bindings.instances),
+                                      },
+                                      wgpu::BindGroupEntry {
+                                          binding: 0,
+                                          resource: wgpu::BindingResource::Buffer(bindings.transforms),
+                                      },
+                                  ],
+                                  label: Some("test::BindGroup1"),
+                              },
+                          );
+                      Self(bind_group)
+                  }
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                      offsets: DynamicOffsets1,
+                  ) {
+                      pass.set_bind_group(1, &self.0, &[offsets.transforms, offsets.instances]);
+                  }
+              }
+              /// The bind group layouts for this module.
+              /// Create these once and reuse them for bind groups and pipeline layouts.
+              #[derive(Debug)]
+              pub struct Layouts {
+                  pub bind_group0: Layout0,
+                  pub bind_group1: Layout1,
+              }
+              impl Layouts {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self {
+                          bind_group0: Layout0::new(device),
+                          bind_group1: Layout1::new(device),
+                      }
+                  }
+              }
+              #[derive(Debug, Copy, Clone)]
+              pub struct BindGroups<'a> {
+                  pub bind_group0: &'a BindGroup0,
+                  pub bind_group1: &'a BindGroup1,
+                  pub dynamic_offsets1: DynamicOffsets1,
+              }
+              impl<'a> BindGroups<'a> {
+                  pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                      self.bind_group0.set(pass);
+                      self.bind_group1.set(pass, self.dynamic_offsets1);
+                  }
+              }
+          }
+          pub fn set_bind_groups<'a>(
+              pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+              bind_group0: &'a bind_groups::BindGroup0,
+              bind_group1: &'a bind_groups::BindGroup1,
+              dynamic_offsets1: bind_groups::DynamicOffsets1,
+          ) {
+              bind_group0.set(pass);
+              bind_group1.set(pass, dynamic_offsets1);
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn bind_groups_module_dynamic_offsets_runtime_sized() {
+    let source = indoc! {r#"
+            struct Transforms { m: mat4x4<f32> };
+
+            @group(0) @binding(0) var<storage, read> instances: array<f32>;
+            @group(0) @binding(1) var<uniform> transforms: Transforms;
+
+            @vertex
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return transforms.m * vec4(instances[0]);
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+
+    // Names from other modules don't enable dynamic offsets.
+    let options = WgslBindgenOption {
+      dynamic_offset_bindings: vec![
+        "other::instances".to_string(),
+        "transforms".to_string(),
+      ],
+      ..Default::default()
+    };
+    let actual = bind_groups_module("test", &module, &bind_group_data, &options).unwrap();
+    assert!(!actual.to_string().contains("DynamicOffsets0"));
+
+    let options = WgslBindgenOption {
+      dynamic_offset_bindings: vec!["test::instances".to_string()],
+      ..Default::default()
+    };
+    assert!(matches!(
+      bind_groups_module("test", &module, &bind_group_data, &options),
+      Err(CreateModuleError::RuntimeSizedDynamicOffset { name, .. }) if name == "instances"
+    ));
+  }
+
+  #[test]
+  fn bind_groups_module_typed_buffer_bindings() {
+    let source = indoc! {r#"
+            struct Camera { f: f32 };
+            struct Lights { count: u32, data: array<f32> };
+
+            @group(0) @binding(0) var<uniform> camera: Camera;
+            @group(0) @binding(1) var<uniform> scale: f32;
+            @group(0) @binding(2) var<storage, read> weights: array<f32>;
+            @group(0) @binding(3) var<storage, read> lights: Lights;
+
+            @vertex
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return vec4(camera.f * scale * weights[0] * lights.data[0]);
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+
+    let actual = bind_groups_module(
+      "test",
+      &module,
+      &bind_group_data,
+      &WgslBindgenOption {
+        typed_buffer_bindings: true,
+        serialization_strategy: WgslTypeSerializeStrategy::Bytemuck,
+        ..Default::default()
+      },
+    )
+    .unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          pub mod bind_groups {
+              #[allow(unused_imports)]
+              use super::*;
+              #[derive(Debug)]
+              pub struct BindGroup0(wgpu::BindGroup);
+              #[allow(non_snake_case)]
+              #[derive(Debug)]
+              pub struct BindGroupLayout0<'a> {
+                  pub camera: _root::typed_buffer::TypedBufferBinding<'a, Camera>,
+                  pub scale: _root::typed_buffer::TypedBufferBinding<'a, f32>,
+                  pub weights: _root::typed_buffer::TypedBufferBinding<'a, [f32]>,
+                  pub lights: wgpu::BufferBinding<'a>,
+              }
+              const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                  label: Some("test::BindGroup0::Layout"),
+                  entries: &[
+                      wgpu::BindGroupLayoutEntry {
+                          binding: 0,
+                          visibility: wgpu::ShaderStages::VERTEX,
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Uniform,
+                              has_dynamic_offset: false,
+                              min_binding_size: std::num::NonZeroU64::new(4),
                           },
                           count: None,
                       },
                       wgpu::BindGroupLayoutEntry {
-                          binding: 6,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                          ty: wgpu::BindingType::StorageTexture {
-                              access: wgpu::StorageTextureAccess::ReadWrite,
-                              format: wgpu::TextureFormat::Rgba8Uint,
-                              view_dimension: wgpu::TextureViewDimension::D2,
+                          binding: 1,
+                          visibility: wgpu::ShaderStages::VERTEX,
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Uniform,
+                              has_dynamic_offset: false,
+                              min_binding_size: std::num::NonZeroU64::new(4),
                           },
                           count: None,
                       },
                       wgpu::BindGroupLayoutEntry {
-                          binding: 7,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                          ty: wgpu::BindingType::Texture {
-                              sample_type: wgpu::TextureSampleType::Float {
-                                  filterable: true,
+                          binding: 2,
+                          visibility: wgpu::ShaderStages::VERTEX,
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Storage {
+                                  read_only: true,
                               },
-                              view_dimension: wgpu::TextureViewDimension::D2,
-                              multisampled: true,
+                              has_dynamic_offset: false,
+                              min_binding_size: std::num::NonZeroU64::new(4),
                           },
                           count: None,
                       },
                       wgpu::BindGroupLayoutEntry {
-                          binding: 8,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                          ty: wgpu::BindingType::Texture {
-                              sample_type: wgpu::TextureSampleType::Depth,
-                              view_dimension: wgpu::TextureViewDimension::D2,
-                              multisampled: true,
+                          binding: 3,
+                          visibility: wgpu::ShaderStages::VERTEX,
+                          ty: wgpu::BindingType::Buffer {
+                              ty: wgpu::BufferBindingType::Storage {
+                                  read_only: true,
+                              },
+                              has_dynamic_offset: false,
+                              min_binding_size: std::num::NonZeroU64::new(8),
                           },
                           count: None,
                       },
                   ],
               };
+              /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout0(wgpu::BindGroupLayout);
+              impl Layout0 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup0::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout0 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
               impl BindGroup0 {
                   pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
                       device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
                   }
                   pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout0) -> Self {
-                      let bind_group_layout = device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0);
+                      Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout0,
+                      bindings: BindGroupLayout0,
+                  ) -> Self {
                       let bind_group = device
                           .create_bind_group(
                               &wgpu::BindGroupDescriptor {
-                                  layout: &bind_group_layout,
+                                  layout,
                                   entries: &[
                                       wgpu::BindGroupEntry {
                                           binding: 0,
-                                          resource: wgpu::BindingResource::TextureView(
-                                              bindings.color_texture,
+                                          resource: wgpu::BindingResource::Buffer(
+                                              bindings.camera.into(),
                                           ),
                                       },
                                       wgpu::BindGroupEntry {
                                           binding: 1,
-                                          resource: wgpu::BindingResource::Sampler(
-                                              bindings.color_sampler,
+                                          resource: wgpu::BindingResource::Buffer(
+                                              bindings.scale.into(),
                                           ),
                                       },
                                       wgpu::BindGroupEntry {
                                           binding: 2,
-                                          resource: wgpu::BindingResource::TextureView(
-                                              bindings.depth_texture,
+                                          resource: wgpu::BindingResource::Buffer(
+                                              bindings.weights.into(),
                                           ),
                                       },
                                       wgpu::BindGroupEntry {
                                           binding: 3,
-                                          resource: wgpu::BindingResource::Sampler(
-                                              bindings.comparison_sampler,
-                                          ),
-                                      },
-                                      wgpu::BindGroupEntry {
-                                          binding: 4,
-                                          resource: wgpu::BindingResource::TextureView(
-                                              bindings.storage_tex_read,
-                                          ),
-                                      },
-                                      wgpu::BindGroupEntry {
-                                          binding: 5,
-                                          resource: wgpu::BindingResource::TextureView(
-                                              bindings.storage_tex_write,
-                                          ),
-                                      },
-                                      wgpu::BindGroupEntry {
-                                          binding: 6,
-                                          resource: wgpu::BindingResource::TextureView(
-                                              bindings.storage_tex_read_write,
-                                          ),
-                                      },
-                                      wgpu::BindGroupEntry {
-                                          binding: 7,
-                                          resource: wgpu::BindingResource::TextureView(
-                                              bindings.color_texture_msaa,
-                                          ),
-                                      },
-                                      wgpu::BindGroupEntry {
-                                          binding: 8,
-                                          resource: wgpu::BindingResource::TextureView(
-                                              bindings.depth_texture_msaa,
+                                          resource: wgpu::BindingResource::Buffer(
+                                              bindings.lights.into(),
                                           ),
                                       },
                                   ],
-                                  label: None,
+                                  label: Some("test::BindGroup0"),
                               },
                           );
                       Self(bind_group)
                   }
-                  pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                      render_pass.set_bind_group(0, &self.0, &[]);
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(0, &self.0, &[]);
                   }
               }
+              /// The bind group layouts for this module.
+              /// Create these once and reuse them for bind groups and pipeline layouts.
               #[derive(Debug)]
-              pub struct BindGroup1(wgpu::BindGroup);
+              pub struct Layouts {
+                  pub bind_group0: Layout0,
+              }
+              impl Layouts {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self {
+                          bind_group0: Layout0::new(device),
+                      }
+                  }
+              }
+              #[derive(Debug, Copy, Clone)]
+              pub struct BindGroups<'a> {
+                  pub bind_group0: &'a BindGroup0,
+              }
+              impl<'a> BindGroups<'a> {
+                  pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                      self.bind_group0.set(pass);
+                  }
+              }
+          }
+          pub fn set_bind_groups<'a>(
+              pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+              bind_group0: &'a bind_groups::BindGroup0,
+          ) {
+              bind_group0.set(pass);
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn bind_group_layout_typed_buffer_bindings_untyped_fallback() {
+    let source = indoc! {r#"
+            struct Lights { count: u32, data: array<f32> };
+            struct Material { f: f32 };
+
+            @group(0) @binding(0) var<storage, read> lights: Lights;
+            @group(0) @binding(1) var<storage, read> materials: binding_array<Material, 4>;
+
+            @compute
+            @workgroup_size(64)
+            fn cs_main() {
+                let x = lights.data[0] + materials[0].f;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+
+    let actual = bind_group_layout(
+      &module,
+      0,
+      &bind_group_data[&0],
+      &WgslBindgenOption {
+        typed_buffer_bindings: true,
+        serialization_strategy: WgslTypeSerializeStrategy::Bytemuck,
+        ..Default::default()
+      },
+    )
+    .unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          #[allow(non_snake_case)]
+          #[derive(Debug)]
+          pub struct BindGroupLayout0<'a> {
+              pub lights: wgpu::BufferBinding<'a>,
+              pub materials: &'a [wgpu::BufferBinding<'a>],
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn bind_groups_module_vertex() {
+    // The actual content of the structs doesn't matter.
+    // We only care about the groups and bindings.
+    let source = indoc! {r#"
+            struct Transforms { f: f32 };
+
+            @group(0) @binding(0) var<uniform> transforms: Transforms;
+
+            @vertex
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return vec4(transforms.f);
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
+
+    let actual = bind_groups_module(
+      "test",
+      &module,
+      &bind_group_data,
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          pub mod bind_groups {
+              #[derive(Debug)]
+              pub struct BindGroup0(wgpu::BindGroup);
               #[allow(non_snake_case)]
               #[derive(Debug)]
-              pub struct BindGroupLayout1<'a> {
+              pub struct BindGroupLayout0<'a> {
                   pub transforms: wgpu::BufferBinding<'a>,
-                  pub one: wgpu::BufferBinding<'a>,
               }
-              const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-                  label: None,
+              const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                  label: Some("test::BindGroup0::Layout"),
                   entries: &[
                       wgpu::BindGroupLayoutEntry {
                           binding: 0,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                          ty: wgpu::BindingType::Buffer {
-                              ty: wgpu::BufferBindingType::Uniform,
-                              has_dynamic_offset: false,
-                              min_binding_size: None,
-                          },
-                          count: None,
-                      },
-                      wgpu::BindGroupLayoutEntry {
-                          binding: 1,
-                          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                          visibility: wgpu::ShaderStages::VERTEX,
                           ty: wgpu::BindingType::Buffer {
                               ty: wgpu::BufferBindingType::Uniform,
                               has_dynamic_offset: false,
-                              min_binding_size: None,
+                              min_binding_size: std::num::NonZeroU64::new(4),
                           },
                           count: None,
                       },
                   ],
               };
-              impl BindGroup1 {
+              /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout0(wgpu::BindGroupLayout);
+              impl Layout0 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup0::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout0 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
+              impl BindGroup0 {
                   pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
-                      device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
+                      device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
                   }
-                  pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout1) -> Self {
-                      let bind_group_layout = device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1);
+                  pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout0) -> Self {
+                      Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout0,
+                      bindings: BindGroupLayout0,
+                  ) -> Self {
                       let bind_group = device
                           .create_bind_group(
                               &wgpu::BindGroupDescriptor {
-                                  layout: &bind_group_layout,
+                                  layout,
                                   entries: &[
                                       wgpu::BindGroupEntry {
                                           binding: 0,
                                           resource: wgpu::BindingResource::Buffer(bindings.transforms),
                                       },
-                                      wgpu::BindGroupEntry {
-                                          binding: 1,
-                                          resource: wgpu::BindingResource::Buffer(bindings.one),
-                                      },
                                   ],
-                                  label: None,
+                                  label: Some("test::BindGroup0"),
                               },
                           );
                       Self(bind_group)
                   }
-                  pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                      render_pass.set_bind_group(1, &self.0, &[]);
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(0, &self.0, &[]);
+                  }
+              }
+              /// The bind group layouts for this module.
+              /// Create these once and reuse them for bind groups and pipeline layouts.
+              #[derive(Debug)]
+              pub struct Layouts {
+                  pub bind_group0: Layout0,
+              }
+              impl Layouts {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self {
+                          bind_group0: Layout0::new(device),
+                      }
                   }
               }
               #[derive(Debug, Copy, Clone)]
               pub struct BindGroups<'a> {
                   pub bind_group0: &'a BindGroup0,
-                  pub bind_group1: &'a BindGroup1,
               }
               impl<'a> BindGroups<'a> {
-                  pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
+                  pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
                       self.bind_group0.set(pass);
-                      self.bind_group1.set(pass);
                   }
               }
           }
           pub fn set_bind_groups<'a>(
-              pass: &mut wgpu::RenderPass<'a>,
+              pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
               bind_group0: &'a bind_groups::BindGroup0,
-              bind_group1: &'a bind_groups::BindGroup1,
-
           ) {
               bind_group0.set(pass);
-              bind_group1.set(pass);
           }
       },
       actual
@@ -945,22 +3029,30 @@ mod tests {
   }
 
   #[test]
-  fn bind_groups_module_vertex() {
-    // The actual content of the structs doesn't matter.
-    // We only care about the groups and bindings.
+  fn bind_groups_module_acceleration_structure() {
     let source = indoc! {r#"
-            struct Transforms {};
-
-            @group(0) @binding(0) var<uniform> transforms: Transforms;
+            @group(0) @binding(0) var acc_struct: acceleration_structure;
 
-            @vertex
-            fn vs_main() {}
+            @compute
+            @workgroup_size(1)
+            fn main() {
+                var rq: ray_query;
+                let desc = RayDesc(0u, 0xFFu, 0.1, 100.0, vec3(0.0), vec3(0.0, 0.0, 1.0));
+                rayQueryInitialize(&rq, acc_struct, desc);
+                rayQueryProceed(&rq);
+            }
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let bind_group_data = get_bind_group_data(&module).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
 
-    let actual = bind_groups_module(&bind_group_data, wgpu::ShaderStages::VERTEX);
+    let actual = bind_groups_module(
+      "test",
+      &module,
+      &bind_group_data,
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -970,46 +3062,78 @@ mod tests {
               #[allow(non_snake_case)]
               #[derive(Debug)]
               pub struct BindGroupLayout0<'a> {
-                  pub transforms: wgpu::BufferBinding<'a>,
+                  pub acc_struct: _root::acceleration_structure::AccelerationStructureBinding,
               }
               const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-                  label: None,
+                  label: Some("test::BindGroup0::Layout"),
                   entries: &[
                       wgpu::BindGroupLayoutEntry {
                           binding: 0,
-                          visibility: wgpu::ShaderStages::VERTEX,
-                          ty: wgpu::BindingType::Buffer {
-                              ty: wgpu::BufferBindingType::Uniform,
-                              has_dynamic_offset: false,
-                              min_binding_size: None,
-                          },
+                          visibility: wgpu::ShaderStages::COMPUTE,
+                          ty: wgpu::BindingType::AccelerationStructure,
                           count: None,
                       },
                   ],
               };
+              /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout0(wgpu::BindGroupLayout);
+              impl Layout0 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup0::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout0 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
               impl BindGroup0 {
                   pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
                       device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
                   }
                   pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout0) -> Self {
-                      let bind_group_layout = device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0);
+                      Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout0,
+                      bindings: BindGroupLayout0,
+                  ) -> Self {
                       let bind_group = device
                           .create_bind_group(
                               &wgpu::BindGroupDescriptor {
-                                  layout: &bind_group_layout,
+                                  layout,
                                   entries: &[
                                       wgpu::BindGroupEntry {
                                           binding: 0,
-                                          resource: wgpu::BindingResource::Buffer(bindings.transforms),
+                                          resource: bindings.acc_struct.into(),
                                       },
                                   ],
-                                  label: None,
+                                  label: Some("test::BindGroup0"),
                               },
                           );
                       Self(bind_group)
                   }
-                  pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                      render_pass.set_bind_group(0, &self.0, &[]);
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(0, &self.0, &[]);
+                  }
+              }
+              /// The bind group layouts for this module.
+              /// Create these once and reuse them for bind groups and pipeline layouts.
+              #[derive(Debug)]
+              pub struct Layouts {
+                  pub bind_group0: Layout0,
+              }
+              impl Layouts {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self {
+                          bind_group0: Layout0::new(device),
+                      }
                   }
               }
               #[derive(Debug, Copy, Clone)]
@@ -1017,13 +3141,13 @@ mod tests {
                   pub bind_group0: &'a BindGroup0,
               }
               impl<'a> BindGroups<'a> {
-                  pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
+                  pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
                       self.bind_group0.set(pass);
                   }
               }
           }
           pub fn set_bind_groups<'a>(
-              pass: &mut wgpu::RenderPass<'a>,
+              pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
               bind_group0: &'a bind_groups::BindGroup0,
           ) {
               bind_group0.set(pass);
@@ -1038,18 +3162,26 @@ mod tests {
     // The actual content of the structs doesn't matter.
     // We only care about the groups and bindings.
     let source = indoc! {r#"
-            struct Transforms {};
+            struct Transforms { f: f32 };
 
             @group(0) @binding(0) var<uniform> transforms: Transforms;
 
             @fragment
-            fn fs_main() {}
+            fn fs_main() -> @location(0) vec4<f32> {
+                return vec4(transforms.f);
+            }
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let bind_group_data = get_bind_group_data(&module).unwrap();
+    let bind_group_data = get_bind_group_data(&module, &module_info(&module)).unwrap();
 
-    let actual = bind_groups_module(&bind_group_data, wgpu::ShaderStages::FRAGMENT);
+    let actual = bind_groups_module(
+      "test",
+      &module,
+      &bind_group_data,
+      &WgslBindgenOption::default(),
+    )
+    .unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -1062,7 +3194,7 @@ mod tests {
                   pub transforms: wgpu::BufferBinding<'a>,
               }
               const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-                  label: None,
+                  label: Some("test::BindGroup0::Layout"),
                   entries: &[
                       wgpu::BindGroupLayoutEntry {
                           binding: 0,
@@ -1070,35 +3202,71 @@ mod tests {
                           ty: wgpu::BindingType::Buffer {
                               ty: wgpu::BufferBindingType::Uniform,
                               has_dynamic_offset: false,
-                              min_binding_size: None,
+                              min_binding_size: std::num::NonZeroU64::new(4),
                           },
                           count: None,
                       },
                   ],
               };
+              /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+              #[derive(Debug)]
+              pub struct Layout0(wgpu::BindGroupLayout);
+              impl Layout0 {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self(BindGroup0::get_bind_group_layout(device))
+                  }
+              }
+              impl std::ops::Deref for Layout0 {
+                  type Target = wgpu::BindGroupLayout;
+                  fn deref(&self) -> &Self::Target {
+                      &self.0
+                  }
+              }
               impl BindGroup0 {
                   pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
                       device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
                   }
                   pub fn from_bindings(device: &wgpu::Device, bindings: BindGroupLayout0) -> Self {
-                      let bind_group_layout = device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0);
+                      Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+                  }
+                  pub fn from_bindings_with_layout(
+                      device: &wgpu::Device,
+                      layout: &Layout0,
+                      bindings: BindGroupLayout0,
+                  ) -> Self {
                       let bind_group = device
                           .create_bind_group(
                               &wgpu::BindGroupDescriptor {
-                                  layout: &bind_group_layout,
+                                  layout,
                                   entries: &[
                                       wgpu::BindGroupEntry {
                                           binding: 0,
                                           resource: wgpu::BindingResource::Buffer(bindings.transforms),
                                       },
                                   ],
-                                  label: None,
+                                  label: Some("test::BindGroup0"),
                               },
                           );
                       Self(bind_group)
                   }
-                  pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                      render_pass.set_bind_group(0, &self.0, &[]);
+                  pub fn set<'a>(
+                      &'a self,
+                      pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                  ) {
+                      pass.set_bind_group(0, &self.0, &[]);
+                  }
+              }
+              /// The bind group layouts for this module.
+              /// Create these once and reuse them for bind groups and pipeline layouts.
+              #[derive(Debug)]
+              pub struct Layouts {
+                  pub bind_group0: Layout0,
+              }
+              impl Layouts {
+                  pub fn new(device: &wgpu::Device) -> Self {
+                      Self {
+                          bind_group0: Layout0::new(device),
+                      }
                   }
               }
               #[derive(Debug, Copy, Clone)]
@@ -1106,13 +3274,13 @@ mod tests {
                   pub bind_group0: &'a BindGroup0,
               }
               impl<'a> BindGroups<'a> {
-                  pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
+                  pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
                       self.bind_group0.set(pass);
                   }
               }
           }
           pub fn set_bind_groups<'a>(
-              pass: &mut wgpu::RenderPass<'a>,
+              pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
               bind_group0: &'a bind_groups::BindGroup0,
           ) {
               bind_group0.set(pass);
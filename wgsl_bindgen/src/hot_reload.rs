@@ -0,0 +1,173 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use crate::bevy_util::SourceWithFullDependenciesResult;
+use crate::SourceFilePath;
+
+/// The shared types for reloading shader modules from disk in debug builds.
+pub fn hot_reload_module() -> TokenStream {
+  quote! {
+      /// An error from reloading a shader module from disk.
+      #[cfg(debug_assertions)]
+      #[derive(Debug)]
+      pub enum ShaderReloadError {
+          /// A shader source file could not be read.
+          Io {
+              path: &'static str,
+              error: std::io::Error,
+          },
+          /// The shader sources could not be composed.
+          /// The message is the formatted diagnostic from naga_oil.
+          Compose(String),
+          /// The composed module doesn't match the generated bindings.
+          Layout(_root::module_layout::LayoutError),
+      }
+
+      #[cfg(debug_assertions)]
+      impl std::fmt::Display for ShaderReloadError {
+          fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+              match self {
+                  Self::Io { path, error } => write!(f, "failed to read {path}: {error}"),
+                  Self::Compose(message) => f.write_str(message),
+                  Self::Layout(error) => write!(f, "bindings need to be regenerated: {error}"),
+              }
+          }
+      }
+
+      #[cfg(debug_assertions)]
+      impl std::error::Error for ShaderReloadError {}
+
+      #[cfg(debug_assertions)]
+      pub fn read_shader_source(path: &'static str) -> Result<String, ShaderReloadError> {
+          std::fs::read_to_string(path).map_err(|error| ShaderReloadError::Io { path, error })
+      }
+
+      /// Checks the modified times of shader source files for changes.
+      #[cfg(debug_assertions)]
+      #[derive(Debug)]
+      pub struct ShaderWatcher {
+          paths: &'static [&'static str],
+          modified: Vec<Option<std::time::SystemTime>>,
+      }
+
+      #[cfg(debug_assertions)]
+      impl ShaderWatcher {
+          pub fn new(paths: &'static [&'static str]) -> Self {
+              Self {
+                  paths,
+                  modified: Self::modified_times(paths),
+              }
+          }
+
+          /// Returns `true` if any of the files changed since the last call.
+          pub fn changed(&mut self) -> bool {
+              let modified = Self::modified_times(self.paths);
+              let changed = modified != self.modified;
+              self.modified = modified;
+              changed
+          }
+
+          fn modified_times(paths: &[&str]) -> Vec<Option<std::time::SystemTime>> {
+              paths
+                  .iter()
+                  .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+                  .collect()
+          }
+      }
+  }
+}
+
+/// The path of the source file when the generated code is compiled.
+/// Relative paths are relative to the crate running the build script.
+fn source_path(file: &SourceFilePath) -> TokenStream {
+  let path = file.as_path().to_str().unwrap();
+  if file.as_path().is_absolute() {
+    quote!(#path)
+  } else {
+    quote!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #path))
+  }
+}
+
+/// The items for reloading the shader module of an entry from disk in debug builds.
+pub fn reload_shader_module_items(
+  source: &SourceWithFullDependenciesResult,
+  label: &str,
+  shader_defs_param: &TokenStream,
+  shader_defs_field: &TokenStream,
+) -> TokenStream {
+  let entry_path = source_path(&source.source_file.file_path);
+  let dependency_paths: Vec<_> = source
+    .full_dependencies
+    .iter()
+    .map(|dep| source_path(&dep.file_path))
+    .collect();
+
+  let add_shader_modules = source.full_dependencies.iter().enumerate().map(|(i, dep)| {
+    let index = Literal::usize_unsuffixed(i + 1);
+    let as_name_assignment = match &dep.module_name {
+      Some(as_name) => {
+        let as_name = as_name.to_string();
+        quote!(as_name: Some(#as_name.into()),)
+      }
+      None => quote!(),
+    };
+
+    quote! {
+        let path = SHADER_PATHS[#index];
+        let source = _root::hot_reload::read_shader_source(path)?;
+        if let Err(e) = composer.add_composable_module(
+            naga_oil::compose::ComposableModuleDescriptor {
+                source: &source,
+                file_path: path,
+                language: naga_oil::compose::ShaderLanguage::Wgsl,
+                #as_name_assignment
+                ..Default::default()
+            }
+        ) {
+            return Err(_root::hot_reload::ShaderReloadError::Compose(e.emit_to_string(&composer)));
+        }
+    }
+  });
+
+  quote! {
+      /// The paths of the entry and its dependencies for reloading the shader module.
+      #[cfg(debug_assertions)]
+      pub const SHADER_PATHS: &[&str] = &[#entry_path, #(#dependency_paths),*];
+
+      /// Creates a watcher for changes to the files in [SHADER_PATHS].
+      #[cfg(debug_assertions)]
+      pub fn shader_watcher() -> _root::hot_reload::ShaderWatcher {
+          _root::hot_reload::ShaderWatcher::new(SHADER_PATHS)
+      }
+
+      /// Creates the shader module from the current sources on disk instead of the
+      /// sources included at compile time.
+      /// The module is checked with [verify_module_layout] before creating the shader module.
+      #[cfg(debug_assertions)]
+      pub fn reload_shader_module(
+          device: &wgpu::Device #shader_defs_param
+      ) -> Result<wgpu::ShaderModule, _root::hot_reload::ShaderReloadError> {
+          let mut composer = naga_oil::compose::Composer::default()
+              .with_capabilities(wgpu::naga::valid::Capabilities::all());
+          #(#add_shader_modules)*
+
+          let path = SHADER_PATHS[0];
+          let source = _root::hot_reload::read_shader_source(path)?;
+          let module = composer
+              .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                  source: &source,
+                  file_path: path,
+                  #shader_defs_field
+                  ..Default::default()
+              })
+              .map_err(|e| _root::hot_reload::ShaderReloadError::Compose(e.emit_to_string(&composer)))?;
+          verify_module_layout(&module).map_err(_root::hot_reload::ShaderReloadError::Layout)?;
+
+          let source = naga_module_to_string(&module);
+          Ok(device.create_shader_module(wgpu::ShaderModuleDescriptor {
+              label: Some(#label),
+              source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(source))
+          }))
+      }
+  }
+}
@@ -38,26 +38,42 @@
 #[allow(dead_code, unused)]
 extern crate wgpu_types as wgpu;
 
-use bevy_util::SourceWithFullDependenciesResult;
-use bindgroup::{bind_groups_module, get_bind_group_data};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use bevy_util::source_file::SourceFile;
+use bevy_util::{demangle, SourceWithFullDependenciesResult};
+use bindgroup::{
+  acceleration_structure_module, bind_group_items, bind_groups_module,
+  get_bind_group_data, share_bind_groups, typed_buffer_module, EntryGroups, GroupData,
+};
 use case::CaseExt;
 use derive_more::IsVariant;
+use hot_reload::{hot_reload_module, reload_shader_module_items};
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use module_layout::{module_layout_module, verify_module_layout_items};
 use naga::ShaderStage;
-use naga_util::module_to_source;
-use proc_macro2::{Literal, Span, TokenStream};
+use naga_util::{module_to_naga_ir, module_to_source, module_to_spirv, NAGA_IR_VERSION};
+use proc_macro2::{Group, Literal, Span, TokenStream, TokenTree};
+use push_constants::{get_push_constant_data, push_constants_items};
 use quote::quote;
 use quote_gen::{
   add_prelude_types_assertions, create_shader_raw_string_literal, RustModBuilder,
-  MOD_REFERENCE_ROOT,
+  RustModBuilderError, RustSourceItem, MOD_REFERENCE_ROOT,
 };
-use syn::{Ident, Index};
+use shader_defs::{get_shader_defs, shader_def_value, shader_defs_items};
+use syn::{Ident, Index, LitByteStr, LitInt};
 use thiserror::Error;
 
 pub mod bevy_util;
 mod bindgroup;
 mod consts;
+mod hot_reload;
+mod module_layout;
 mod naga_util;
+mod push_constants;
 mod quote_gen;
+mod shader_defs;
 mod structs;
 mod types;
 mod wgsl;
@@ -80,23 +96,231 @@ pub enum WgslTypeSerializeStrategy {
 }
 
 /// Errors while generating Rust source for a WGSl shader module.
-#[derive(Debug, PartialEq, Eq, Error)]
+///
+/// The `name` is the demangled WGSL item like `Uniforms` or `Uniforms.color` for struct members.
+/// The source and span point to the item's declaration in the WGSL file if it could be found.
+#[derive(Debug, Error, Diagnostic)]
 pub enum CreateModuleError {
-  /// Bind group sets must be consecutive and start from 0.
-  /// See `bind_group_layouts` for
-  /// [PipelineLayoutDescriptor](https://docs.rs/wgpu/latest/wgpu/struct.PipelineLayoutDescriptor.html#).
-  #[error("bind groups are non-consecutive or do not start from 0")]
-  NonConsecutiveBindGroups,
-
   /// Each binding resource must be associated with exactly one binding index.
   #[error("duplicate binding found with index `{binding}`")]
-  DuplicateBinding { binding: u32 },
+  DuplicateBinding {
+    binding: u32,
+    name: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("duplicate binding")]
+    span: Option<SourceSpan>,
+  },
+
+  /// Only a single push constant variable is supported for each shader module.
+  #[error("multiple push constant variables found")]
+  MultiplePushConstants {
+    name: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("additional push constant variable")]
+    span: Option<SourceSpan>,
+  },
+
+  /// The binding's type has no corresponding wgpu binding type.
+  #[error("unsupported type `{ty}` for binding `{name}`")]
+  UnsupportedBindingType {
+    name: String,
+    ty: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("unsupported binding type")]
+    span: Option<SourceSpan>,
+  },
+
+  /// Binding arrays must have a fixed size to generate the layout entry count.
+  #[error("runtime-sized binding array `{name}` is not supported")]
+  RuntimeSizedBindingArray {
+    name: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("runtime-sized binding array")]
+    span: Option<SourceSpan>,
+  },
+
+  /// The type of a vertex input field has no corresponding wgpu vertex format.
+  #[error("unsupported vertex attribute type `{ty}` for `{name}`")]
+  UnsupportedVertexFormat {
+    name: String,
+    ty: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("unsupported vertex attribute type")]
+    span: Option<SourceSpan>,
+  },
+
+  /// The WGSL type has no corresponding Rust type.
+  #[error("unsupported type `{ty}` for `{name}`")]
+  UnsupportedType {
+    name: String,
+    ty: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("unsupported type")]
+    span: Option<SourceSpan>,
+  },
+
+  /// Only the last member of a struct can be a runtime-sized array.
+  #[error("runtime-sized array `{name}` is not the last member of its struct")]
+  RuntimeSizedArrayNotLast {
+    name: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("runtime-sized array")]
+    span: Option<SourceSpan>,
+  },
+
+  /// The stride for dynamic offsets requires a fixed size buffer binding.
+  #[error("runtime-sized binding `{name}` can't use dynamic offsets")]
+  RuntimeSizedDynamicOffset {
+    name: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("runtime-sized binding")]
+    span: Option<SourceSpan>,
+  },
+
+  /// naga couldn't compute the size and alignment of a type.
+  #[error("failed to compute the layout of `{name}`: {message}")]
+  TypeLayout {
+    name: String,
+    message: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("type with invalid layout")]
+    span: Option<SourceSpan>,
+  },
+
+  /// Imported module names must be valid Rust module paths.
+  #[error("`{name}` is not a valid Rust module path")]
+  InvalidModulePath {
+    name: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("invalid module path")]
+    span: Option<SourceSpan>,
+  },
+
+  /// The shader source for the selected output type couldn't be written.
+  #[error("failed to write the shader output for `{name}`: {message}")]
+  ShaderOutput {
+    name: String,
+    message: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("shader entry")]
+    span: Option<SourceSpan>,
+  },
+
+  /// Items generated for the same Rust module must be identical.
+  /// This can happen when entries generate different code for the same imported item.
+  #[error("conflicting definitions generated for `{name}`")]
+  ConflictingItems {
+    name: String,
+    #[source_code]
+    src: Option<Arc<NamedSource<String>>>,
+    #[label("item with conflicting definitions")]
+    span: Option<SourceSpan>,
+  },
+}
+
+impl CreateModuleError {
+  fn location_mut(
+    &mut self,
+  ) -> (&str, &mut Option<Arc<NamedSource<String>>>, &mut Option<SourceSpan>) {
+    match self {
+      Self::DuplicateBinding {
+        name, src, span, ..
+      }
+      | Self::MultiplePushConstants { name, src, span }
+      | Self::UnsupportedBindingType {
+        name, src, span, ..
+      }
+      | Self::RuntimeSizedBindingArray { name, src, span }
+      | Self::UnsupportedVertexFormat {
+        name, src, span, ..
+      }
+      | Self::UnsupportedType {
+        name, src, span, ..
+      }
+      | Self::RuntimeSizedArrayNotLast { name, src, span }
+      | Self::RuntimeSizedDynamicOffset { name, src, span }
+      | Self::TypeLayout {
+        name, src, span, ..
+      }
+      | Self::InvalidModulePath { name, src, span }
+      | Self::ShaderOutput {
+        name, src, span, ..
+      }
+      | Self::ConflictingItems { name, src, span } => (name, src, span),
+    }
+  }
+
+  /// Point the error at the declaration of the item in the entry or its imports.
+  fn with_source(mut self, entry: &SourceWithFullDependenciesResult) -> Self {
+    let (name, src, span) = self.location_mut();
+
+    let (mod_path, item) = match name.rsplit_once("::") {
+      Some((mod_path, item)) => (Some(mod_path), item),
+      None => (None, name),
+    };
+    let (item, member) = match item.split_once('.') {
+      Some((item, member)) => (item, Some(member)),
+      None => (item, None),
+    };
+
+    let files = || {
+      std::iter::once(entry.source_file).chain(entry.full_dependencies.iter().copied())
+    };
+    let is_declaring_module = |file: &SourceFile| {
+      let file_mod_path = file
+        .module_name
+        .as_ref()
+        .map(|name| bevy_util::make_valid_rust_import(name));
+      file_mod_path.as_deref() == mod_path
+    };
+
+    // Imported items are declared in the dependency with the matching module name.
+    // Fall back to any file for names without a module path like generated item names.
+    let declaration = files()
+      .filter(|file| is_declaring_module(file))
+      .chain(files())
+      .find_map(|file| Some((file, file.find_declaration(item, member)?)));
+
+    if let Some((file, declaration)) = declaration {
+      let source = NamedSource::new(file.file_path.to_string(), file.content.clone());
+      *src = Some(Arc::new(source));
+      *span = Some(declaration);
+    }
+    self
+  }
+}
+
+impl From<RustModBuilderError> for CreateModuleError {
+  fn from(error: RustModBuilderError) -> Self {
+    match error {
+      RustModBuilderError::DuplicateContentError { id, .. } => Self::ConflictingItems {
+        name: id,
+        src: None,
+        span: None,
+      },
+    }
+  }
 }
 
 pub(crate) struct WgslEntryResult<'a> {
   mod_name: String,
   naga_module: naga::Module,
   source_including_deps: SourceWithFullDependenciesResult<'a>,
+  /// The permutation if this is a variant of the entry composed with additional shader defs.
+  permutation: Option<WgslShaderPermutation>,
+  /// The validation info including the globals used by each entry point.
+  module_info: naga::valid::ModuleInfo,
 }
 
 fn create_rust_bindings(
@@ -105,58 +329,510 @@ fn create_rust_bindings(
 ) -> Result<String, CreateModuleError> {
   let mut mod_builder = RustModBuilder::new(true);
   mod_builder.add(MOD_REFERENCE_ROOT, add_prelude_types_assertions(options));
+  if options.typed_buffer_bindings {
+    mod_builder.add("typed_buffer", typed_buffer_module());
+  }
 
-  for entry in entries.iter() {
-    let WgslEntryResult {
-      mod_name,
-      naga_module,
-      ..
-    } = entry;
-    let bind_group_data = get_bind_group_data(naga_module)?;
-    let shader_stages = wgsl::shader_stages(naga_module);
-
-    // Write all the structs, including uniforms and entry function inputs.
-    mod_builder
-      .add_items(mod_name, structs::structs_items(naga_module, options))
-      .unwrap();
-
-    mod_builder
-      .add_items(mod_name, consts::consts_items(naga_module))
-      .unwrap();
-
-    mod_builder.add(mod_name, bind_groups_module(&bind_group_data, shader_stages));
-    mod_builder.add(mod_name, vertex_struct_methods(naga_module));
-
-    mod_builder.add(mod_name, compute_module(naga_module));
-    mod_builder.add(mod_name, entry_point_constants(naga_module));
-    mod_builder.add(mod_name, vertex_states(naga_module));
-
-    let bind_group_layouts: Vec<_> = bind_group_data
-      .keys()
-      .map(|group_no| {
-        let group = indexed_name_to_ident("BindGroup", *group_no);
-        quote!(bind_groups::#group::get_bind_group_layout(device))
+  let mut entry_groups = entries
+    .iter()
+    .map(|entry| {
+      Ok(EntryGroups {
+        module: &entry.naga_module,
+        bind_group_data: get_bind_group_data(&entry.naga_module, &entry.module_info)
+          .map_err(|e| e.with_source(&entry.source_including_deps))?,
+      })
+    })
+    .collect::<Result<Vec<_>, CreateModuleError>>()?;
+
+  let uses_pass_ext = entry_groups.iter().any(|e| !e.bind_group_data.is_empty())
+    || entries.iter().any(|e| {
+      !wgsl::get_vertex_input_structs(&e.naga_module).is_empty()
+        || wgsl::has_push_constants(&e.naga_module)
+    });
+  if uses_pass_ext {
+    mod_builder.add("pass_ext", pass_ext_module());
+  }
+  let uses_acceleration_structures = entry_groups
+    .iter()
+    .flat_map(|e| e.bind_group_data.values())
+    .flat_map(|group| &group.bindings)
+    .any(|b| b.binding_type.inner == naga::TypeInner::AccelerationStructure);
+  if uses_acceleration_structures {
+    mod_builder.add("acceleration_structure", acceleration_structure_module());
+  }
+  if options.shader_source_output_type == WgslShaderSourceOutputType::Composer {
+    mod_builder.add("module_layout", module_layout_module());
+  }
+  if uses_hot_reload(options) {
+    mod_builder.add("hot_reload", hot_reload_module());
+  }
+
+  // Groups from imported modules are generated once to share types between entries.
+  for shared in share_bind_groups(&mut entry_groups) {
+    let items = bind_group_items(
+      &shared.mod_path,
+      shared.module,
+      shared.group_no,
+      &shared.group,
+      options,
+    )
+    .map_err(|e| e.with_source(&entries[shared.entry].source_including_deps))?;
+    mod_builder.add(&format!("{}::bind_groups", shared.mod_path), items);
+  }
+
+  for (entry, groups) in entries.iter().zip(&entry_groups) {
+    // Permutations are composed right after their entry module.
+    let base = entry.permutation.as_ref().and_then(|_| {
+      entries.iter().find(|base| {
+        base.permutation.is_none()
+          && base.source_including_deps.source_file.file_path
+            == entry.source_including_deps.source_file.file_path
+      })
+    });
+    add_entry_items(&mut mod_builder, entry, base, &groups.bind_group_data, options)
+      .map_err(|e| e.with_source(&entry.source_including_deps))?;
+  }
+
+  for base in entries.iter().filter(|entry| entry.permutation.is_none()) {
+    let permutations: Vec<_> = entries
+      .iter()
+      .filter(|entry| {
+        entry.permutation.is_some()
+          && entry.source_including_deps.source_file.file_path
+            == base.source_including_deps.source_file.file_path
       })
       .collect();
+    if !permutations.is_empty() {
+      mod_builder.add(&base.mod_name, permutation_enum(base, &permutations, options));
+    }
+  }
+
+  let output = mod_builder.generate();
+  Ok(pretty_print(&output))
+}
+
+fn add_entry_items(
+  mod_builder: &mut RustModBuilder,
+  entry: &WgslEntryResult,
+  base: Option<&WgslEntryResult>,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+  options: &WgslBindgenOption,
+) -> Result<(), CreateModuleError> {
+  let WgslEntryResult {
+    mod_name,
+    naga_module,
+    module_info,
+    ..
+  } = entry;
+  let push_constant_data = get_push_constant_data(naga_module, module_info)?;
+
+  // Write all the structs, including uniforms and entry function inputs.
+  let mut items = structs::structs_items(naga_module, options)?;
+  items.extend(consts::consts_items(naga_module));
+
+  // Permutations reexport the items that are the same in the entry module.
+  let mut shared_items = HashSet::new();
+  let mut changed_imports = HashSet::new();
+  if let Some(base) = base {
+    let mut base_items = structs::structs_items(&base.naga_module, options)?;
+    base_items.extend(consts::consts_items(&base.naga_module));
+    changed_imports = move_changed_imported_items(mod_name, &mut items, &base_items);
+    shared_items = share_permutation_items(&mut items, &base_items);
+  }
+  mod_builder.add_items(mod_name, items)?;
+
+  let mut add = |content: TokenStream| {
+    mod_builder.add(mod_name, replace_imported_paths(content, mod_name, &changed_imports))
+  };
+  add(bind_groups_module(mod_name, naga_module, bind_group_data, options)?);
+  add(vertex_struct_methods(naga_module, &shared_items)?);
+
+  let shader_defs_type = shader_defs_type(entry, options);
+  add(compute_module(mod_name, naga_module, shader_defs_type.as_ref(), options));
+  add(entry_point_constants(naga_module));
+  add(vertex_states(naga_module));
+  add(required_features(naga_module));
+
+  if let Some(data) = &push_constant_data {
+    add(push_constants_items(naga_module, data, options)?);
+  }
+
+  add(pipeline_layout(
+    mod_name,
+    bind_group_data,
+    push_constant_data.is_some(),
+    options,
+  ));
+  add(shader_module(entry, options)?);
+  Ok(())
+}
+
+/// Moves the imported items that are different in the permutation into the
+/// permutation module like `pbr::skinned::lights`, since the imported module is
+/// shared with the entry and other permutations.
+/// Returns the module paths and names of the moved items.
+fn move_changed_imported_items(
+  mod_name: &str,
+  items: &mut [RustSourceItem],
+  base_items: &[RustSourceItem],
+) -> HashSet<(String, String)> {
+  // Items that refer to a changed item also change, so repeat until nothing else changes.
+  let mut changed = HashSet::new();
+  loop {
+    let mut next = changed.clone();
+    for item in items.iter() {
+      let Some(mod_path) = &item.mod_path else {
+        continue;
+      };
+      let key = (mod_path.clone(), item.name.clone());
+      if next.contains(&key) {
+        continue;
+      }
+      let content =
+        replace_imported_paths(item.item.clone(), mod_name, &changed).to_string();
+      let unchanged = base_items.iter().any(|base| {
+        base.mod_path.as_ref() == Some(mod_path)
+          && base.name == item.name
+          && base.item.to_string() == content
+      });
+      if !unchanged {
+        next.insert(key);
+      }
+    }
+    if next.len() == changed.len() {
+      break;
+    }
+    changed = next;
+  }
+
+  for item in items.iter_mut() {
+    item.item = replace_imported_paths(item.item.clone(), mod_name, &changed);
+    if let Some(mod_path) = &item.mod_path {
+      if changed.contains(&(mod_path.clone(), item.name.clone())) {
+        item.mod_path = Some(format!("{mod_name}::{mod_path}"));
+      }
+    }
+  }
+  changed
+}
+
+/// Replaces paths like `_root::lights::Light` to the changed imported items with
+/// the paths of their copies in the permutation module.
+fn replace_imported_paths(
+  tokens: TokenStream,
+  mod_name: &str,
+  changed: &HashSet<(String, String)>,
+) -> TokenStream {
+  if changed.is_empty() {
+    return tokens;
+  }
+
+  let mod_segments: Vec<_> = mod_name
+    .split("::")
+    .map(|name| Ident::new(name, Span::call_site()))
+    .collect();
+  let tokens: Vec<_> = tokens.into_iter().collect();
+
+  let mut output = TokenStream::new();
+  for (i, token) in tokens.iter().enumerate() {
+    match token {
+      TokenTree::Group(group) => {
+        let stream = replace_imported_paths(group.stream(), mod_name, changed);
+        let mut replaced = Group::new(group.delimiter(), stream);
+        replaced.set_span(group.span());
+        output.extend([TokenTree::Group(replaced)]);
+      }
+      TokenTree::Ident(ident) if ident == MOD_REFERENCE_ROOT => {
+        // The segments of the path after `_root`, separated by `::`.
+        let segments: Vec<_> = tokens[i + 1..]
+          .chunks_exact(3)
+          .map_while(|chunk| match chunk {
+            [TokenTree::Punct(a), TokenTree::Punct(b), TokenTree::Ident(segment)]
+              if a.as_char() == ':' && b.as_char() == ':' =>
+            {
+              Some(segment.to_string())
+            }
+            _ => None,
+          })
+          .collect();
+        let is_changed = (1..segments.len()).any(|len| {
+          changed.contains(&(segments[..len].join("::"), segments[len].clone()))
+        });
+
+        output.extend([token.clone()]);
+        if is_changed {
+          output.extend(quote!(#(::#mod_segments)*));
+        }
+      }
+      _ => output.extend([token.clone()]),
+    }
+  }
+  output
+}
+
+/// Replaces the items defined by the entry that are unchanged in the permutation
+/// with reexports from the entry module and returns their names.
+fn share_permutation_items(
+  items: &mut [RustSourceItem],
+  base_items: &[RustSourceItem],
+) -> HashSet<String> {
+  let mut shared_items = HashSet::new();
+  for item in items.iter_mut().filter(|item| item.mod_path.is_none()) {
+    let unchanged = base_items.iter().any(|base| {
+      base.mod_path.is_none()
+        && base.name == item.name
+        && base.item.to_string() == item.item.to_string()
+    });
+    if unchanged {
+      let name = Ident::new(&item.name, Span::call_site());
+      item.item = quote!(pub use super::#name;);
+      shared_items.insert(item.name.clone());
+    }
+  }
+  shared_items
+}
+
+/// The `Permutation` enum for selecting between the entry module and its permutations.
+fn permutation_enum(
+  base: &WgslEntryResult,
+  permutations: &[&WgslEntryResult],
+  options: &WgslBindgenOption,
+) -> TokenStream {
+  let (variants, mod_names): (Vec<_>, Vec<_>) = permutations
+    .iter()
+    .filter_map(|entry| entry.permutation.as_ref())
+    .map(|permutation| {
+      (
+        Ident::new(&permutation.name.to_camel(), Span::call_site()),
+        Ident::new(&permutation.name, Span::call_site()),
+      )
+    })
+    .unzip();
+  let count = Index::from(variants.len() + 1);
+
+  let (shader_defs_param, shader_defs_arg) = match shader_defs_type(base, options) {
+    Some(ty) => (quote!(, shader_defs: &#ty), quote!(, shader_defs)),
+    None => (quote!(), quote!()),
+  };
 
-    let create_pipeline_layout = quote! {
+  quote! {
+      #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+      pub enum Permutation {
+          Default,
+          #(#variants),*
+      }
+
+      impl Permutation {
+          pub const ALL: [Self; #count] = [Self::Default, #(Self::#variants),*];
+
+          pub fn create_shader_module(
+              self,
+              device: &wgpu::Device #shader_defs_param
+          ) -> wgpu::ShaderModule {
+              match self {
+                  Self::Default => create_shader_module(device #shader_defs_arg),
+                  #(Self::#variants => #mod_names::create_shader_module(device #shader_defs_arg)),*
+              }
+          }
+
+          pub fn create_pipeline_layout(self, device: &wgpu::Device) -> wgpu::PipelineLayout {
+              match self {
+                  Self::Default => create_pipeline_layout(device),
+                  #(Self::#variants => #mod_names::create_pipeline_layout(device)),*
+              }
+          }
+      }
+  }
+}
+
+fn pipeline_layout(
+  mod_name: &str,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+  has_push_constants: bool,
+  options: &WgslBindgenOption,
+) -> TokenStream {
+  let pipeline_layout_label = object_label(mod_name, "PipelineLayout", options);
+
+  let push_constant_ranges = if has_push_constants {
+    quote!(&[PUSH_CONSTANT_RANGE])
+  } else {
+    quote!(&[])
+  };
+
+  // Modules without bind groups don't have any layouts to cache.
+  if bind_group_data.is_empty() {
+    return quote! {
         pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
             device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: None,
-                bind_group_layouts: &[
-                    #(&#bind_group_layouts),*
-                ],
-                push_constant_ranges: &[],
+                label: Some(#pipeline_layout_label),
+                bind_group_layouts: &[],
+                push_constant_ranges: #push_constant_ranges,
             })
         }
     };
+  }
 
-    mod_builder.add(mod_name, create_pipeline_layout);
-    mod_builder.add(mod_name, shader_module(entry, options));
+  // wgpu expects bind groups to be consecutive starting from 0.
+  // Fill any unused group indices with an empty layout.
+  let last_group = bind_group_data.keys().last().copied().unwrap_or_default();
+  let bind_group_layouts: Vec<_> = (0..=last_group)
+    .map(|group_no| {
+      if bind_group_data.contains_key(&group_no) {
+        let field = indexed_name_to_ident("bind_group", group_no);
+        quote!(&layouts.#field)
+      } else {
+        quote!(&empty_layout)
+      }
+    })
+    .collect();
+
+  let empty_layout = if bind_group_data.len() as u32 <= last_group {
+    let empty_layout_label = object_label(mod_name, "EmptyBindGroupLayout", options);
+    quote! {
+        let empty_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(#empty_layout_label),
+            entries: &[],
+        });
+    }
+  } else {
+    quote!()
+  };
+
+  quote! {
+      pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+          create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+      }
+
+      pub fn create_pipeline_layout_with_layouts(
+          device: &wgpu::Device,
+          layouts: &bind_groups::Layouts,
+      ) -> wgpu::PipelineLayout {
+          #empty_layout
+          device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+              label: Some(#pipeline_layout_label),
+              bind_group_layouts: &[
+                  #(#bind_group_layouts),*
+              ],
+              push_constant_ranges: #push_constant_ranges,
+          })
+      }
   }
+}
 
-  let output = mod_builder.generate();
-  Ok(pretty_print(&output))
+/// Traits for setting generated bind groups, push constants, and vertex buffers
+/// on any pass or encoder.
+fn pass_ext_module() -> TokenStream {
+  quote! {
+      /// A render pass, compute pass, or render bundle encoder for setting bind groups.
+      pub trait SetBindGroup<'a> {
+          fn set_bind_group(
+              &mut self,
+              index: u32,
+              bind_group: &'a wgpu::BindGroup,
+              offsets: &[wgpu::DynamicOffset],
+          );
+      }
+
+      impl<'a> SetBindGroup<'a> for wgpu::RenderPass<'a> {
+          fn set_bind_group(
+              &mut self,
+              index: u32,
+              bind_group: &'a wgpu::BindGroup,
+              offsets: &[wgpu::DynamicOffset],
+          ) {
+              wgpu::RenderPass::set_bind_group(self, index, bind_group, offsets);
+          }
+      }
+
+      impl<'a> SetBindGroup<'a> for wgpu::ComputePass<'a> {
+          fn set_bind_group(
+              &mut self,
+              index: u32,
+              bind_group: &'a wgpu::BindGroup,
+              offsets: &[wgpu::DynamicOffset],
+          ) {
+              wgpu::ComputePass::set_bind_group(self, index, bind_group, offsets);
+          }
+      }
+
+      impl<'a> SetBindGroup<'a> for wgpu::RenderBundleEncoder<'a> {
+          fn set_bind_group(
+              &mut self,
+              index: u32,
+              bind_group: &'a wgpu::BindGroup,
+              offsets: &[wgpu::DynamicOffset],
+          ) {
+              wgpu::RenderBundleEncoder::set_bind_group(self, index, bind_group, offsets);
+          }
+      }
+
+      /// A render pass, compute pass, or render bundle encoder for setting push constants.
+      /// Compute passes ignore the shader stages.
+      pub trait SetPushConstants {
+          fn set_push_constants(&mut self, stages: wgpu::ShaderStages, offset: u32, data: &[u8]);
+      }
+
+      impl<'a> SetPushConstants for wgpu::RenderPass<'a> {
+          fn set_push_constants(&mut self, stages: wgpu::ShaderStages, offset: u32, data: &[u8]) {
+              wgpu::RenderPass::set_push_constants(self, stages, offset, data);
+          }
+      }
+
+      impl<'a> SetPushConstants for wgpu::ComputePass<'a> {
+          fn set_push_constants(&mut self, _stages: wgpu::ShaderStages, offset: u32, data: &[u8]) {
+              wgpu::ComputePass::set_push_constants(self, offset, data);
+          }
+      }
+
+      impl<'a> SetPushConstants for wgpu::RenderBundleEncoder<'a> {
+          fn set_push_constants(&mut self, stages: wgpu::ShaderStages, offset: u32, data: &[u8]) {
+              wgpu::RenderBundleEncoder::set_push_constants(self, stages, offset, data);
+          }
+      }
+
+      /// A render pass or render bundle encoder for setting vertex buffers.
+      pub trait SetVertexBuffer<'a> {
+          fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>);
+      }
+
+      impl<'a> SetVertexBuffer<'a> for wgpu::RenderPass<'a> {
+          fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+              wgpu::RenderPass::set_vertex_buffer(self, slot, buffer_slice);
+          }
+      }
+
+      impl<'a> SetVertexBuffer<'a> for wgpu::RenderBundleEncoder<'a> {
+          fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+              wgpu::RenderBundleEncoder::set_vertex_buffer(self, slot, buffer_slice);
+          }
+      }
+  }
+}
+
+/// The device features required to create pipelines for this module.
+fn required_features(module: &naga::Module) -> TokenStream {
+  let mut features = wgpu::Features::empty();
+  if wgsl::has_push_constants(module) {
+    features |= wgpu::Features::PUSH_CONSTANTS;
+  }
+  if wgsl::has_ray_queries(module) {
+    features |= wgpu::Features::RAY_QUERY;
+  }
+
+  // Don't generate unused code.
+  let mut flags = features
+    .iter_names()
+    .map(|(name, _)| Ident::new(name, Span::call_site()));
+  match flags.next() {
+    Some(first) => {
+      let features = flags.fold(
+        quote!(wgpu::Features::#first),
+        |features, flag| quote!(#features.union(wgpu::Features::#flag)),
+      );
+      quote!(pub const REQUIRED_FEATURES: wgpu::Features = #features;)
+    }
+    None => quote!(),
+  }
 }
 
 fn pretty_print(tokens: &TokenStream) -> String {
@@ -169,24 +845,120 @@ fn indexed_name_to_ident(name: &str, index: u32) -> Ident {
   Ident::new(&format!("{name}{index}"), Span::call_site())
 }
 
-fn shader_module_using_final_shader_string(entry: &WgslEntryResult) -> TokenStream {
-  let shader_content = module_to_source(&entry.naga_module).unwrap();
+/// The debug label for a generated wgpu object like "triangle::BindGroup1".
+fn object_label(mod_name: &str, name: &str, options: &WgslBindgenOption) -> String {
+  let prefix = options.label_prefix.as_deref().unwrap_or_default();
+  format!("{prefix}{mod_name}::{name}")
+}
+
+fn shader_output_error(
+  entry: &WgslEntryResult,
+  message: impl ToString,
+) -> CreateModuleError {
+  CreateModuleError::ShaderOutput {
+    name: entry
+      .source_including_deps
+      .source_file
+      .file_path
+      .to_string(),
+    message: message.to_string(),
+    src: None,
+    span: None,
+  }
+}
+
+fn shader_module_using_final_shader_string(
+  entry: &WgslEntryResult,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let shader_content =
+    module_to_source(&entry.naga_module).map_err(|e| shader_output_error(entry, e))?;
   let shader_literal = create_shader_raw_string_literal(&shader_content);
+  let label = object_label(&entry.mod_name, "ShaderModule", options);
   let create_shader_module = quote! {
       pub fn create_shader_module(device: &wgpu::Device) -> wgpu::ShaderModule {
           let source = std::borrow::Cow::Borrowed(SHADER_STRING);
           device.create_shader_module(wgpu::ShaderModuleDescriptor {
-              label: None,
+              label: Some(#label),
               source: wgpu::ShaderSource::Wgsl(source)
           })
       }
   };
   let shader_str_def = quote!(const SHADER_STRING: &'static str = #shader_literal;);
 
-  quote! {
+  Ok(quote! {
     #create_shader_module
     #shader_str_def
-  }
+  })
+}
+
+fn shader_module_using_spirv(
+  entry: &WgslEntryResult,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let shader_content =
+    module_to_source(&entry.naga_module).map_err(|e| shader_output_error(entry, e))?;
+  let shader_literal = create_shader_raw_string_literal(&shader_content);
+  let words = module_to_spirv(&entry.naga_module, &entry.module_info)
+    .map_err(|e| shader_output_error(entry, e))?
+    .into_iter()
+    .map(|word| LitInt::new(&format!("{word:#010x}"), Span::call_site()));
+  let label = object_label(&entry.mod_name, "ShaderModule", options);
+
+  Ok(quote! {
+      pub fn create_shader_module(device: &wgpu::Device) -> wgpu::ShaderModule {
+          // Fall back to WGSL when the driver can't use the SPIR-V directly.
+          let source = if device.features().contains(wgpu::Features::SPIRV_SHADER_PASSTHROUGH) {
+              wgpu::ShaderSource::SpirV(std::borrow::Cow::Borrowed(SHADER_SPIRV))
+          } else {
+              wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(SHADER_STRING))
+          };
+          device.create_shader_module(wgpu::ShaderModuleDescriptor {
+              label: Some(#label),
+              source
+          })
+      }
+      const SHADER_STRING: &'static str = #shader_literal;
+      const SHADER_SPIRV: &[u32] = &[#(#words),*];
+  })
+}
+
+fn shader_module_using_naga_ir(
+  entry: &WgslEntryResult,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let bytes =
+    module_to_naga_ir(&entry.naga_module).map_err(|e| shader_output_error(entry, e))?;
+  let bytes = LitByteStr::new(&bytes, Span::call_site());
+  let label = object_label(&entry.mod_name, "ShaderModule", options);
+
+  Ok(quote! {
+      /// The naga version used to serialize [SHADER_NAGA_IR].
+      pub const SHADER_NAGA_VERSION: &str = #NAGA_IR_VERSION;
+
+      /// Deserializes the module included at build time.
+      /// This requires a `bincode` 1.x dependency and the `deserialize` feature of naga.
+      ///
+      /// # Panics
+      /// The module can only be deserialized by the naga version in [SHADER_NAGA_VERSION],
+      /// so this panics if wgpu uses a different naga version.
+      pub fn naga_module() -> wgpu::naga::Module {
+          bincode::deserialize(SHADER_NAGA_IR).unwrap_or_else(|e| {
+              panic!(
+                  "failed to deserialize the naga module from naga {SHADER_NAGA_VERSION}, which must match the naga version of wgpu: {e}"
+              )
+          })
+      }
+
+      pub fn create_shader_module(device: &wgpu::Device) -> wgpu::ShaderModule {
+          let source = std::borrow::Cow::Owned(naga_module());
+          device.create_shader_module(wgpu::ShaderModuleDescriptor {
+              label: Some(#label),
+              source: wgpu::ShaderSource::Naga(source)
+          })
+      }
+      const SHADER_NAGA_IR: &[u8] = #bytes;
+  })
 }
 
 fn shader_module_using_composer(
@@ -238,19 +1010,121 @@ fn shader_module_using_composer(
 
   let entry_relative_path =
     get_relative_path(&entry.source_including_deps.source_file.file_path);
+  let label = object_label(&entry.mod_name, "ShaderModule", options);
+
+  // Modules without shader defs keep the simpler signatures.
+  // Permutations use the entry module's `ShaderDefs` and override their own defs.
+  let shader_defs = get_shader_defs(&entry.source_including_deps);
+  let permutation_defs = entry
+    .permutation
+    .as_ref()
+    .map(|permutation| permutation.shader_defs.as_slice())
+    .unwrap_or_default();
+  let inserts: Vec<_> = permutation_defs
+    .iter()
+    .map(|(name, value)| {
+      let value = shader_def_value(value);
+      quote!(shader_defs.insert(#name.to_owned(), #value);)
+    })
+    .collect();
+
+  let shader_defs_type = shader_defs_type(entry, options);
+  let (shader_defs_items, shader_defs_param, shader_defs_arg, shader_defs_field) =
+    match &shader_defs_type {
+      None => {
+        let shader_defs_field = if inserts.is_empty() {
+          quote!()
+        } else {
+          quote! {
+              shader_defs: {
+                  let mut shader_defs = std::collections::HashMap::new();
+                  #(#inserts)*
+                  shader_defs
+              },
+          }
+        };
+        (quote!(), quote!(), quote!(), shader_defs_field)
+      }
+      Some(shader_defs_type) => {
+        let shader_defs_items = if entry.permutation.is_some() {
+          quote!()
+        } else {
+          shader_defs_items(&shader_defs)
+        };
+        let shader_defs_field = if inserts.is_empty() {
+          quote!(shader_defs: shader_defs.to_shader_defs(),)
+        } else {
+          quote! {
+              shader_defs: {
+                  let mut shader_defs = shader_defs.to_shader_defs();
+                  #(#inserts)*
+                  shader_defs
+              },
+          }
+        };
+        (
+          shader_defs_items,
+          quote!(, shader_defs: &#shader_defs_type),
+          quote!(, shader_defs),
+          shader_defs_field,
+        )
+      }
+    };
+
+  let verify_module_layout = verify_module_layout_items(&entry.naga_module);
+
+  // The bindings are generated with the default shader defs, so other defs must not
+  // change the bindings or struct layouts.
+  let (create_shader_module_doc, verify_shader_defs) = match &shader_defs_type {
+    Some(shader_defs_type) => (
+      quote! {
+          /// Composes the shader module with the shader defs.
+          ///
+          /// # Panics
+          /// In debug builds, if the shader defs change the bindings or struct layouts
+          /// of the generated code, which are generated from `ShaderDefs::default()`.
+      },
+      quote! {
+        #[cfg(debug_assertions)]
+        if *shader_defs != #shader_defs_type::default() {
+            if let Err(e) = verify_module_layout(&module) {
+                panic!("shader defs {shader_defs:?} don't match the generated bindings: {e}");
+            }
+        }
+      },
+    ),
+    None => (quote!(), quote!()),
+  };
+  let reload_shader_module = if uses_hot_reload(options) {
+    reload_shader_module_items(
+      &entry.source_including_deps,
+      &label,
+      &shader_defs_param,
+      &shader_defs_field,
+    )
+  } else {
+    quote!()
+  };
 
   quote! {
+    #shader_defs_items
+
+
     pub fn init_composer() -> naga_oil::compose::Composer {
       #[allow(unused_mut)]
-      let mut composer = naga_oil::compose::Composer::default();
+      let mut composer = naga_oil::compose::Composer::default()
+        .with_capabilities(wgpu::naga::valid::Capabilities::all());
       #(#add_shader_modules_token_stream)*
       composer
     }
 
-    pub fn make_naga_module(composer: &mut naga_oil::compose::Composer) -> wgpu::naga::Module {
+    pub fn make_naga_module(
+      composer: &mut naga_oil::compose::Composer #shader_defs_param
+    ) -> wgpu::naga::Module {
       composer.make_naga_module(naga_oil::compose::NagaModuleDescriptor {
         source: include_str!(#entry_relative_path),
         file_path: #entry_relative_path,
+        #shader_defs_field
         ..Default::default()
       }).expect("failed to build naga module")
     }
@@ -273,37 +1147,81 @@ fn shader_module_using_composer(
       ).expect("failed to convert naga module to source")
     }
 
-    pub fn create_shader_module(device: &wgpu::Device) -> wgpu::ShaderModule {
+    #create_shader_module_doc
+    pub fn create_shader_module(
+      device: &wgpu::Device #shader_defs_param
+    ) -> wgpu::ShaderModule {
       let mut composer = init_composer();
-      let module = make_naga_module(&mut composer);
+      let module = make_naga_module(&mut composer #shader_defs_arg);
+      #verify_shader_defs
 
       let source = naga_module_to_string(&module);
       let source = std::borrow::Cow::Owned(source);
       device.create_shader_module(wgpu::ShaderModuleDescriptor {
-        label: None,
+        label: Some(#label),
         source: wgpu::ShaderSource::Wgsl(source)
       })
     }
+
+    #verify_module_layout
+
+    #reload_shader_module
   }
 }
 
-fn shader_module(entry: &WgslEntryResult, options: &WgslBindgenOption) -> TokenStream {
+/// The type of the `shader_defs` parameter of `create_shader_module` relative to the
+/// entry module, or `None` if the shader module doesn't use shader defs.
+fn shader_defs_type(
+  entry: &WgslEntryResult,
+  options: &WgslBindgenOption,
+) -> Option<TokenStream> {
+  if options.shader_source_output_type != WgslShaderSourceOutputType::Composer
+    || get_shader_defs(&entry.source_including_deps).is_empty()
+  {
+    None
+  } else if entry.permutation.is_some() {
+    // Permutations use the entry module's `ShaderDefs`.
+    Some(quote!(super::ShaderDefs))
+  } else {
+    Some(quote!(ShaderDefs))
+  }
+}
+
+fn uses_hot_reload(options: &WgslBindgenOption) -> bool {
+  options.hot_reload
+    && options.shader_source_output_type == WgslShaderSourceOutputType::Composer
+}
+
+fn shader_module(
+  entry: &WgslEntryResult,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
   match options.shader_source_output_type {
     WgslShaderSourceOutputType::FinalShaderString => {
-      shader_module_using_final_shader_string(entry)
+      shader_module_using_final_shader_string(entry, options)
+    }
+    WgslShaderSourceOutputType::Composer => {
+      Ok(shader_module_using_composer(entry, options))
     }
-    WgslShaderSourceOutputType::Composer => shader_module_using_composer(entry, options),
+    WgslShaderSourceOutputType::SpirV => shader_module_using_spirv(entry, options),
+    WgslShaderSourceOutputType::NagaIr => shader_module_using_naga_ir(entry, options),
   }
 }
 
-fn compute_module(module: &naga::Module) -> TokenStream {
+fn compute_module(
+  mod_name: &str,
+  module: &naga::Module,
+  shader_defs_type: Option<&TokenStream>,
+  options: &WgslBindgenOption,
+) -> TokenStream {
   let entry_points: Vec<_> = module
     .entry_points
     .iter()
     .filter_map(|e| {
       if e.stage == naga::ShaderStage::Compute {
         let workgroup_size_constant = workgroup_size(e);
-        let create_pipeline = create_compute_pipeline(e);
+        let create_pipeline =
+          create_compute_pipeline(mod_name, e, shader_defs_type, options);
 
         Some(quote! {
             #workgroup_size_constant
@@ -327,16 +1245,26 @@ fn compute_module(module: &naga::Module) -> TokenStream {
   }
 }
 
-fn create_compute_pipeline(e: &naga::EntryPoint) -> TokenStream {
+fn create_compute_pipeline(
+  mod_name: &str,
+  e: &naga::EntryPoint,
+  shader_defs_type: Option<&TokenStream>,
+  options: &WgslBindgenOption,
+) -> TokenStream {
   // Compute pipeline creation has few parameters and can be generated.
   let pipeline_name =
     Ident::new(&format!("create_{}_pipeline", e.name), Span::call_site());
   let entry_point = &e.name;
-  // TODO: Include a user supplied module name in the label?
-  let label = format!("Compute Pipeline {}", e.name);
+  let label = object_label(mod_name, &format!("ComputePipeline::{}", e.name), options);
+  let (shader_defs_param, shader_defs_arg) = match shader_defs_type {
+    Some(ty) => (quote!(, shader_defs: &super::#ty), quote!(, shader_defs)),
+    None => (quote!(), quote!()),
+  };
   quote! {
-      pub fn #pipeline_name(device: &wgpu::Device) -> wgpu::ComputePipeline {
-          let module = super::create_shader_module(device);
+      pub fn #pipeline_name(
+          device: &wgpu::Device #shader_defs_param
+      ) -> wgpu::ComputePipeline {
+          let module = super::create_shader_module(device #shader_defs_arg);
           let layout = super::create_pipeline_layout(device);
           device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
               label: Some(#label),
@@ -356,9 +1284,17 @@ fn workgroup_size(e: &naga::EntryPoint) -> TokenStream {
   quote!(pub const #name: [u32; 3] = [#x, #y, #z];)
 }
 
-fn vertex_struct_methods(module: &naga::Module) -> TokenStream {
-  let structs = vertex_input_structs(module);
-  quote!(#(#structs)*)
+fn vertex_struct_methods(
+  module: &naga::Module,
+  shared_items: &HashSet<String>,
+) -> Result<TokenStream, CreateModuleError> {
+  // Reexported structs already have the methods from the entry module.
+  let structs = vertex_input_structs(module)?
+    .into_iter()
+    .zip(wgsl::get_vertex_input_structs(module))
+    .filter(|(_, input)| !shared_items.contains(&input.name))
+    .map(|(tokens, _)| tokens);
+  Ok(quote!(#(#structs)*))
 }
 
 fn entry_point_constants(module: &naga::Module) -> TokenStream {
@@ -385,12 +1321,20 @@ fn entry_point_constants(module: &naga::Module) -> TokenStream {
 fn vertex_states(module: &naga::Module) -> TokenStream {
   let vertex_inputs = wgsl::get_vertex_input_structs(module);
   let mut step_mode_params = vec![];
+  let mut buffer_params = vec![];
+  let mut set_buffers = vec![];
   let layout_expressions: Vec<TokenStream> = vertex_inputs
     .iter()
     .map(|input| {
       let name = Ident::new(&input.name, Span::call_site());
       let step_mode = Ident::new(&input.name.to_snake(), Span::call_site());
       step_mode_params.push(quote!(#step_mode: wgpu::VertexStepMode));
+
+      // The buffer slots match the order of the vertex buffer layouts.
+      let slot = Literal::usize_unsuffixed(set_buffers.len());
+      buffer_params.push(quote!(#step_mode: wgpu::BufferSlice<'a>));
+      set_buffers.push(quote!(pass.set_vertex_buffer(#slot, #step_mode);));
+
       quote!(#name::vertex_buffer_layout(#step_mode))
     })
     .collect();
@@ -427,6 +1371,19 @@ fn vertex_states(module: &naga::Module) -> TokenStream {
   if vertex_entries.is_empty() {
     quote!()
   } else {
+    let set_vertex_buffers = if set_buffers.is_empty() {
+      quote!()
+    } else {
+      quote! {
+          pub fn set_vertex_buffers<'a>(
+              pass: &mut impl _root::pass_ext::SetVertexBuffer<'a>,
+              #(#buffer_params),*
+          ) {
+              #(#set_buffers)*
+          }
+      }
+    };
+
     quote! {
         #[derive(Debug)]
         pub struct VertexEntry<const N: usize> {
@@ -446,11 +1403,14 @@ fn vertex_states(module: &naga::Module) -> TokenStream {
         }
 
         #(#vertex_entries)*
+        #set_vertex_buffers
     }
   }
 }
 
-fn vertex_input_structs(module: &naga::Module) -> Vec<TokenStream> {
+fn vertex_input_structs(
+  module: &naga::Module,
+) -> Result<Vec<TokenStream>, CreateModuleError> {
   let vertex_inputs = wgsl::get_vertex_input_structs(module);
   vertex_inputs.iter().map(|input|  {
         let name = Ident::new(&input.name, Span::call_site());
@@ -463,19 +1423,27 @@ fn vertex_input_structs(module: &naga::Module) -> Vec<TokenStream> {
             .map(|(location, m)| {
                 let field_name: TokenStream = m.name.as_ref().unwrap().parse().unwrap();
                 let location = Index::from(*location as usize);
-                let format = wgsl::vertex_format(&module.types[m.ty]);
+                let ty = &module.types[m.ty];
+                let format = wgsl::vertex_format(ty).ok_or_else(|| {
+                    CreateModuleError::UnsupportedVertexFormat {
+                        name: format!("{}.{}", demangle(&input.name), m.name.as_ref().unwrap()),
+                        ty: wgsl::type_name(module, ty),
+                        src: None,
+                        span: None,
+                    }
+                })?;
                 // TODO: Will the debug implementation always work with the macro?
                 let format = Ident::new(&format!("{format:?}"), Span::call_site());
 
-                quote! {
+                Ok(quote! {
                     wgpu::VertexAttribute {
                         format: wgpu::VertexFormat::#format,
                         offset: std::mem::offset_of!(#name, #field_name) as u64,
                         shader_location: #location,
                     }
-                }
+                })
             })
-            .collect();
+            .collect::<Result<Vec<_>, CreateModuleError>>()?;
 
 
         // The vertex_attr_array! macro doesn't account for field alignment.
@@ -487,7 +1455,7 @@ fn vertex_input_structs(module: &naga::Module) -> Vec<TokenStream> {
         // https://gpuweb.github.io/gpuweb/#abstract-opdef-validating-gpuvertexbufferlayout
 
         // TODO: Support vertex inputs that aren't in a struct.
-        quote! {
+        Ok(quote! {
             impl #name {
                 pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; #count] = [#(#attributes),*];
 
@@ -499,7 +1467,7 @@ fn vertex_input_structs(module: &naga::Module) -> Vec<TokenStream> {
                     }
                 }
             }
-        }
+        })
     }).collect()
 }
 
@@ -518,7 +1486,6 @@ macro_rules! assert_tokens_eq {
 mod test {
   use indoc::indoc;
 
-  use self::bevy_util::source_file::SourceFile;
   use super::*;
 
   fn create_shader_module(
@@ -527,13 +1494,16 @@ mod test {
   ) -> Result<String, CreateModuleError> {
     let naga_module = naga::front::wgsl::parse_str(source).unwrap();
     let dummy_source = SourceFile::create(SourceFilePath::new(""), None, "".into());
+    let module_info = wgsl::module_info(&naga_module);
     let entry = WgslEntryResult {
       mod_name: "test".into(),
       naga_module,
+      module_info,
       source_including_deps: SourceWithFullDependenciesResult {
         full_dependencies: Default::default(),
         source_file: &dummy_source,
       },
+      permutation: None,
     };
 
     create_rust_bindings(vec![entry], &options)
@@ -562,7 +1532,7 @@ mod test {
                         device
                             .create_pipeline_layout(
                                 &wgpu::PipelineLayoutDescriptor {
-                                    label: None,
+                                    label: Some("test::PipelineLayout"),
                                     bind_group_layouts: &[],
                                     push_constant_ranges: &[],
                                 },
@@ -572,7 +1542,7 @@ mod test {
                         let source = std::borrow::Cow::Borrowed(SHADER_STRING);
                         device
                             .create_shader_module(wgpu::ShaderModuleDescriptor {
-                                label: None,
+                                label: Some("test::ShaderModule"),
                                 source: wgpu::ShaderSource::Wgsl(source),
                             })
                     }
@@ -620,8 +1590,80 @@ mod test {
             fn main() {}
         "#};
 
+    // The gap in the groups is allowed, so the bindings are checked next.
     let result = create_shader_module(source, WgslBindgenOption::default());
-    assert!(matches!(result, Err(CreateModuleError::NonConsecutiveBindGroups)));
+    assert!(matches!(
+      result,
+      Err(CreateModuleError::UnsupportedBindingType { name, .. }) if name == "a"
+    ));
+  }
+
+  #[test]
+  fn create_shader_module_non_consecutive_bind_groups_structs() {
+    let source = indoc! {r#"
+            struct A {
+                f: vec4<f32>
+            };
+            @group(0) @binding(0) var<uniform> a: A;
+            @group(1) @binding(0) var<uniform> b: A;
+            @group(3) @binding(0) var<uniform> c: A;
+
+            @fragment
+            fn main() {}
+        "#};
+
+    create_shader_module(source, WgslBindgenOption::default()).unwrap();
+  }
+
+  #[test]
+  fn pipeline_layout_non_consecutive_bind_groups() {
+    let source = indoc! {r#"
+            @group(1) @binding(0) var<uniform> a: vec4<f32>;
+            @group(3) @binding(0) var<uniform> b: vec4<f32>;
+
+            @fragment
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data =
+      get_bind_group_data(&module, &wgsl::module_info(&module)).unwrap();
+    let actual =
+      pipeline_layout("test", &bind_group_data, false, &WgslBindgenOption::default());
+
+    assert_tokens_eq!(
+      quote! {
+          pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+              create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+          }
+          pub fn create_pipeline_layout_with_layouts(
+              device: &wgpu::Device,
+              layouts: &bind_groups::Layouts,
+          ) -> wgpu::PipelineLayout {
+              let empty_layout = device
+                  .create_bind_group_layout(
+                      &wgpu::BindGroupLayoutDescriptor {
+                          label: Some("test::EmptyBindGroupLayout"),
+                          entries: &[],
+                      },
+                  );
+              device
+                  .create_pipeline_layout(
+                      &wgpu::PipelineLayoutDescriptor {
+                          label: Some("test::PipelineLayout"),
+                          bind_group_layouts: &[
+                              &empty_layout,
+                              &layouts.bind_group1,
+                              &empty_layout,
+                              &layouts.bind_group3,
+                          ],
+                          push_constant_ranges: &[],
+                      },
+                  )
+          }
+      },
+      actual
+    );
   }
 
   #[test]
@@ -638,7 +1680,10 @@ mod test {
         "#};
 
     let result = create_shader_module(source, WgslBindgenOption::default());
-    assert!(matches!(result, Err(CreateModuleError::DuplicateBinding { binding: 2 })));
+    assert!(matches!(
+      result,
+      Err(CreateModuleError::DuplicateBinding { binding: 2, .. })
+    ));
   }
 
   #[test]
@@ -649,7 +1694,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_methods(&module);
+    let actual = vertex_struct_methods(&module, &HashSet::new()).unwrap();
 
     assert_tokens_eq!(quote!(), actual);
   }
@@ -669,7 +1714,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_methods(&module);
+    let actual = vertex_struct_methods(&module, &HashSet::new()).unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -726,7 +1771,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_methods(&module);
+    let actual = vertex_struct_methods(&module, &HashSet::new()).unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -784,7 +1829,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_methods(&module);
+    let actual = vertex_struct_methods(&module, &HashSet::new()).unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -841,7 +1886,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_methods(&module);
+    let actual = vertex_struct_methods(&module, &HashSet::new()).unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -883,6 +1928,24 @@ mod test {
     );
   }
 
+  #[test]
+  fn write_vertex_module_shared_input() {
+    let source = indoc! {r#"
+            struct VertexInput0 {
+                @location(0) position: vec4<f32>,
+            };
+
+            @vertex
+            fn main(in0: VertexInput0) {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let shared_items = HashSet::from(["VertexInput0".to_owned()]);
+    let actual = vertex_struct_methods(&module, &shared_items).unwrap();
+
+    assert_tokens_eq!(quote!(), actual);
+  }
+
   #[test]
   fn write_compute_module_empty() {
     let source = indoc! {r#"
@@ -891,7 +1954,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = compute_module(&module);
+    let actual = compute_module("test", &module, None, &WgslBindgenOption::default());
 
     assert_tokens_eq!(quote!(), actual);
   }
@@ -910,7 +1973,7 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = compute_module(&module);
+    let actual = compute_module("test", &module, None, &WgslBindgenOption::default());
 
     assert_tokens_eq!(
       quote! {
@@ -922,7 +1985,7 @@ mod test {
                   device
                       .create_compute_pipeline(
                           &wgpu::ComputePipelineDescriptor {
-                              label: Some("Compute Pipeline main1"),
+                              label: Some("test::ComputePipeline::main1"),
                               layout: Some(&layout),
                               module: &module,
                               entry_point: "main1",
@@ -936,7 +1999,7 @@ mod test {
                   device
                       .create_compute_pipeline(
                           &wgpu::ComputePipelineDescriptor {
-                              label: Some("Compute Pipeline main2"),
+                              label: Some("test::ComputePipeline::main2"),
                               layout: Some(&layout),
                               module: &module,
                               entry_point: "main2",
@@ -949,6 +2012,92 @@ mod test {
     );
   }
 
+  #[test]
+  fn write_compute_module_label_prefix() {
+    let source = indoc! {r#"
+            @compute
+            @workgroup_size(64)
+            fn main() {}
+        "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = compute_module(
+      "test",
+      &module,
+      None,
+      &WgslBindgenOption {
+        label_prefix: Some("app::".to_string()),
+        ..Default::default()
+      },
+    );
+
+    assert_tokens_eq!(
+      quote! {
+          pub mod compute {
+              pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [64, 1, 1];
+              pub fn create_main_pipeline(device: &wgpu::Device) -> wgpu::ComputePipeline {
+                  let module = super::create_shader_module(device);
+                  let layout = super::create_pipeline_layout(device);
+                  device
+                      .create_compute_pipeline(
+                          &wgpu::ComputePipelineDescriptor {
+                              label: Some("app::test::ComputePipeline::main"),
+                              layout: Some(&layout),
+                              module: &module,
+                              entry_point: "main",
+                          },
+                      )
+              }
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn write_compute_module_shader_defs() {
+    let source = indoc! {r#"
+            @compute
+            @workgroup_size(64)
+            fn main() {}
+        "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = compute_module(
+      "test",
+      &module,
+      Some(&quote!(ShaderDefs)),
+      &WgslBindgenOption::default(),
+    );
+
+    assert_tokens_eq!(
+      quote! {
+          pub mod compute {
+              pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [64, 1, 1];
+              pub fn create_main_pipeline(
+                  device: &wgpu::Device,
+                  shader_defs: &super::ShaderDefs,
+              ) -> wgpu::ComputePipeline {
+                  let module = super::create_shader_module(device, shader_defs);
+                  let layout = super::create_pipeline_layout(device);
+                  device
+                      .create_compute_pipeline(
+                          &wgpu::ComputePipelineDescriptor {
+                              label: Some("test::ComputePipeline::main"),
+                              layout: Some(&layout),
+                              module: &module,
+                              entry_point: "main",
+                          },
+                      )
+              }
+          }
+      },
+      actual
+    );
+  }
+
   #[test]
   fn write_entry_constants() {
     let source = indoc! {r#"
@@ -1065,6 +2214,12 @@ mod test {
                   buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
               }
           }
+          pub fn set_vertex_buffers<'a>(
+              pass: &mut impl _root::pass_ext::SetVertexBuffer<'a>,
+              vertex_input: wgpu::BufferSlice<'a>,
+          ) {
+              pass.set_vertex_buffer(0, vertex_input);
+          }
       },
       actual
     )
@@ -1113,11 +2268,62 @@ mod test {
                   ],
               }
           }
+          pub fn set_vertex_buffers<'a>(
+              pass: &mut impl _root::pass_ext::SetVertexBuffer<'a>,
+              input0: wgpu::BufferSlice<'a>,
+              input1: wgpu::BufferSlice<'a>,
+          ) {
+              pass.set_vertex_buffer(0, input0);
+              pass.set_vertex_buffer(1, input1);
+          }
       },
       actual
     )
   }
 
+  #[test]
+  fn write_required_features() {
+    let source = indoc! {r#"
+            var<push_constant> scale: f32;
+            @group(0) @binding(0) var acc_struct: acceleration_structure;
+
+            @compute
+            @workgroup_size(1)
+            fn main() {
+                var rq: ray_query;
+                let desc = RayDesc(0u, 0xFFu, 0.1, scale, vec3(0.0), vec3(0.0, 0.0, 1.0));
+                rayQueryInitialize(&rq, acc_struct, desc);
+            }
+        "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = required_features(&module);
+
+    assert_tokens_eq!(
+      quote! {
+          pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::PUSH_CONSTANTS
+              .union(wgpu::Features::RAY_QUERY);
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn write_required_features_none() {
+    let source = indoc! {r#"
+            @compute
+            @workgroup_size(1)
+            fn main() {}
+        "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = required_features(&module);
+
+    assert_tokens_eq!(quote!(), actual);
+  }
+
   #[test]
   fn write_vertex_states_no_entries() {
     let source = indoc! {r#"
@@ -0,0 +1,658 @@
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::Ident;
+
+use crate::bevy_util::demangle;
+
+/// Defines the items in the generator and returns the same items for the generated
+/// `module_layout` module, so bindings are described the same way in both.
+macro_rules! shared_items {
+  ($($tokens:tt)*) => {
+    $($tokens)*
+
+    fn shared_items() -> TokenStream {
+      quote! { $($tokens)* }
+    }
+  };
+}
+
+shared_items! {
+    /// The type of a binding as it is used by the shader.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BindingKind {
+        Uniform {
+            size: u32,
+        },
+        Storage {
+            read_only: bool,
+            size: u32,
+        },
+        Texture {
+            dim: naga::ImageDimension,
+            arrayed: bool,
+            sample_kind: naga::ScalarKind,
+            multisampled: bool,
+        },
+        DepthTexture {
+            dim: naga::ImageDimension,
+            arrayed: bool,
+            multisampled: bool,
+        },
+        StorageTexture {
+            dim: naga::ImageDimension,
+            arrayed: bool,
+            format: naga::StorageFormat,
+            read: bool,
+            write: bool,
+        },
+        Sampler {
+            comparison: bool,
+        },
+        AccelerationStructure,
+    }
+
+    /// The number of resources in a binding.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BindingCount {
+        Single,
+        /// A `binding_array` with a fixed size.
+        Array(u32),
+        /// A `binding_array` without a size.
+        RuntimeArray,
+    }
+
+    /// Describes the binding of a global variable.
+    /// Returns `None` for types that can't be used in bindings.
+    pub fn binding_kind(
+        module: &naga::Module,
+        global: &naga::GlobalVariable,
+    ) -> Option<(BindingKind, BindingCount)> {
+        let (inner, count) = match &module.types[global.ty].inner {
+            naga::TypeInner::BindingArray { base, size } => {
+                let count = match size {
+                    naga::ArraySize::Constant(size) => BindingCount::Array(size.get()),
+                    naga::ArraySize::Dynamic => BindingCount::RuntimeArray,
+                };
+                (&module.types[*base].inner, count)
+            }
+            inner => (inner, BindingCount::Single),
+        };
+
+        let kind = match (global.space, inner) {
+            (naga::AddressSpace::Uniform, _) => BindingKind::Uniform {
+                size: inner.size(module.to_ctx()),
+            },
+            (naga::AddressSpace::Storage { access }, _) => BindingKind::Storage {
+                read_only: !access.contains(naga::StorageAccess::STORE),
+                size: inner.size(module.to_ctx()),
+            },
+            (_, naga::TypeInner::Image { dim, arrayed, class }) => match *class {
+                naga::ImageClass::Sampled { kind, multi } => BindingKind::Texture {
+                    dim: *dim,
+                    arrayed: *arrayed,
+                    sample_kind: kind,
+                    multisampled: multi,
+                },
+                naga::ImageClass::Depth { multi } => BindingKind::DepthTexture {
+                    dim: *dim,
+                    arrayed: *arrayed,
+                    multisampled: multi,
+                },
+                naga::ImageClass::Storage { format, access } => BindingKind::StorageTexture {
+                    dim: *dim,
+                    arrayed: *arrayed,
+                    format,
+                    read: access.contains(naga::StorageAccess::LOAD),
+                    write: access.contains(naga::StorageAccess::STORE),
+                },
+            },
+            (_, naga::TypeInner::Sampler { comparison }) => BindingKind::Sampler {
+                comparison: *comparison,
+            },
+            (_, naga::TypeInner::AccelerationStructure) => BindingKind::AccelerationStructure,
+            _ => return None,
+        };
+        Some((kind, count))
+    }
+}
+
+impl ToTokens for BindingKind {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let naga_variant = |ty: &str, value: &dyn std::fmt::Debug| {
+      let ty = Ident::new(ty, Span::call_site());
+      let variant = Ident::new(&format!("{value:?}"), Span::call_site());
+      quote!(wgpu::naga::#ty::#variant)
+    };
+
+    tokens.extend(match *self {
+      BindingKind::Uniform { size } => {
+        let size = Literal::u32_unsuffixed(size);
+        quote!(_root::module_layout::BindingKind::Uniform { size: #size })
+      }
+      BindingKind::Storage { read_only, size } => {
+        let size = Literal::u32_unsuffixed(size);
+        quote! {
+            _root::module_layout::BindingKind::Storage {
+                read_only: #read_only,
+                size: #size,
+            }
+        }
+      }
+      BindingKind::Texture {
+        dim,
+        arrayed,
+        sample_kind,
+        multisampled,
+      } => {
+        let dim = naga_variant("ImageDimension", &dim);
+        let sample_kind = naga_variant("ScalarKind", &sample_kind);
+        quote! {
+            _root::module_layout::BindingKind::Texture {
+                dim: #dim,
+                arrayed: #arrayed,
+                sample_kind: #sample_kind,
+                multisampled: #multisampled,
+            }
+        }
+      }
+      BindingKind::DepthTexture {
+        dim,
+        arrayed,
+        multisampled,
+      } => {
+        let dim = naga_variant("ImageDimension", &dim);
+        quote! {
+            _root::module_layout::BindingKind::DepthTexture {
+                dim: #dim,
+                arrayed: #arrayed,
+                multisampled: #multisampled,
+            }
+        }
+      }
+      BindingKind::StorageTexture {
+        dim,
+        arrayed,
+        format,
+        read,
+        write,
+      } => {
+        let dim = naga_variant("ImageDimension", &dim);
+        let format = naga_variant("StorageFormat", &format);
+        quote! {
+            _root::module_layout::BindingKind::StorageTexture {
+                dim: #dim,
+                arrayed: #arrayed,
+                format: #format,
+                read: #read,
+                write: #write,
+            }
+        }
+      }
+      BindingKind::Sampler { comparison } => {
+        quote!(_root::module_layout::BindingKind::Sampler { comparison: #comparison })
+      }
+      BindingKind::AccelerationStructure => {
+        quote!(_root::module_layout::BindingKind::AccelerationStructure)
+      }
+    });
+  }
+}
+
+impl ToTokens for BindingCount {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    tokens.extend(match *self {
+      BindingCount::Single => quote!(_root::module_layout::BindingCount::Single),
+      BindingCount::Array(count) => {
+        let count = Literal::u32_unsuffixed(count);
+        quote!(_root::module_layout::BindingCount::Array(#count))
+      }
+      BindingCount::RuntimeArray => {
+        quote!(_root::module_layout::BindingCount::RuntimeArray)
+      }
+    });
+  }
+}
+
+/// The shared types for checking reloaded modules against the generated layouts.
+pub fn module_layout_module() -> TokenStream {
+  let shared_items = shared_items();
+
+  quote! {
+      use wgpu::naga;
+
+      #shared_items
+
+      /// A binding recorded when generating the bindings.
+      #[derive(Debug)]
+      pub struct BindingLayout {
+          pub group: u32,
+          pub binding: u32,
+          pub name: &'static str,
+          pub kind: BindingKind,
+          pub count: BindingCount,
+      }
+
+      /// A struct recorded when generating the bindings.
+      #[derive(Debug)]
+      pub struct StructLayout {
+          pub name: &'static str,
+          /// The name in the composed module, which may be mangled by naga_oil.
+          pub naga_name: &'static str,
+          pub size: u32,
+          pub members: &'static [MemberLayout],
+      }
+
+      #[derive(Debug, PartialEq, Eq)]
+      pub struct MemberLayout {
+          pub name: &'static str,
+          pub offset: u32,
+          pub size: u32,
+      }
+
+      /// A difference between a module and the layouts of the generated code.
+      #[derive(Debug)]
+      pub enum LayoutError {
+          MissingBinding {
+              group: u32,
+              binding: u32,
+              name: &'static str,
+          },
+          UnexpectedBinding {
+              group: u32,
+              binding: u32,
+              name: Option<String>,
+          },
+          BindingType {
+              group: u32,
+              binding: u32,
+              name: &'static str,
+              expected: (BindingKind, BindingCount),
+              actual: Option<(BindingKind, BindingCount)>,
+          },
+          MissingStruct {
+              name: &'static str,
+          },
+          StructSize {
+              name: &'static str,
+              expected: u32,
+              actual: u32,
+          },
+          StructMembers {
+              name: &'static str,
+              expected: usize,
+              actual: usize,
+          },
+          MemberLayout {
+              name: &'static str,
+              member: &'static str,
+              expected_offset: u32,
+              expected_size: u32,
+              actual: Option<(u32, u32)>,
+          },
+      }
+
+      impl std::fmt::Display for LayoutError {
+          fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+              match self {
+                  Self::MissingBinding { group, binding, name } => write!(
+                      f,
+                      "binding `{name}` at @group({group}) @binding({binding}) is missing from the module"
+                  ),
+                  Self::UnexpectedBinding { group, binding, name } => write!(
+                      f,
+                      "binding `{}` at @group({group}) @binding({binding}) is not in the generated bindings",
+                      name.as_deref().unwrap_or_default()
+                  ),
+                  Self::BindingType { group, binding, name, expected, actual } => {
+                      write!(
+                          f,
+                          "binding `{name}` at @group({group}) @binding({binding}) changed from {expected:?} to "
+                      )?;
+                      match actual {
+                          Some(actual) => write!(f, "{actual:?}"),
+                          None => write!(f, "an unsupported type"),
+                      }
+                  }
+                  Self::MissingStruct { name } => {
+                      write!(f, "struct `{name}` is missing from the module")
+                  }
+                  Self::StructSize { name, expected, actual } => write!(
+                      f,
+                      "struct `{name}` changed size from {expected} to {actual} bytes"
+                  ),
+                  Self::StructMembers { name, expected, actual } => write!(
+                      f,
+                      "struct `{name}` changed from {expected} to {actual} members"
+                  ),
+                  Self::MemberLayout { name, member, expected_offset, expected_size, actual } => {
+                      write!(
+                          f,
+                          "member `{name}.{member}` changed from offset {expected_offset} and size {expected_size} to "
+                      )?;
+                      match actual {
+                          Some((offset, size)) => write!(f, "offset {offset} and size {size}"),
+                          None => write!(f, "missing"),
+                      }
+                  }
+              }
+          }
+      }
+
+      impl std::error::Error for LayoutError {}
+
+      pub fn verify_module_layout(
+          module: &wgpu::naga::Module,
+          bindings: &[BindingLayout],
+          structs: &[StructLayout],
+      ) -> Result<(), LayoutError> {
+          for (_, global) in module.global_variables.iter() {
+              let Some(resource) = &global.binding else {
+                  continue;
+              };
+              let (group, binding) = (resource.group, resource.binding);
+              let Some(expected) = bindings
+                  .iter()
+                  .find(|b| b.group == group && b.binding == binding)
+              else {
+                  return Err(LayoutError::UnexpectedBinding { group, binding, name: global.name.clone() });
+              };
+              let actual = binding_kind(module, global);
+              if actual != Some((expected.kind, expected.count)) {
+                  return Err(LayoutError::BindingType {
+                      group,
+                      binding,
+                      name: expected.name,
+                      expected: (expected.kind, expected.count),
+                      actual,
+                  });
+              }
+          }
+
+          for expected in bindings {
+              let found = module.global_variables.iter().any(|(_, g)| {
+                  g.binding.as_ref().is_some_and(|b| b.group == expected.group && b.binding == expected.binding)
+              });
+              if !found {
+                  return Err(LayoutError::MissingBinding {
+                      group: expected.group,
+                      binding: expected.binding,
+                      name: expected.name,
+                  });
+              }
+          }
+
+          for expected in structs {
+              let inner = module
+                  .types
+                  .iter()
+                  .find(|(_, ty)| ty.name.as_deref() == Some(expected.naga_name))
+                  .map(|(_, ty)| &ty.inner);
+              let Some(wgpu::naga::TypeInner::Struct { members, span }) = inner else {
+                  return Err(LayoutError::MissingStruct { name: expected.name });
+              };
+              if *span != expected.size {
+                  return Err(LayoutError::StructSize {
+                      name: expected.name,
+                      expected: expected.size,
+                      actual: *span,
+                  });
+              }
+              if members.len() != expected.members.len() {
+                  return Err(LayoutError::StructMembers {
+                      name: expected.name,
+                      expected: expected.members.len(),
+                      actual: members.len(),
+                  });
+              }
+              for member in expected.members {
+                  let actual = members
+                      .iter()
+                      .find(|m| m.name.as_deref() == Some(member.name))
+                      .map(|m| (m.offset, module.types[m.ty].inner.size(module.to_ctx())));
+                  if actual != Some((member.offset, member.size)) {
+                      return Err(LayoutError::MemberLayout {
+                          name: expected.name,
+                          member: member.name,
+                          expected_offset: member.offset,
+                          expected_size: member.size,
+                          actual,
+                      });
+                  }
+              }
+          }
+
+          Ok(())
+      }
+  }
+}
+
+/// The `verify_module_layout` function with the bindings and struct layouts of the module.
+pub fn verify_module_layout_items(module: &naga::Module) -> TokenStream {
+  let mut globals: Vec<_> = module
+    .global_variables
+    .iter()
+    .filter_map(|(_, global)| global.binding.as_ref().map(|binding| (binding, global)))
+    .collect();
+  globals.sort_by_key(|(binding, _)| (binding.group, binding.binding));
+
+  // Validated modules only use supported types for bindings.
+  let bindings = globals.iter().filter_map(|(binding, global)| {
+    let group = Literal::u32_unsuffixed(binding.group);
+    let binding = Literal::u32_unsuffixed(binding.binding);
+    let name = demangle(global.name.as_deref().unwrap_or_default()).to_string();
+    let (kind, count) = binding_kind(module, global)?;
+    Some(quote! {
+        _root::module_layout::BindingLayout {
+            group: #group,
+            binding: #binding,
+            name: #name,
+            kind: #kind,
+            count: #count,
+        }
+    })
+  });
+
+  // Entry point inputs and outputs don't have a memory layout.
+  let structs = module.types.iter().filter_map(|(_, ty)| {
+    let naga::TypeInner::Struct { members, span } = &ty.inner else {
+      return None;
+    };
+    let naga_name = ty.name.as_deref()?;
+    if members.iter().any(|m| m.binding.is_some()) {
+      return None;
+    }
+
+    let name = demangle(naga_name).to_string();
+    let span = Literal::u32_unsuffixed(*span);
+    let members = members.iter().map(|m| {
+      let member = m.name.as_deref().unwrap_or_default();
+      let offset = Literal::u32_unsuffixed(m.offset);
+      let size = Literal::u32_unsuffixed(module.types[m.ty].inner.size(module.to_ctx()));
+      quote! {
+          _root::module_layout::MemberLayout {
+              name: #member,
+              offset: #offset,
+              size: #size,
+          }
+      }
+    });
+    Some(quote! {
+        _root::module_layout::StructLayout {
+            name: #name,
+            naga_name: #naga_name,
+            size: #span,
+            members: &[#(#members),*],
+        }
+    })
+  });
+
+  quote! {
+      /// Checks that the bindings and struct layouts of `module` match the generated code.
+      /// This catches changes to shaders composed at runtime that would require regenerating the bindings.
+      pub fn verify_module_layout(
+          module: &wgpu::naga::Module,
+      ) -> Result<(), _root::module_layout::LayoutError> {
+          const BINDINGS: &[_root::module_layout::BindingLayout] = &[#(#bindings),*];
+          const STRUCTS: &[_root::module_layout::StructLayout] = &[#(#structs),*];
+          _root::module_layout::verify_module_layout(module, BINDINGS, STRUCTS)
+      }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+
+  use super::*;
+  use crate::assert_tokens_eq;
+
+  #[test]
+  fn write_verify_module_layout() {
+    let source = indoc! {r#"
+        struct Uniforms {
+            color: vec4<f32>,
+            scale: f32,
+        };
+
+        @group(0) @binding(0) var<uniform> uniforms: Uniforms;
+        @group(0) @binding(1) var<storage, read_write> values: array<u32>;
+        @group(1) @binding(0) var color_texture: texture_2d<f32>;
+
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            return uniforms.color * f32(values[0]) * textureLoad(color_texture, vec2(0), 0);
+        }
+    "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = verify_module_layout_items(&module);
+
+    assert_tokens_eq!(
+      quote! {
+          /// Checks that the bindings and struct layouts of `module` match the generated code.
+          /// This catches changes to shaders composed at runtime that would require regenerating the bindings.
+          pub fn verify_module_layout(
+              module: &wgpu::naga::Module,
+          ) -> Result<(), _root::module_layout::LayoutError> {
+              const BINDINGS: &[_root::module_layout::BindingLayout] = &[
+                  _root::module_layout::BindingLayout {
+                      group: 0,
+                      binding: 0,
+                      name: "uniforms",
+                      kind: _root::module_layout::BindingKind::Uniform { size: 32 },
+                      count: _root::module_layout::BindingCount::Single,
+                  },
+                  _root::module_layout::BindingLayout {
+                      group: 0,
+                      binding: 1,
+                      name: "values",
+                      kind: _root::module_layout::BindingKind::Storage {
+                          read_only: false,
+                          size: 4,
+                      },
+                      count: _root::module_layout::BindingCount::Single,
+                  },
+                  _root::module_layout::BindingLayout {
+                      group: 1,
+                      binding: 0,
+                      name: "color_texture",
+                      kind: _root::module_layout::BindingKind::Texture {
+                          dim: wgpu::naga::ImageDimension::D2,
+                          arrayed: false,
+                          sample_kind: wgpu::naga::ScalarKind::Float,
+                          multisampled: false,
+                      },
+                      count: _root::module_layout::BindingCount::Single,
+                  }
+              ];
+              const STRUCTS: &[_root::module_layout::StructLayout] = &[
+                  _root::module_layout::StructLayout {
+                      name: "Uniforms",
+                      naga_name: "Uniforms",
+                      size: 32,
+                      members: &[
+                          _root::module_layout::MemberLayout {
+                              name: "color",
+                              offset: 0,
+                              size: 16,
+                          },
+                          _root::module_layout::MemberLayout {
+                              name: "scale",
+                              offset: 16,
+                              size: 4,
+                          }
+                      ],
+                  }
+              ];
+              _root::module_layout::verify_module_layout(module, BINDINGS, STRUCTS)
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn binding_kind_resources() {
+    let source = indoc! {r#"
+        @group(0) @binding(0) var<storage, read> values: array<vec4<f32>>;
+        @group(0) @binding(1) var depth_textures: binding_array<texture_depth_2d_array, 4>;
+        @group(0) @binding(2) var output: texture_storage_2d<rgba8unorm, write>;
+        @group(0) @binding(3) var shadow_sampler: sampler_comparison;
+        @group(0) @binding(4) var samples: texture_multisampled_2d<u32>;
+
+        @compute @workgroup_size(1)
+        fn main() {
+            let value = values[0] * textureSampleCompareLevel(depth_textures[0], shadow_sampler, vec2(0.0), 0, 0.0);
+            textureStore(output, vec2(0), value * f32(textureLoad(samples, vec2(0), 0).x));
+        }
+    "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual: Vec<_> = module
+      .global_variables
+      .iter()
+      .map(|(_, global)| binding_kind(&module, global).unwrap())
+      .collect();
+
+    assert_eq!(
+      vec![
+        (
+          BindingKind::Storage {
+            read_only: true,
+            size: 16
+          },
+          BindingCount::Single
+        ),
+        (
+          BindingKind::DepthTexture {
+            dim: naga::ImageDimension::D2,
+            arrayed: true,
+            multisampled: false
+          },
+          BindingCount::Array(4)
+        ),
+        (
+          BindingKind::StorageTexture {
+            dim: naga::ImageDimension::D2,
+            arrayed: false,
+            format: naga::StorageFormat::Rgba8Unorm,
+            read: false,
+            write: true
+          },
+          BindingCount::Single
+        ),
+        (BindingKind::Sampler { comparison: true }, BindingCount::Single),
+        (
+          BindingKind::Texture {
+            dim: naga::ImageDimension::D2,
+            arrayed: false,
+            sample_kind: naga::ScalarKind::Uint,
+            multisampled: true
+          },
+          BindingCount::Single
+        ),
+      ],
+      actual
+    );
+  }
+}
@@ -0,0 +1,148 @@
+use naga::back::{glsl, hlsl, msl, spv};
+use naga::valid::ModuleInfo;
+
+use crate::WgslShaderBackend;
+
+/// An error from translating a module with one of the naga backends.
+pub struct BackendCheckError {
+  /// The entry point that failed or `None` if the whole module failed.
+  pub entry_point: Option<String>,
+  pub message: String,
+}
+
+impl BackendCheckError {
+  fn new(entry_point: Option<&str>, err: impl std::error::Error) -> Self {
+    // Include the sources since the backend errors are often just a wrapper.
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+      message += &format!(": {err}");
+      source = err.source();
+    }
+
+    Self {
+      entry_point: entry_point.map(ToOwned::to_owned),
+      message,
+    }
+  }
+}
+
+/// Translates the module with the naga backend and returns the errors for each entry point.
+pub fn check_backend(
+  module: &naga::Module,
+  info: &ModuleInfo,
+  backend: WgslShaderBackend,
+) -> Vec<BackendCheckError> {
+  match backend {
+    WgslShaderBackend::Spirv => check_spirv(module, info),
+    WgslShaderBackend::Msl => check_msl(module, info),
+    WgslShaderBackend::Hlsl => check_hlsl(module, info),
+    WgslShaderBackend::Glsl => check_glsl(module, info),
+  }
+}
+
+fn check_spirv(module: &naga::Module, info: &ModuleInfo) -> Vec<BackendCheckError> {
+  let options = spv::Options::default();
+
+  module
+    .entry_points
+    .iter()
+    .filter_map(|entry_point| {
+      let pipeline_options = spv::PipelineOptions {
+        shader_stage: entry_point.stage,
+        entry_point: entry_point.name.clone(),
+      };
+      spv::write_vec(module, info, &options, Some(&pipeline_options))
+        .err()
+        .map(|err| BackendCheckError::new(Some(&entry_point.name), err))
+    })
+    .collect()
+}
+
+fn check_msl(module: &naga::Module, info: &ModuleInfo) -> Vec<BackendCheckError> {
+  // Use the latest version supported by naga to only report unsupported features.
+  let options = msl::Options {
+    lang_version: (2, 4),
+    ..Default::default()
+  };
+
+  match msl::write_string(module, info, &options, &msl::PipelineOptions::default()) {
+    Ok((_, translation_info)) => {
+      entry_point_errors(module, translation_info.entry_point_names)
+    }
+    Err(err) => vec![BackendCheckError::new(None, err)],
+  }
+}
+
+fn check_hlsl(module: &naga::Module, info: &ModuleInfo) -> Vec<BackendCheckError> {
+  // The push constants register is assigned by wgpu when creating the pipeline.
+  let options = hlsl::Options {
+    push_constants_target: Some(hlsl::BindTarget {
+      space: 0,
+      register: 0,
+      binding_array_size: None,
+    }),
+    ..Default::default()
+  };
+
+  let mut output = String::new();
+  match hlsl::Writer::new(&mut output, &options).write(module, info) {
+    Ok(reflection_info) => entry_point_errors(module, reflection_info.entry_point_names),
+    Err(err) => vec![BackendCheckError::new(None, err)],
+  }
+}
+
+fn check_glsl(module: &naga::Module, info: &ModuleInfo) -> Vec<BackendCheckError> {
+  module
+    .entry_points
+    .iter()
+    .filter_map(|entry_point| {
+      // WebGL2 only supports GLSL ES 3.0, which has no compute shaders.
+      let version = match entry_point.stage {
+        naga::ShaderStage::Compute => glsl::Version::new_gles(310),
+        _ => glsl::Version::Embedded {
+          version: 300,
+          is_webgl: true,
+        },
+      };
+      let options = glsl::Options {
+        version,
+        ..Default::default()
+      };
+      let pipeline_options = glsl::PipelineOptions {
+        shader_stage: entry_point.stage,
+        entry_point: entry_point.name.clone(),
+        multiview: None,
+      };
+
+      let mut output = String::new();
+      glsl::Writer::new(
+        &mut output,
+        module,
+        info,
+        &options,
+        &pipeline_options,
+        naga::proc::BoundsCheckPolicies::default(),
+      )
+      .and_then(|mut writer| writer.write())
+      .err()
+      .map(|err| BackendCheckError::new(Some(&entry_point.name), err))
+    })
+    .collect()
+}
+
+fn entry_point_errors<E: std::error::Error>(
+  module: &naga::Module,
+  entry_point_names: Vec<Result<String, E>>,
+) -> Vec<BackendCheckError> {
+  module
+    .entry_points
+    .iter()
+    .zip(entry_point_names)
+    .filter_map(|(entry_point, result)| {
+      result
+        .err()
+        .map(|err| BackendCheckError::new(Some(&entry_point.name), err))
+    })
+    .collect()
+}
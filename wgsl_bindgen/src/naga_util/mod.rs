@@ -1,2 +1,4 @@
+mod backend_check;
 mod module_to_source;
-pub use module_to_source::*;
\ No newline at end of file
+pub use backend_check::*;
+pub use module_to_source::*;
@@ -1,5 +1,7 @@
 // https://github.com/LucentFlux/naga-to-tokenstream/blob/main/src/lib.rs#L26
-pub fn module_to_source(module: &naga::Module) -> Result<String, naga::back::wgsl::Error> {
+pub fn module_to_source(
+  module: &naga::Module,
+) -> Result<String, naga::back::wgsl::Error> {
   // Clone since we sometimes modify things
   #[allow(unused_mut)]
   let mut module = module.clone();
@@ -18,7 +20,7 @@ pub fn module_to_source(module: &naga::Module) -> Result<String, naga::back::wgs
   .validate(&module);
 
   // Write to wgsl
-  let info = info.unwrap();
+  let info = info.map_err(|e| naga::back::wgsl::Error::Custom(e.to_string()))?;
   let src = naga::back::wgsl::write_string(
     &module,
     &info,
@@ -31,3 +33,68 @@ pub fn module_to_source(module: &naga::Module) -> Result<String, naga::back::wgs
 
   return Ok(src);
 }
+
+pub fn module_to_spirv(
+  module: &naga::Module,
+  info: &naga::valid::ModuleInfo,
+) -> Result<Vec<u32>, naga::back::spv::Error> {
+  // wgpu translates the SPIR-V again and applies its own coordinate space adjustments.
+  let options = naga::back::spv::Options {
+    flags: naga::back::spv::WriterFlags::empty(),
+    ..Default::default()
+  };
+  naga::back::spv::write_vec(module, info, &options, None)
+}
+
+/// The naga version of the modules serialized by [module_to_naga_ir].
+/// This must match the version of the naga dependency, which is checked by a test.
+pub const NAGA_IR_VERSION: &str = "0.19";
+
+/// Serializes the module with bincode for `ShaderSource::Naga` in the generated code.
+/// The serialized module can only be read by the same naga version.
+pub fn module_to_naga_ir(module: &naga::Module) -> Result<Vec<u8>, bincode::Error> {
+  bincode::serialize(module)
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+
+  use super::*;
+
+  #[test]
+  fn naga_ir_roundtrip() {
+    let source = indoc! {r#"
+        struct Uniforms {
+            color: vec4<f32>,
+        };
+
+        @group(0) @binding(0) var<uniform> uniforms: Uniforms;
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {
+            return uniforms.color;
+        }
+    "#};
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+
+    let bytes = module_to_naga_ir(&module).unwrap();
+    let actual: naga::Module = bincode::deserialize(&bytes).unwrap();
+
+    assert_eq!(module_to_source(&module).unwrap(), module_to_source(&actual).unwrap());
+  }
+
+  #[test]
+  fn naga_ir_version_matches_dependency() {
+    let manifest = include_str!("../../Cargo.toml");
+    let naga = manifest
+      .lines()
+      .find(|line| line.starts_with("naga = "))
+      .unwrap();
+
+    assert!(
+      naga.contains(&format!("version = \"{NAGA_IR_VERSION}.")),
+      "NAGA_IR_VERSION {NAGA_IR_VERSION} doesn't match `{naga}`"
+    );
+  }
+}
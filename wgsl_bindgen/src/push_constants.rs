@@ -0,0 +1,284 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use crate::bevy_util::demangle;
+use crate::bindgroup::shader_stages_tokens;
+use crate::quote_gen::rust_type;
+use crate::wgsl::{global_shader_stages, module_layouter, type_name};
+use crate::{CreateModuleError, WgslBindgenOption};
+
+pub struct PushConstantData<'a> {
+  /// The demangled name of the push constant variable.
+  pub name: String,
+  pub binding_type: &'a naga::Type,
+  /// The size in bytes of the push constant type.
+  pub size: u32,
+  /// The stages of the entry points that actually use the push constants.
+  pub stages: wgpu::ShaderStages,
+}
+
+pub fn get_push_constant_data<'a>(
+  module: &'a naga::Module,
+  info: &naga::valid::ModuleInfo,
+) -> Result<Option<PushConstantData<'a>>, CreateModuleError> {
+  let mut push_constants = module
+    .global_variables
+    .iter()
+    .filter(|(_, g)| g.space == naga::AddressSpace::PushConstant);
+
+  let Some((handle, global)) = push_constants.next() else {
+    return Ok(None);
+  };
+
+  // Entry points can use at most one push constant variable.
+  // A single range starting at 0 avoids overlapping ranges for the pipeline layout.
+  if let Some((_, other)) = push_constants.next() {
+    return Err(CreateModuleError::MultiplePushConstants {
+      name: demangle(other.name.as_deref().unwrap_or_default()).into(),
+      src: None,
+      span: None,
+    });
+  }
+
+  // wgpu rejects push constant ranges without any stages,
+  // so push constants that no entry point uses are skipped.
+  let Some(stages) = global_shader_stages(module, info).get(&handle).copied() else {
+    return Ok(None);
+  };
+
+  let layouter = module_layouter(module)?;
+
+  Ok(Some(PushConstantData {
+    name: demangle(global.name.as_deref().unwrap_or_default()).into(),
+    binding_type: &module.types[global.ty],
+    size: layouter[global.ty].size,
+    stages,
+  }))
+}
+
+pub fn push_constants_items(
+  module: &naga::Module,
+  data: &PushConstantData,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let push_constants_type =
+    rust_type(module, data.binding_type, options).ok_or_else(|| {
+      CreateModuleError::UnsupportedType {
+        name: data.name.clone(),
+        ty: type_name(module, data.binding_type),
+        src: None,
+        span: None,
+      }
+    })?;
+  let stages = shader_stages_tokens(data.stages);
+  let size = Literal::u32_unsuffixed(data.size);
+
+  let (doc, write_bytes, bytes) = if options.serialization_strategy.is_encase() {
+    let doc = quote! {
+        /// Sets the push constants for the stages in [PUSH_CONSTANT_RANGE].
+        ///
+        /// # Panics
+        /// If encase fails to write the push constants, which doesn't happen since
+        /// the buffer grows to fit the value.
+    };
+    let write_bytes = quote! {
+        let mut buffer = encase::StorageBuffer::new(Vec::new());
+        buffer
+            .write(push_constants)
+            .expect("push constants should always fit in a growable buffer");
+        let bytes = buffer.into_inner();
+    };
+    (doc, write_bytes, quote!(&bytes))
+  } else {
+    let doc = quote! {
+        /// Sets the push constants for the stages in [PUSH_CONSTANT_RANGE].
+    };
+    (doc, quote!(), quote!(bytemuck::bytes_of(push_constants)))
+  };
+
+  Ok(quote! {
+      pub const PUSH_CONSTANT_RANGE: wgpu::PushConstantRange = wgpu::PushConstantRange {
+          stages: #stages,
+          range: 0..#size,
+      };
+
+      #doc
+      pub fn set_push_constants(
+          pass: &mut impl _root::pass_ext::SetPushConstants,
+          push_constants: &#push_constants_type,
+      ) {
+          #write_bytes
+          pass.set_push_constants(
+              PUSH_CONSTANT_RANGE.stages,
+              PUSH_CONSTANT_RANGE.range.start,
+              #bytes,
+          );
+      }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+
+  use super::*;
+  use crate::wgsl::module_info;
+  use crate::{assert_tokens_eq, WgslTypeSerializeStrategy};
+
+  #[test]
+  fn push_constant_data_none() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> a: f32;
+
+            @fragment
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    assert!(get_push_constant_data(&module, &module_info(&module))
+      .unwrap()
+      .is_none());
+  }
+
+  #[test]
+  fn push_constant_data_unused() {
+    let source = indoc! {r#"
+            var<push_constant> a: f32;
+
+            @fragment
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    assert!(get_push_constant_data(&module, &module_info(&module))
+      .unwrap()
+      .is_none());
+  }
+
+  #[test]
+  fn push_constant_data_multiple() {
+    let source = indoc! {r#"
+            var<push_constant> a: f32;
+            var<push_constant> b: f32;
+
+            @fragment
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    assert!(matches!(
+      get_push_constant_data(&module, &module_info(&module)),
+      Err(CreateModuleError::MultiplePushConstants { name, .. }) if name == "b"
+    ));
+  }
+
+  #[test]
+  fn push_constants_items_bytemuck() {
+    let source = indoc! {r#"
+            struct PushConstants {
+                color: vec4<f32>,
+                scale: f32,
+            };
+            var<push_constant> constants: PushConstants;
+
+            @vertex
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return vec4(constants.scale);
+            }
+
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> {
+                return constants.color;
+            }
+
+            @compute
+            @workgroup_size(1)
+            fn cs_main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let data = get_push_constant_data(&module, &module_info(&module))
+      .unwrap()
+      .unwrap();
+    let actual = push_constants_items(
+      &module,
+      &data,
+      &WgslBindgenOption {
+        serialization_strategy: WgslTypeSerializeStrategy::Bytemuck,
+        ..Default::default()
+      },
+    )
+    .unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          pub const PUSH_CONSTANT_RANGE: wgpu::PushConstantRange = wgpu::PushConstantRange {
+              stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
+              range: 0..32,
+          };
+          /// Sets the push constants for the stages in [PUSH_CONSTANT_RANGE].
+          pub fn set_push_constants(
+              pass: &mut impl _root::pass_ext::SetPushConstants,
+              push_constants: &PushConstants,
+          ) {
+              pass.set_push_constants(
+                  PUSH_CONSTANT_RANGE.stages,
+                  PUSH_CONSTANT_RANGE.range.start,
+                  bytemuck::bytes_of(push_constants),
+              );
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn push_constants_items_encase() {
+    let source = indoc! {r#"
+            var<push_constant> scale: f32;
+
+            @compute
+            @workgroup_size(1)
+            fn main() {
+                let x = scale;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let data = get_push_constant_data(&module, &module_info(&module))
+      .unwrap()
+      .unwrap();
+    let actual =
+      push_constants_items(&module, &data, &WgslBindgenOption::default()).unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          pub const PUSH_CONSTANT_RANGE: wgpu::PushConstantRange = wgpu::PushConstantRange {
+              stages: wgpu::ShaderStages::COMPUTE,
+              range: 0..4,
+          };
+          /// Sets the push constants for the stages in [PUSH_CONSTANT_RANGE].
+          ///
+          /// # Panics
+          /// If encase fails to write the push constants, which doesn't happen since
+          /// the buffer grows to fit the value.
+          pub fn set_push_constants(
+              pass: &mut impl _root::pass_ext::SetPushConstants,
+              push_constants: &f32,
+          ) {
+              let mut buffer = encase::StorageBuffer::new(Vec::new());
+              buffer
+                  .write(push_constants)
+                  .expect("push constants should always fit in a growable buffer");
+              let bytes = buffer.into_inner();
+              pass.set_push_constants(
+                  PUSH_CONSTANT_RANGE.stages,
+                  PUSH_CONSTANT_RANGE.range.start,
+                  &bytes,
+              );
+          }
+      },
+      actual
+    );
+  }
+}
@@ -7,8 +7,9 @@ use syn::{Ident, Index};
 
 use super::rust_type;
 use crate::{
-  bevy_util::demangle_splitting_mod_path_and_item, WgslTypeSerializeStrategy,
-  WgslBindgenOption,
+  bevy_util::{demangle, demangle_splitting_mod_path_and_item},
+  wgsl::type_name,
+  CreateModuleError, WgslBindgenOption, WgslTypeSerializeStrategy,
 };
 
 #[derive(Clone)]
@@ -39,24 +40,40 @@ struct NagaToRustStructState<'a> {
 
 impl<'a> NagaToRustStructState<'a> {
   fn create_fold(
+    struct_name: &'a str,
     naga_members: &'a [StructMember],
     naga_module: &'a naga::Module,
     options: &'a WgslBindgenOption,
     layout_size: usize,
     is_directly_sharable: bool,
-  ) -> impl FnMut(NagaToRustStructState<'a>, &'a StructMember) -> NagaToRustStructState<'a>
-  {
+  ) -> impl FnMut(
+    NagaToRustStructState<'a>,
+    &'a StructMember,
+  ) -> Result<NagaToRustStructState<'a>, CreateModuleError> {
     let fold = move |mut state: NagaToRustStructState<'a>,
                      naga_member: &'a StructMember|
-          -> NagaToRustStructState<'a> {
-      let name_ident = Ident::new(naga_member.name.as_ref().unwrap(), Span::call_site());
+          -> Result<NagaToRustStructState<'a>, CreateModuleError> {
+      let member_name = naga_member.name.as_ref().unwrap();
+      let name_ident = Ident::new(member_name, Span::call_site());
       let naga_type = &naga_module.types[naga_member.ty];
-
-      let rust_type = rust_type(naga_module, naga_type, &options);
+      let error_name = format!("{}.{member_name}", demangle(struct_name));
+
+      let rust_type = rust_type(naga_module, naga_type, options).ok_or_else(|| {
+        CreateModuleError::UnsupportedType {
+          name: error_name.clone(),
+          ty: type_name(naga_module, naga_type),
+          src: None,
+          span: None,
+        }
+      })?;
       let is_rsa = rust_type.size.is_none();
 
       if is_rsa && state.index != naga_members.len() - 1 {
-        panic!("Only the last field of a struct can be a runtime-sized array");
+        return Err(CreateModuleError::RuntimeSizedArrayNotLast {
+          name: error_name,
+          src: None,
+          span: None,
+        });
       }
 
       // check if we need padding bytes
@@ -106,7 +123,7 @@ impl<'a> NagaToRustStructState<'a> {
 
       state.index += 1;
       state.members.push(entry);
-      state
+      Ok(state)
     };
 
     fold
@@ -141,23 +158,25 @@ impl<'a> RustStructMemberEntry<'a> {
   }
 
   fn from_naga(
+    struct_name: &'a str,
     naga_members: &'a [naga::StructMember],
     naga_module: &'a naga::Module,
     options: &'a WgslBindgenOption,
     layout_size: usize,
     is_directly_sharable: bool,
-  ) -> Vec<Self> {
-    let state = naga_members.iter().fold(
+  ) -> Result<Vec<Self>, CreateModuleError> {
+    let state = naga_members.iter().try_fold(
       NagaToRustStructState::default(),
       NagaToRustStructState::create_fold(
+        struct_name,
         naga_members,
         naga_module,
         options,
         layout_size,
         is_directly_sharable,
       ),
-    );
-    state.members
+    )?;
+    Ok(state.members)
   }
 }
 
@@ -462,12 +481,11 @@ impl<'a> RustStructBuilder<'a> {
       quote!()
     };
 
-    let ignore_case_tokens = 
-      if self.name.chars().next().unwrap().is_lowercase() {
-        quote!(#[allow(non_camel_case_types)])
-      } else {
-        quote!()
-      };
+    let ignore_case_tokens = if self.name.chars().next().unwrap().is_lowercase() {
+      quote!(#[allow(non_camel_case_types)])
+    } else {
+      quote!()
+    };
 
     let fields = self.build_fields();
     let struct_new_fn = self.build_fn_new();
@@ -508,19 +526,19 @@ impl<'a> RustStructBuilder<'a> {
     is_directly_sharable: bool,
     is_host_sharable: bool,
     has_rts_array: bool,
-  ) -> Self {
+  ) -> Result<Self, CreateModuleError> {
+    let name = naga_type.name.as_ref().unwrap();
     let members = RustStructMemberEntry::from_naga(
+      name,
       naga_members,
       naga_module,
       options,
       layout.size as usize,
       is_directly_sharable,
-    );
-
-    let name = naga_type.name.as_ref().unwrap().into();
+    )?;
 
     let mut builder = RustStructBuilder {
-      name,
+      name: name.into(),
       members,
       is_host_sharable,
       naga_module,
@@ -532,6 +550,6 @@ impl<'a> RustStructBuilder<'a> {
     // we don't need full qualification here
     let (_, demangled_name) = demangle_splitting_mod_path_and_item(&builder.name);
     builder.name = demangled_name.into();
-    builder
+    Ok(builder)
   }
 }
@@ -4,9 +4,10 @@ use quote::{quote, ToTokens};
 use strum::IntoEnumIterator;
 use syn::Index;
 
+use crate::wgsl::module_layouter;
 use crate::{
-  quote_gen::demangle_and_qualify, WgslTypeSerializeStrategy, WgslType, WgslTypeMapExt,
-  WgslBindgenOption,
+  quote_gen::demangle_and_qualify, WgslBindgenOption, WgslType, WgslTypeMapExt,
+  WgslTypeSerializeStrategy,
 };
 
 #[derive(Debug, Clone)]
@@ -41,9 +42,7 @@ pub(crate) fn add_prelude_types_assertions(options: &WgslBindgenOption) -> Token
 
   let assertions = WgslType::iter()
     .filter_map(|built_in| {
-      let ty = options
-        .wgsl_type_map
-        .get_rust_type_info(built_in)?;
+      let ty = options.wgsl_type_map.get_rust_type_info(built_in)?;
       let size_after_alignment = ty.size_after_alignment()?;
 
       let alignment = Index::from(ty.alignment_value());
@@ -79,9 +78,9 @@ pub(crate) const fn RustTypeInfo(
 pub(crate) fn rust_scalar_type(
   scalar: &naga::Scalar,
   alignment: naga::proc::Alignment,
-) -> RustTypeInfo {
+) -> Option<RustTypeInfo> {
   // TODO: Support other widths?
-  match (scalar.kind, scalar.width) {
+  let rust_type = match (scalar.kind, scalar.width) {
     (ScalarKind::Sint, 1) => RustTypeInfo(quote!(i8), 1, alignment),
     (ScalarKind::Uint, 1) => RustTypeInfo(quote!(u8), 1, alignment),
     (ScalarKind::Sint, 2) => RustTypeInfo(quote!(i16), 2, alignment),
@@ -92,8 +91,9 @@ pub(crate) fn rust_scalar_type(
     (ScalarKind::Float, 8) => RustTypeInfo(quote!(f64), 8, alignment),
     // TODO: Do booleans have a width?
     (ScalarKind::Bool, 1) => RustTypeInfo(quote!(bool), 1, alignment),
-    _ => unreachable!(),
-  }
+    _ => return None,
+  };
+  Some(rust_type)
 }
 
 /// Get the array stride and padding in bytes
@@ -214,29 +214,28 @@ fn map_naga_mat_type(
   built_in_ty
 }
 
+/// Returns `None` for types without a Rust equivalent like textures or pointers.
 pub(crate) fn rust_type(
   module: &naga::Module,
   ty: &naga::Type,
   options: &WgslBindgenOption,
-) -> RustTypeInfo {
-  let t_handle = module.types.get(ty).unwrap();
-  let mut layouter = naga::proc::Layouter::default();
-  layouter.update(module.to_ctx()).unwrap();
+) -> Option<RustTypeInfo> {
+  let t_handle = module.types.get(ty)?;
+  // Layout errors are reported as unsupported types by the callers.
+  let layouter = module_layouter(module).ok()?;
 
   let type_layout = layouter[t_handle];
 
   let alignment = type_layout.alignment;
 
   let create_rust_type = |ty: WgslType| -> Option<RustTypeInfo> {
-    let info = options
-      .wgsl_type_map
-      .get_rust_type_info(ty)?;
+    let info = options.wgsl_type_map.get_rust_type_info(ty)?;
     assert!(alignment == info.alignment);
     Some(info)
   };
 
-  match &ty.inner {
-    naga::TypeInner::Scalar(scalar) => rust_scalar_type(scalar, alignment),
+  let rust_type = match &ty.inner {
+    naga::TypeInner::Scalar(scalar) => rust_scalar_type(scalar, alignment)?,
     naga::TypeInner::Vector { size, scalar } => {
       let rust_type =
         map_naga_vec_type(*size, *scalar, alignment).and_then(create_rust_type);
@@ -246,7 +245,7 @@ pub(crate) fn rust_type(
         // TODO: Add more built-in types to WgslTypes and handle it there instead
         // here the padding bytes are also inserted
         let (stride, _) = get_stride_and_padding(alignment, *size, scalar.width, options);
-        let inner_type = rust_scalar_type(scalar, alignment).tokens;
+        let inner_type = rust_scalar_type(scalar, alignment)?.tokens;
         let len = Index::from((stride / scalar.width as u32) as usize);
         RustTypeInfo(quote!([#inner_type; #len]), stride as usize, alignment)
       }
@@ -264,7 +263,7 @@ pub(crate) fn rust_type(
       } else {
         // TODO: Add more built types to WgslTypes and handle it there instead
         // here the padding bytes are also inserted
-        let inner_type = rust_scalar_type(scalar, alignment).tokens;
+        let inner_type = rust_scalar_type(scalar, alignment)?.tokens;
         let (col_array_stride, _) =
           get_stride_and_padding(alignment, *rows, scalar.width, options);
         let size = col_array_stride * (*columns as u32);
@@ -274,17 +273,13 @@ pub(crate) fn rust_type(
         RustTypeInfo(quote!([[#inner_type; #rows]; #cols]), size as usize, alignment)
       }
     }
-    naga::TypeInner::Image { .. } => todo!(),
-    naga::TypeInner::Sampler { .. } => todo!(),
-    naga::TypeInner::Atomic(scalar) => rust_scalar_type(scalar, alignment),
-    naga::TypeInner::Pointer { base: _, space: _ } => todo!(),
-    naga::TypeInner::ValuePointer { .. } => todo!(),
+    naga::TypeInner::Atomic(scalar) => rust_scalar_type(scalar, alignment)?,
     naga::TypeInner::Array {
       base,
       size: naga::ArraySize::Constant(size),
       stride,
     } => {
-      let inner_ty = rust_type(module, &module.types[*base], options);
+      let inner_ty = rust_type(module, &module.types[*base], options)?;
       let count = Index::from(size.get() as usize);
 
       RustTypeInfo(quote!([#inner_ty; #count]), *stride as usize, alignment)
@@ -295,7 +290,7 @@ pub(crate) fn rust_type(
       ..
     } => {
       // panic!("Runtime-sized arrays can only be used in variable declarations or as the last field of a struct.");
-      let element_type = rust_type(module, &module.types[*base], &options);
+      let element_type = rust_type(module, &module.types[*base], options)?;
       let member_type = match options.serialization_strategy {
         WgslTypeSerializeStrategy::Encase => {
           quote!(Vec<#element_type>)
@@ -318,8 +313,33 @@ pub(crate) fn rust_type(
       let name = demangle_and_qualify(ty.name.as_ref().unwrap());
       RustTypeInfo(name, type_layout.size as usize, alignment)
     }
-    naga::TypeInner::BindingArray { base: _, size: _ } => todo!(),
-    naga::TypeInner::AccelerationStructure => todo!(),
-    naga::TypeInner::RayQuery => todo!(),
-  }
+    naga::TypeInner::BindingArray {
+      base,
+      size: naga::ArraySize::Constant(size),
+    } => {
+      let inner_ty = rust_type(module, &module.types[*base], options)?;
+      let count = Index::from(size.get() as usize);
+
+      RustTypeInfo(quote!([#inner_ty; #count]), type_layout.size as usize, alignment)
+    }
+    naga::TypeInner::BindingArray {
+      base,
+      size: naga::ArraySize::Dynamic,
+    } => {
+      let element_type = rust_type(module, &module.types[*base], options)?;
+      RustTypeInfo {
+        tokens: quote!([#element_type]),
+        size: None,
+        alignment,
+      }
+    }
+    // Handle types and pointers have no host shareable representation.
+    naga::TypeInner::Image { .. }
+    | naga::TypeInner::Sampler { .. }
+    | naga::TypeInner::Pointer { .. }
+    | naga::TypeInner::ValuePointer { .. }
+    | naga::TypeInner::AccelerationStructure
+    | naga::TypeInner::RayQuery => return None,
+  };
+  Some(rust_type)
 }
@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use naga_oil::compose::ShaderDefValue;
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::quote;
+use regex::Regex;
+use syn::Ident;
+
+use crate::bevy_util::SourceWithFullDependenciesResult;
+use crate::FxIndexMap;
+
+/// The type of a shader def based on how the preprocessor directives use it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ShaderDefKind {
+  /// Only checked with `#ifdef` or `#ifndef`.
+  Defined,
+  /// Compared with `true` or `false` in an `#if`.
+  Bool,
+  /// Compared with an integer in an `#if`.
+  Int,
+  /// Compared with `true` or `false` in an `#if` and also checked with `#ifdef` or `#ifndef`.
+  OptionalBool,
+  /// Compared with an integer in an `#if` and also checked with `#ifdef` or `#ifndef`.
+  OptionalInt,
+}
+
+fn conditional_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+
+  // Based on the directives supported by the naga_oil preprocessor.
+  MEM.get_or_init(|| {
+    Regex::new(
+      r"(?m)^\s*#\s*(?:else\s+)?\s*(ifdef|ifndef|if)\s+(\w+)(?:\s*[=!<>]+\s*([-\w]+))?",
+    )
+    .unwrap()
+  })
+}
+
+fn define_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| Regex::new(r"(?m)^\s*#\s*define\s+(\w+)").unwrap())
+}
+
+/// Finds the shader defs used by the entry and its dependencies in order of appearance.
+/// Shader defs defined by the entry with `#define` are not included.
+pub fn get_shader_defs(
+  entry: &SourceWithFullDependenciesResult,
+) -> FxIndexMap<String, ShaderDefKind> {
+  // Whether each def is checked with `#ifdef` or `#ifndef` and the type of its comparisons.
+  let mut uses: FxIndexMap<String, (bool, Option<ShaderDefKind>)> = FxIndexMap::default();
+
+  let sources =
+    std::iter::once(entry.source_file).chain(entry.full_dependencies.iter().copied());
+  for source in sources {
+    for cap in conditional_regex().captures_iter(&source.content) {
+      let (defined, value) = uses.entry(cap[2].to_owned()).or_default();
+      match (&cap[1], cap.get(3).map(|v| v.as_str())) {
+        ("if", Some("true" | "false")) => {
+          *value = (*value).max(Some(ShaderDefKind::Bool))
+        }
+        ("if", _) => *value = Some(ShaderDefKind::Int),
+        _ => *defined = true,
+      }
+    }
+  }
+
+  for cap in define_regex().captures_iter(&entry.source_file.content) {
+    uses.shift_remove(&cap[1]);
+  }
+
+  uses
+    .into_iter()
+    .map(|(name, uses)| {
+      // Comparisons need a value, and checking if the def is defined only works
+      // if the value can be left out.
+      let kind = match uses {
+        (_, None) => ShaderDefKind::Defined,
+        (false, Some(kind)) => kind,
+        (true, Some(ShaderDefKind::Bool)) => ShaderDefKind::OptionalBool,
+        (true, Some(_)) => ShaderDefKind::OptionalInt,
+      };
+      (name, kind)
+    })
+    .collect()
+}
+
+/// The shader defs used for composing the module while generating the bindings.
+/// This matches the `ShaderDefs::default()` of the generated code.
+pub fn default_shader_defs(
+  shader_defs: &FxIndexMap<String, ShaderDefKind>,
+) -> HashMap<String, ShaderDefValue> {
+  shader_defs
+    .iter()
+    .filter_map(|(name, kind)| match kind {
+      ShaderDefKind::Defined => None,
+      ShaderDefKind::Bool | ShaderDefKind::OptionalBool => {
+        Some((name.clone(), ShaderDefValue::Bool(false)))
+      }
+      ShaderDefKind::Int | ShaderDefKind::OptionalInt => {
+        Some((name.clone(), ShaderDefValue::Int(0)))
+      }
+    })
+    .collect()
+}
+
+pub fn shader_def_value(value: &ShaderDefValue) -> TokenStream {
+  match value {
+    ShaderDefValue::Bool(value) => {
+      quote!(naga_oil::compose::ShaderDefValue::Bool(#value))
+    }
+    ShaderDefValue::Int(value) => {
+      let value = Literal::i32_unsuffixed(*value);
+      quote!(naga_oil::compose::ShaderDefValue::Int(#value))
+    }
+    ShaderDefValue::UInt(value) => {
+      let value = Literal::u32_unsuffixed(*value);
+      quote!(naga_oil::compose::ShaderDefValue::UInt(#value))
+    }
+  }
+}
+
+pub fn shader_defs_items(shader_defs: &FxIndexMap<String, ShaderDefKind>) -> TokenStream {
+  let field_name = |name: &str| Ident::new(&name.to_lowercase(), Span::call_site());
+
+  let fields = shader_defs.iter().map(|(name, kind)| {
+    let field = field_name(name);
+    match kind {
+      ShaderDefKind::Defined | ShaderDefKind::Bool => quote!(pub #field: bool),
+      ShaderDefKind::Int => quote!(pub #field: i32),
+      ShaderDefKind::OptionalBool => quote!(pub #field: Option<bool>),
+      ShaderDefKind::OptionalInt => quote!(pub #field: Option<i32>),
+    }
+  });
+
+  let inserts = shader_defs.iter().map(|(name, kind)| {
+    let field = field_name(name);
+    match kind {
+      // Defining the def with any value enables the `#ifdef` blocks.
+      ShaderDefKind::Defined => quote! {
+          if self.#field {
+              shader_defs.insert(#name.to_owned(), naga_oil::compose::ShaderDefValue::Bool(true));
+          }
+      },
+      ShaderDefKind::Bool => quote! {
+          shader_defs.insert(#name.to_owned(), naga_oil::compose::ShaderDefValue::Bool(self.#field));
+      },
+      ShaderDefKind::Int => quote! {
+          shader_defs.insert(#name.to_owned(), naga_oil::compose::ShaderDefValue::Int(self.#field));
+      },
+      // `None` leaves the def undefined for the `#ifdef` blocks.
+      ShaderDefKind::OptionalBool => quote! {
+          if let Some(value) = self.#field {
+              shader_defs.insert(#name.to_owned(), naga_oil::compose::ShaderDefValue::Bool(value));
+          }
+      },
+      ShaderDefKind::OptionalInt => quote! {
+          if let Some(value) = self.#field {
+              shader_defs.insert(#name.to_owned(), naga_oil::compose::ShaderDefValue::Int(value));
+          }
+      },
+    }
+  });
+
+  let has_optional = shader_defs
+    .values()
+    .any(|kind| matches!(kind, ShaderDefKind::OptionalBool | ShaderDefKind::OptionalInt));
+
+  // naga_oil needs a value for every def compared in an `#if`, even in inactive blocks,
+  // so the optional defs default to a value like the defs used for the bindings.
+  let (derive_default, default_impl) = if has_optional {
+    let defaults = shader_defs.iter().map(|(name, kind)| {
+      let field = field_name(name);
+      match kind {
+        ShaderDefKind::Defined | ShaderDefKind::Bool => quote!(#field: false),
+        ShaderDefKind::Int => quote!(#field: 0),
+        ShaderDefKind::OptionalBool => quote!(#field: Some(false)),
+        ShaderDefKind::OptionalInt => quote!(#field: Some(0)),
+      }
+    });
+    let default_impl = quote! {
+        impl Default for ShaderDefs {
+            fn default() -> Self {
+                Self {
+                    #(#defaults),*
+                }
+            }
+        }
+    };
+    (quote!(), default_impl)
+  } else {
+    (quote!(Default,), quote!())
+  };
+
+  quote! {
+      #[derive(Debug, #derive_default Clone, Copy, PartialEq, Eq)]
+      pub struct ShaderDefs {
+          #(#fields),*
+      }
+
+      #default_impl
+
+      impl ShaderDefs {
+          pub fn to_shader_defs(
+              &self,
+          ) -> std::collections::HashMap<String, naga_oil::compose::ShaderDefValue> {
+              let mut shader_defs = std::collections::HashMap::new();
+              #(#inserts)*
+              shader_defs
+          }
+      }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+  use smallvec::smallvec;
+
+  use super::*;
+  use crate::assert_tokens_eq;
+  use crate::bevy_util::source_file::SourceFile;
+  use crate::{SourceFilePath, SourceModuleName};
+
+  #[test]
+  fn get_shader_defs_entry_and_dependencies() {
+    let entry = SourceFile::create(
+      SourceFilePath::new("entry.wgsl"),
+      None,
+      indoc! {r#"
+          #import lights
+          #define LOCAL
+
+          @fragment
+          fn main() -> @location(0) vec4<f32> {
+          #ifdef SHADOWS
+              return lights::shadowed();
+          #else ifndef LOCAL
+              return vec4(0.0);
+          #else if MSAA_SAMPLES > 1
+              return vec4(1.0);
+          #endif
+          }
+      "#}
+      .to_owned(),
+    );
+    let dependency = SourceFile::create(
+      SourceFilePath::new("lights.wgsl"),
+      Some(SourceModuleName::new("lights")),
+      indoc! {r#"
+          fn shadowed() -> vec4<f32> {
+          #if SHADOWS == true
+              return vec4(0.5);
+          #endif
+          #ifdef HDR
+              return vec4(2.0);
+          #endif
+          }
+      "#}
+      .to_owned(),
+    );
+
+    let actual = get_shader_defs(&SourceWithFullDependenciesResult {
+      source_file: &entry,
+      full_dependencies: smallvec![&dependency],
+    });
+
+    assert_eq!(
+      vec![
+        ("SHADOWS", ShaderDefKind::OptionalBool),
+        ("MSAA_SAMPLES", ShaderDefKind::Int),
+        ("HDR", ShaderDefKind::Defined),
+      ],
+      actual
+        .iter()
+        .map(|(name, kind)| (name.as_str(), *kind))
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn write_shader_defs_items() {
+    let shader_defs = [
+      ("SHADOWS".to_owned(), ShaderDefKind::Defined),
+      ("HDR".to_owned(), ShaderDefKind::Bool),
+      ("MSAA_SAMPLES".to_owned(), ShaderDefKind::Int),
+    ]
+    .into_iter()
+    .collect();
+
+    let actual = shader_defs_items(&shader_defs);
+
+    assert_tokens_eq!(
+      quote! {
+          #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+          pub struct ShaderDefs {
+              pub shadows: bool,
+              pub hdr: bool,
+              pub msaa_samples: i32,
+          }
+          impl ShaderDefs {
+              pub fn to_shader_defs(
+                  &self,
+              ) -> std::collections::HashMap<String, naga_oil::compose::ShaderDefValue> {
+                  let mut shader_defs = std::collections::HashMap::new();
+                  if self.shadows {
+                      shader_defs.insert("SHADOWS".to_owned(), naga_oil::compose::ShaderDefValue::Bool(true));
+                  }
+                  shader_defs.insert("HDR".to_owned(), naga_oil::compose::ShaderDefValue::Bool(self.hdr));
+                  shader_defs.insert("MSAA_SAMPLES".to_owned(), naga_oil::compose::ShaderDefValue::Int(self.msaa_samples));
+                  shader_defs
+              }
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn write_shader_defs_items_optional() {
+    let shader_defs = [
+      ("SHADOWS".to_owned(), ShaderDefKind::OptionalBool),
+      ("MSAA_SAMPLES".to_owned(), ShaderDefKind::OptionalInt),
+      ("HDR".to_owned(), ShaderDefKind::Defined),
+    ]
+    .into_iter()
+    .collect();
+
+    let actual = shader_defs_items(&shader_defs);
+
+    assert_tokens_eq!(
+      quote! {
+          #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+          pub struct ShaderDefs {
+              pub shadows: Option<bool>,
+              pub msaa_samples: Option<i32>,
+              pub hdr: bool,
+          }
+          impl Default for ShaderDefs {
+              fn default() -> Self {
+                  Self {
+                      shadows: Some(false),
+                      msaa_samples: Some(0),
+                      hdr: false
+                  }
+              }
+          }
+          impl ShaderDefs {
+              pub fn to_shader_defs(
+                  &self,
+              ) -> std::collections::HashMap<String, naga_oil::compose::ShaderDefValue> {
+                  let mut shader_defs = std::collections::HashMap::new();
+                  if let Some(value) = self.shadows {
+                      shader_defs.insert("SHADOWS".to_owned(), naga_oil::compose::ShaderDefValue::Bool(value));
+                  }
+                  if let Some(value) = self.msaa_samples {
+                      shader_defs.insert("MSAA_SAMPLES".to_owned(), naga_oil::compose::ShaderDefValue::Int(value));
+                  }
+                  if self.hdr {
+                      shader_defs.insert("HDR".to_owned(), naga_oil::compose::ShaderDefValue::Bool(true));
+                  }
+                  shader_defs
+              }
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn compose_ifdef_branch_with_and_without_def() {
+    let compose = |shader_defs: &[(&str, ShaderDefValue)]| {
+      let mut composer = naga_oil::compose::Composer::default();
+      composer
+        .add_composable_module(naga_oil::compose::ComposableModuleDescriptor {
+          source: indoc! {r#"
+              #define_import_path lights
+
+              fn shadowed() -> f32 {
+              #if SHADOWS == true
+                  return 0.5;
+              #else
+                  return 1.0;
+              #endif
+              }
+          "#},
+          file_path: "lights.wgsl",
+          ..Default::default()
+        })
+        .unwrap();
+      composer.make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+        source: indoc! {r#"
+              #import lights
+
+              fn light() -> f32 {
+              #ifdef SHADOWS
+                  return lights::shadowed();
+              #else
+                  return 1.0;
+              #endif
+              }
+
+              @compute @workgroup_size(1)
+              fn main() {
+                  let value = light();
+              #ifdef HDR
+                  let hdr = value * 2.0;
+              #endif
+              }
+          "#},
+        file_path: "entry.wgsl",
+        shader_defs: shader_defs
+          .iter()
+          .map(|(name, value)| (name.to_string(), *value))
+          .collect(),
+        ..Default::default()
+      })
+    };
+    let calls_shadowed = |module: &naga::Module| {
+      module.functions.iter().any(|(_, f)| {
+        f.name
+          .as_deref()
+          .is_some_and(|name| name.starts_with("shadowed"))
+      })
+    };
+    let has_hdr = |module: &naga::Module| {
+      module.entry_points[0]
+        .function
+        .named_expressions
+        .values()
+        .any(|name| name == "hdr")
+    };
+
+    // The optional def is defined for both values, so the `#ifdef` branch is included.
+    for value in [true, false] {
+      let module = compose(&[("SHADOWS", ShaderDefValue::Bool(value))]).unwrap();
+      assert!(calls_shadowed(&module));
+    }
+
+    // Leaving out the def excludes the `#ifdef` branch, but naga_oil still needs a
+    // value for the comparison in the imported module.
+    let error = compose(&[]).unwrap_err();
+    assert!(matches!(
+      error.inner,
+      naga_oil::compose::ComposerErrorInner::UnknownShaderDef { ref shader_def_name, .. }
+        if shader_def_name == "SHADOWS"
+    ));
+
+    // Defs that are only checked with `#ifdef` are left out when disabled.
+    let module = compose(&[("SHADOWS", ShaderDefValue::Bool(false))]).unwrap();
+    assert!(!has_hdr(&module));
+    let module = compose(&[
+      ("SHADOWS", ShaderDefValue::Bool(false)),
+      ("HDR", ShaderDefValue::Bool(true)),
+    ])
+    .unwrap();
+    assert!(has_hdr(&module));
+  }
+}
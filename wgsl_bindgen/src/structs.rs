@@ -5,16 +5,16 @@ use proc_macro2::TokenStream;
 
 use crate::{
   quote_gen::{RustSourceItem, RustStructBuilder},
-  WgslBindgenOption, WgslTypeSerializeStrategy,
+  wgsl::module_layouter,
+  CreateModuleError, WgslBindgenOption, WgslTypeSerializeStrategy,
 };
 
 pub fn structs_items(
   module: &naga::Module,
   options: &WgslBindgenOption,
-) -> Vec<RustSourceItem> {
+) -> Result<Vec<RustSourceItem>, CreateModuleError> {
   // Initialize the layout calculator provided by naga.
-  let mut layouter = naga::proc::Layouter::default();
-  layouter.update(module.to_ctx()).unwrap();
+  let layouter = module_layouter(module)?;
 
   let mut global_variable_types = HashSet::new();
   for g in module.global_variables.iter() {
@@ -53,7 +53,9 @@ pub fn structs_items(
           &global_variable_types,
         );
 
-        Some(RustSourceItem::from_mangled(t.name.as_ref().unwrap(), rust_struct))
+        Some(rust_struct.map(|rust_struct| {
+          RustSourceItem::from_mangled(t.name.as_ref().unwrap(), rust_struct)
+        }))
       } else {
         None
       }
@@ -62,11 +64,12 @@ pub fn structs_items(
 }
 
 #[allow(unused)]
-pub fn structs(module: &naga::Module, options: &WgslBindgenOption) -> Vec<TokenStream> {
-  structs_items(module, options)
-    .into_iter()
-    .map(|s| s.item)
-    .collect()
+pub fn structs(
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Result<Vec<TokenStream>, CreateModuleError> {
+  let items = structs_items(module, options)?;
+  Ok(items.into_iter().map(|s| s.item).collect())
 }
 
 fn rust_struct(
@@ -77,7 +80,7 @@ fn rust_struct(
   naga_module: &naga::Module,
   options: &WgslBindgenOption,
   global_variable_types: &HashSet<Handle<Type>>,
-) -> TokenStream {
+) -> Result<TokenStream, CreateModuleError> {
   let layout = layouter[t_handle];
 
   // Assume types used in global variables are host shareable and require validation.
@@ -102,8 +105,8 @@ fn rust_struct(
     is_directly_sharable,
     is_host_sharable,
     has_rts_array,
-  );
-  builder.build()
+  )?;
+  Ok(builder.build())
 }
 
 fn add_types_recursive(
@@ -234,7 +237,7 @@ mod tests {
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
 
-    let structs = structs(&module, &WgslBindgenOption::default());
+    let structs = structs(&module, &WgslBindgenOption::default()).unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -452,7 +455,8 @@ mod tests {
         wgsl_type_map: GlamWgslTypeMap.build(WgslTypeSerializeStrategy::Encase),
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -630,7 +634,8 @@ mod tests {
         wgsl_type_map: NalgebraWgslTypeMap.build(WgslTypeSerializeStrategy::Encase),
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -786,7 +791,8 @@ mod tests {
         wgsl_type_map: WgslRustTypeMap.build(WgslTypeSerializeStrategy::Encase),
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -851,7 +857,8 @@ mod tests {
         wgsl_type_map: WgslRustTypeMap.build(WgslTypeSerializeStrategy::Encase),
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -934,7 +941,8 @@ mod tests {
         wgsl_type_map: WgslRustTypeMap.build(WgslTypeSerializeStrategy::Bytemuck),
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -985,7 +993,8 @@ mod tests {
         wgsl_type_map: WgslRustTypeMap.build(WgslTypeSerializeStrategy::Bytemuck),
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -1049,7 +1058,8 @@ mod tests {
         wgsl_type_map: WgslRustTypeMap.build(WgslTypeSerializeStrategy::Bytemuck),
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -1172,7 +1182,8 @@ mod tests {
         wgsl_type_map: NalgebraWgslTypeMap.build(WgslTypeSerializeStrategy::Encase),
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -1216,7 +1227,8 @@ mod tests {
         serialization_strategy: WgslTypeSerializeStrategy::Encase,
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -1247,7 +1259,8 @@ mod tests {
         serialization_strategy: WgslTypeSerializeStrategy::Bytemuck,
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
 
     let actual = quote!(#(#structs)*);
 
@@ -1278,7 +1291,6 @@ mod tests {
   }
 
   #[test]
-  #[should_panic]
   fn write_runtime_sized_array_not_last_field() {
     let source = indoc! {r#"
             struct RtsStruct {
@@ -1292,13 +1304,17 @@ mod tests {
         "#};
     let module = naga::front::wgsl::parse_str(source).unwrap();
 
-    let _structs = structs(
+    let result = structs(
       &module,
       &WgslBindgenOption {
         serialization_strategy: WgslTypeSerializeStrategy::Encase,
         ..Default::default()
       },
     );
+    assert!(matches!(
+      result,
+      Err(CreateModuleError::RuntimeSizedArrayNotLast { name, .. }) if name == "RtsStruct.the_array"
+    ));
   }
 
   #[test]
@@ -1320,7 +1336,8 @@ mod tests {
         serialization_strategy: WgslTypeSerializeStrategy::Bytemuck,
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -1367,7 +1384,8 @@ mod tests {
         wgsl_type_map: GlamWgslTypeMap.build(WgslTypeSerializeStrategy::Bytemuck),
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -1416,7 +1434,8 @@ mod tests {
         wgsl_type_map: WgslRustTypeMap.build(WgslTypeSerializeStrategy::Bytemuck),
         ..Default::default()
       },
-    );
+    )
+    .unwrap();
     let actual = quote!(#(#structs)*);
 
     assert_tokens_eq!(
@@ -1,46 +1,170 @@
-use naga::StructMember;
+use naga::{FastHashMap, FastHashSet, GlobalVariable, Handle, StructMember};
 use proc_macro2::TokenStream;
 use quote::quote;
 
-pub fn shader_stages(module: &naga::Module) -> wgpu::ShaderStages {
-  module
-    .entry_points
+use crate::bevy_util::demangle;
+use crate::CreateModuleError;
+
+fn shader_stage(stage: naga::ShaderStage) -> wgpu::ShaderStages {
+  match stage {
+    naga::ShaderStage::Vertex => wgpu::ShaderStages::VERTEX,
+    naga::ShaderStage::Fragment => wgpu::ShaderStages::FRAGMENT,
+    naga::ShaderStage::Compute => wgpu::ShaderStages::COMPUTE,
+  }
+}
+
+/// Collects the stages of the entry points that statically use each global variable.
+/// Globals that aren't used by any entry point are not included.
+pub fn global_shader_stages(
+  module: &naga::Module,
+  info: &naga::valid::ModuleInfo,
+) -> FastHashMap<Handle<GlobalVariable>, wgpu::ShaderStages> {
+  let mut stages = FastHashMap::default();
+  for (index, entry) in module.entry_points.iter().enumerate() {
+    let entry_info = info.get_entry_point(index);
+    for (handle, _) in module.global_variables.iter() {
+      if !entry_info[handle].is_empty() {
+        *stages.entry(handle).or_insert(wgpu::ShaderStages::NONE) |=
+          shader_stage(entry.stage);
+      }
+    }
+  }
+  stages
+}
+
+/// Collects the sampled textures and samplers that can't be bound as filtering.
+/// Float textures that are used but never sampled, like textures only accessed with
+/// `textureLoad`, are non-filterable. Integer textures are never filterable.
+/// Non-comparison samplers are non-filtering if every texture they sample is non-filterable.
+pub fn non_filtering_globals(
+  module: &naga::Module,
+  info: &naga::valid::ModuleInfo,
+) -> FastHashSet<Handle<GlobalVariable>> {
+  let mut used = FastHashSet::default();
+  let mut sampling_pairs = Vec::new();
+  for index in 0..module.entry_points.len() {
+    let entry_info = info.get_entry_point(index);
+    for (handle, _) in module.global_variables.iter() {
+      if !entry_info[handle].is_empty() {
+        used.insert(handle);
+      }
+    }
+    sampling_pairs.extend(entry_info.sampling_set.iter().map(|k| (k.image, k.sampler)));
+  }
+
+  // Binding arrays are filterable based on their element type.
+  let element_type = |global: &GlobalVariable| match module.types[global.ty].inner {
+    naga::TypeInner::BindingArray { base, .. } => &module.types[base].inner,
+    ref inner => inner,
+  };
+
+  let mut non_filtering: FastHashSet<_> = module
+    .global_variables
+    .iter()
+    .filter(|(handle, global)| match *element_type(global) {
+      naga::TypeInner::Image {
+        class: naga::ImageClass::Sampled { kind, .. },
+        ..
+      } => match kind {
+        naga::ScalarKind::Float => {
+          used.contains(handle)
+            && !sampling_pairs.iter().any(|(image, _)| image == handle)
+        }
+        _ => true,
+      },
+      _ => false,
+    })
+    .map(|(handle, _)| handle)
+    .collect();
+
+  let samplers: Vec<_> = module
+    .global_variables
     .iter()
-    .map(|entry| match entry.stage {
-      naga::ShaderStage::Vertex => wgpu::ShaderStages::VERTEX,
-      naga::ShaderStage::Fragment => wgpu::ShaderStages::FRAGMENT,
-      naga::ShaderStage::Compute => wgpu::ShaderStages::COMPUTE,
+    .filter(|(handle, global)| {
+      let mut images = sampling_pairs
+        .iter()
+        .filter(|(_, sampler)| sampler == handle)
+        .map(|(image, _)| image)
+        .peekable();
+      matches!(element_type(global), naga::TypeInner::Sampler { comparison: false })
+        && images.peek().is_some()
+        && images.all(|image| non_filtering.contains(image))
     })
-    .collect()
+    .map(|(handle, _)| handle)
+    .collect();
+  non_filtering.extend(samplers);
+
+  non_filtering
+}
+
+/// The validator also performs the global usage analysis for each entry point.
+#[cfg(test)]
+pub fn module_info(module: &naga::Module) -> naga::valid::ModuleInfo {
+  naga::valid::Validator::new(
+    naga::valid::ValidationFlags::empty(),
+    naga::valid::Capabilities::all(),
+  )
+  .validate(module)
+  .unwrap()
+}
+
+/// Computes the size and alignment of each type in the module.
+pub fn module_layouter(
+  module: &naga::Module,
+) -> Result<naga::proc::Layouter, CreateModuleError> {
+  let mut layouter = naga::proc::Layouter::default();
+  layouter
+    .update(module.to_ctx())
+    .map_err(|e| CreateModuleError::TypeLayout {
+      name: type_name(module, &module.types[e.ty]),
+      message: e.inner.to_string(),
+      src: None,
+      span: None,
+    })?;
+  Ok(layouter)
 }
 
-pub fn buffer_binding_type(storage: naga::AddressSpace) -> TokenStream {
+pub fn has_push_constants(module: &naga::Module) -> bool {
+  module
+    .global_variables
+    .iter()
+    .any(|(_, g)| g.space == naga::AddressSpace::PushConstant)
+}
+
+/// Acceleration structure bindings and ray queries both require ray query support.
+pub fn has_ray_queries(module: &naga::Module) -> bool {
+  module.types.iter().any(|(_, t)| {
+    matches!(t.inner, naga::TypeInner::AccelerationStructure | naga::TypeInner::RayQuery)
+  })
+}
+
+pub fn buffer_binding_type(storage: naga::AddressSpace) -> Option<TokenStream> {
   match storage {
-    naga::AddressSpace::Uniform => quote!(wgpu::BufferBindingType::Uniform),
+    naga::AddressSpace::Uniform => Some(quote!(wgpu::BufferBindingType::Uniform)),
     naga::AddressSpace::Storage { access } => {
       let _is_read = access.contains(naga::StorageAccess::LOAD);
       let is_write = access.contains(naga::StorageAccess::STORE);
 
       // TODO: Is this correct?
       if is_write {
-        quote!(wgpu::BufferBindingType::Storage { read_only: false })
+        Some(quote!(wgpu::BufferBindingType::Storage { read_only: false }))
       } else {
-        quote!(wgpu::BufferBindingType::Storage { read_only: true })
+        Some(quote!(wgpu::BufferBindingType::Storage { read_only: true }))
       }
     }
-    _ => todo!(),
+    _ => None,
   }
 }
 
-pub fn vertex_format(ty: &naga::Type) -> wgpu::VertexFormat {
+pub fn vertex_format(ty: &naga::Type) -> Option<wgpu::VertexFormat> {
   // Not all wgsl types work as vertex attributes in wgpu.
-  match &ty.inner {
+  let format = match &ty.inner {
     naga::TypeInner::Scalar(scalar) => match (scalar.kind, scalar.width) {
       (naga::ScalarKind::Sint, 4) => wgpu::VertexFormat::Sint32,
       (naga::ScalarKind::Uint, 4) => wgpu::VertexFormat::Uint32,
       (naga::ScalarKind::Float, 4) => wgpu::VertexFormat::Float32,
       (naga::ScalarKind::Float, 8) => wgpu::VertexFormat::Float64,
-      _ => todo!(),
+      _ => return None,
     },
     naga::TypeInner::Vector { size, scalar } => match size {
       naga::VectorSize::Bi => match (scalar.kind, scalar.width) {
@@ -52,14 +176,14 @@ pub fn vertex_format(ty: &naga::Type) -> wgpu::VertexFormat {
         (naga::ScalarKind::Sint, 4) => wgpu::VertexFormat::Sint32x2,
         (naga::ScalarKind::Float, 4) => wgpu::VertexFormat::Float32x2,
         (naga::ScalarKind::Float, 8) => wgpu::VertexFormat::Float64x2,
-        _ => todo!(),
+        _ => return None,
       },
       naga::VectorSize::Tri => match (scalar.kind, scalar.width) {
         (naga::ScalarKind::Uint, 4) => wgpu::VertexFormat::Uint32x3,
         (naga::ScalarKind::Sint, 4) => wgpu::VertexFormat::Sint32x3,
         (naga::ScalarKind::Float, 4) => wgpu::VertexFormat::Float32x3,
         (naga::ScalarKind::Float, 8) => wgpu::VertexFormat::Float64x3,
-        _ => todo!(),
+        _ => return None,
       },
       naga::VectorSize::Quad => match (scalar.kind, scalar.width) {
         (naga::ScalarKind::Sint, 1) => wgpu::VertexFormat::Sint8x4,
@@ -70,10 +194,19 @@ pub fn vertex_format(ty: &naga::Type) -> wgpu::VertexFormat {
         (naga::ScalarKind::Sint, 4) => wgpu::VertexFormat::Sint32x4,
         (naga::ScalarKind::Float, 4) => wgpu::VertexFormat::Float32x4,
         (naga::ScalarKind::Float, 8) => wgpu::VertexFormat::Float64x4,
-        _ => todo!(),
+        _ => return None,
       },
     },
-    _ => todo!(), // are these types even valid as attributes?
+    _ => return None,
+  };
+  Some(format)
+}
+
+/// The type as it is written in WGSL for error messages.
+pub fn type_name(module: &naga::Module, ty: &naga::Type) -> String {
+  match &ty.name {
+    Some(name) => demangle(name).into(),
+    None => ty.inner.to_wgsl(&module.to_ctx()),
   }
 }
 
@@ -135,79 +268,81 @@ mod tests {
   use super::*;
 
   #[test]
-  fn shader_stages_none() {
+  fn global_shader_stages_per_entry_usage() {
     let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> vertex_only: vec4<f32>;
+            @group(0) @binding(1) var<uniform> fragment_only: vec4<f32>;
+            @group(0) @binding(2) var<storage, read_write> vertex_compute: vec4<f32>;
+            @group(0) @binding(3) var<uniform> unused: vec4<f32>;
 
-        "#};
-
-    let module = naga::front::wgsl::parse_str(source).unwrap();
-    assert_eq!(wgpu::ShaderStages::NONE, shader_stages(&module));
-  }
+            fn helper() -> vec4<f32> {
+                return vertex_compute;
+            }
 
-  #[test]
-  fn shader_stages_vertex() {
-    let source = indoc! {r#"
             @vertex
-            fn main()  {}
-        "#};
-
-    let module = naga::front::wgsl::parse_str(source).unwrap();
-    assert_eq!(wgpu::ShaderStages::VERTEX, shader_stages(&module));
-  }
-
-  #[test]
-  fn shader_stages_fragment() {
-    let source = indoc! {r#"
-            @fragment
-            fn main()  {}
-        "#};
-
-    let module = naga::front::wgsl::parse_str(source).unwrap();
-    assert_eq!(wgpu::ShaderStages::FRAGMENT, shader_stages(&module));
-  }
-
-  #[test]
-  fn shader_stages_vertex_fragment() {
-    let source = indoc! {r#"
-            @vertex
-            fn vs_main()  {}
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return vertex_only + helper();
+            }
 
             @fragment
-            fn fs_main()  {}
-        "#};
-
-    let module = naga::front::wgsl::parse_str(source).unwrap();
-    assert_eq!(wgpu::ShaderStages::VERTEX_FRAGMENT, shader_stages(&module));
-  }
+            fn fs_main() -> @location(0) vec4<f32> {
+                return fragment_only;
+            }
 
-  #[test]
-  fn shader_stages_compute() {
-    let source = indoc! {r#"
             @compute
             @workgroup_size(64)
-            fn main()  {}
+            fn cs_main() {
+                vertex_compute = vec4<f32>(1.0);
+            }
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    assert_eq!(wgpu::ShaderStages::COMPUTE, shader_stages(&module));
+    let stages = global_shader_stages(&module, &module_info(&module));
+
+    let stages_for = |name: &str| {
+      module
+        .global_variables
+        .iter()
+        .find(|(_, g)| g.name.as_deref() == Some(name))
+        .and_then(|(handle, _)| stages.get(&handle).copied())
+    };
+
+    assert_eq!(Some(wgpu::ShaderStages::VERTEX), stages_for("vertex_only"));
+    assert_eq!(Some(wgpu::ShaderStages::FRAGMENT), stages_for("fragment_only"));
+    assert_eq!(
+      Some(wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::COMPUTE),
+      stages_for("vertex_compute")
+    );
+    assert_eq!(None, stages_for("unused"));
   }
 
   #[test]
-  fn shader_stages_all() {
+  fn non_filtering_globals_from_usage() {
     let source = indoc! {r#"
-            @vertex
-            fn vs_main()  {}
+            @group(0) @binding(0) var sampled: texture_2d<f32>;
+            @group(0) @binding(1) var loaded: texture_2d<f32>;
+            @group(0) @binding(2) var unused: texture_2d<f32>;
+            @group(0) @binding(3) var ids: texture_2d<u32>;
+            @group(0) @binding(4) var filtering: sampler;
+            @group(0) @binding(5) var non_filtering: sampler;
 
             @fragment
-            fn fs_main()  {}
-
-            @compute
-            @workgroup_size(64)
-            fn cs_main()  {}
+            fn fs_main() -> @location(0) vec4<f32> {
+                let a = textureSample(sampled, filtering, vec2(0.0));
+                let b = textureLoad(loaded, vec2(0), 0);
+                let c = textureGather(0, ids, non_filtering, vec2(0.0));
+                return a + b + vec4<f32>(c);
+            }
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    assert_eq!(wgpu::ShaderStages::all(), shader_stages(&module));
+    let non_filtering = non_filtering_globals(&module, &module_info(&module));
+
+    let names: FastHashSet<_> = non_filtering
+      .iter()
+      .map(|h| module.global_variables[*h].name.as_deref().unwrap())
+      .collect();
+    assert_eq!(FastHashSet::from_iter(["loaded", "ids", "non_filtering"]), names);
   }
 
   #[test]
@@ -1,15 +1,21 @@
 use std::io::Write;
 use std::path::PathBuf;
 
+use case::CaseExt;
 use derive_builder::Builder;
-use miette::Diagnostic;
+use derive_more::Display;
+use miette::{Diagnostic, LabeledSpan, NamedSource, SourceSpan};
+pub use naga::valid::{Capabilities, ValidationFlags};
+pub use naga_oil::compose::ShaderDefValue;
 use naga_oil::compose::{
-  ComposableModuleDescriptor, Composer, ComposerError, NagaModuleDescriptor,
-  ShaderLanguage,
+  ComposableModuleDescriptor, Composer, ComposerError, ComposerErrorInner, ErrSource,
+  NagaModuleDescriptor, ShaderLanguage,
 };
 use thiserror::Error;
 
 use self::source_file::SourceFile;
+use crate::naga_util::check_backend;
+use crate::shader_defs::{default_shader_defs, get_shader_defs};
 use crate::{
   bevy_util::*, WgslEntryResult, WgslTypeMap, WgslTypeMapBuild, WgslTypeSerializeStrategy,
 };
@@ -32,22 +38,152 @@ pub enum WgslBindgenError {
   #[diagnostic(transparent)]
   DependencyTreeError(#[from] DependencyTreeError),
 
-  #[error("Failed to compose modules with entry `{entry}`\n{inner}")]
+  #[error("Failed to compose modules with entry `{entry}`\n{}", demangle(&.inner.to_string()))]
   NagaModuleComposeError {
     entry: String,
     inner: naga_oil::compose::ComposerErrorInner,
+
+    /// The preprocessed source of the module that failed to compose.
+    #[source_code]
+    src: NamedSource<String>,
+
+    #[label(collection)]
+    labels: Vec<LabeledSpan>,
+
+    #[help]
+    notes: Option<String>,
   },
 
   #[error(transparent)]
+  #[diagnostic(transparent)]
   ModuleCreationError(#[from] CreateModuleError),
 
   #[error(transparent)]
   WriteOutputError(#[from] std::io::Error),
 
+  #[error("Failed to translate entry `{entry}` with the naga backends")]
+  BackendTranslationError {
+    entry: String,
+
+    #[related]
+    errors: Vec<BackendError>,
+  },
+
+  #[error("Permutation `{name}` uses `{entry_point}`, which is not an entry point")]
+  UnknownPermutationEntryPoint { name: String, entry_point: String },
+
+  #[error("Permutation name `{name}` is not a valid Rust identifier")]
+  InvalidPermutationName { name: String },
+
+  #[error("Permutation `{name}` of `{entry_point}` conflicts with another permutation or the `Default` variant")]
+  ConflictingPermutationName { name: String, entry_point: String },
+
   #[error("Output file is not specified. Maybe use `generate_string` instead")]
   OutputFileNotSpecified,
 }
 
+/// An error from translating an entry point or the whole module with a naga backend.
+#[derive(Debug, Error, Diagnostic)]
+#[error(
+  "{backend} translation failed{}: {message}",
+  .entry_point.as_ref().map(|name| format!(" for entry point `{name}`")).unwrap_or_default()
+)]
+pub struct BackendError {
+  pub backend: WgslShaderBackend,
+  pub entry_point: Option<String>,
+  pub message: String,
+
+  #[source_code]
+  pub src: NamedSource<String>,
+
+  #[label("Entry point")]
+  pub span: Option<SourceSpan>,
+}
+
+/// Converts the composer error to a diagnostic for the file containing the error.
+/// This is based on naga_oil's `ComposerError::emit_to_string`.
+fn compose_error(
+  entry: &str,
+  composer: &Composer,
+  err: ComposerError,
+) -> WgslBindgenError {
+  // naga_oil stores the module index in the upper bits of its spans.
+  const SPAN_SHIFT: usize = 21;
+
+  let source = err.source.source(composer).into_owned();
+  let source_offset = err.source.offset();
+  let map_span = |span: naga::Span| {
+    let range = span.to_range().unwrap_or(0..0);
+    let start = (range.start & ((1 << SPAN_SHIFT) - 1)).saturating_sub(source_offset);
+    let end = (range.end & ((1 << SPAN_SHIFT) - 1)).saturating_sub(source_offset);
+    SourceSpan::from(start..end.max(start))
+  };
+  let label = |message: Option<String>, span: SourceSpan| {
+    LabeledSpan::new_with_span(message.map(|m| demangle(&m).into_owned()), span)
+  };
+
+  let (labels, notes) = match &err.inner {
+    ComposerErrorInner::WgslParseError(e) => (
+      e.labels()
+        .map(|(span, message)| label(Some(message.to_owned()), map_span(span)))
+        .collect(),
+      Vec::new(),
+    ),
+    ComposerErrorInner::HeaderValidationError(e)
+    | ComposerErrorInner::ShaderValidationError(e) => {
+      let mut notes = Vec::new();
+      let mut error_source = std::error::Error::source(e.as_inner());
+      while let Some(source) = error_source {
+        notes.push(source.to_string());
+        error_source = source.source();
+      }
+      let labels = e
+        .spans()
+        .map(|(span, message)| label(Some(message.clone()), map_span(*span)))
+        .collect();
+      (labels, notes)
+    }
+    ComposerErrorInner::ImportNotFound(message, pos)
+    | ComposerErrorInner::ImportParseError(message, pos) => {
+      (vec![label(Some(message.clone()), (*pos, 0).into())], Vec::new())
+    }
+    ComposerErrorInner::InvalidIdentifier { at, .. } => {
+      (vec![label(None, map_span(*at))], Vec::new())
+    }
+    ComposerErrorInner::DecorationInSource(range) => {
+      (vec![label(None, range.clone().into())], Vec::new())
+    }
+    ComposerErrorInner::NotEnoughEndIfs(pos)
+    | ComposerErrorInner::TooManyEndIfs(pos)
+    | ComposerErrorInner::ElseWithoutCondition(pos)
+    | ComposerErrorInner::UnknownShaderDef { pos, .. }
+    | ComposerErrorInner::UnknownShaderDefOperator { pos, .. }
+    | ComposerErrorInner::InvalidShaderDefComparisonValue { pos, .. }
+    | ComposerErrorInner::OverrideNotVirtual { pos, .. }
+    | ComposerErrorInner::GlslInvalidVersion(pos)
+    | ComposerErrorInner::DefineInModule(pos)
+    | ComposerErrorInner::InvalidShaderDefDefinitionValue { pos, .. } => {
+      (vec![label(None, (*pos, 0).into())], Vec::new())
+    }
+    _ => (Vec::new(), Vec::new()),
+  };
+
+  // Spans past the end of the source can't be displayed.
+  let labels = labels
+    .into_iter()
+    .filter(|l: &LabeledSpan| l.offset() + l.len() <= source.len())
+    .collect();
+  let notes = (!notes.is_empty()).then(|| demangle(&notes.join("\n")).into_owned());
+
+  WgslBindgenError::NagaModuleComposeError {
+    entry: entry.to_owned(),
+    src: NamedSource::new(err.source.path(composer), source),
+    inner: err.inner,
+    labels,
+    notes,
+  }
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WgslShaderSourceOutputType {
   /// Include the final shader string directly in the output
@@ -56,6 +192,122 @@ pub enum WgslShaderSourceOutputType {
 
   /// Use Composer including helper functions which will be executed on runtime
   Composer,
+
+  /// Include SPIR-V translated from the final shader at build time.
+  /// The final shader string is used instead when the device doesn't support
+  /// `SPIRV_SHADER_PASSTHROUGH`. This requires the `spirv` feature for wgpu.
+  ///
+  /// Each shader is included both as SPIR-V and as the final shader string for the
+  /// fallback, so the output is larger than with [Self::FinalShaderString].
+  /// Without passthrough, wgpu still parses the SPIR-V with naga when creating the
+  /// shader module, so this only skips parsing at runtime on devices that support
+  /// `SPIRV_SHADER_PASSTHROUGH`.
+  SpirV,
+
+  /// Include the final naga module serialized with bincode at build time.
+  /// This requires the `naga-ir` feature for wgpu, the `deserialize` feature for naga,
+  /// and bincode 1.x.
+  NagaIr,
+}
+
+/// The naga validation applied to the shaders while generating the bindings.
+///
+/// Shaders that fail validation return a [WgslBindgenError::NagaModuleComposeError]
+/// instead of failing when the shader module is created at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WgslShaderValidation {
+  pub flags: ValidationFlags,
+  pub capabilities: Capabilities,
+}
+
+impl WgslShaderValidation {
+  /// Allows all native shader capabilities like push constants or 64-bit floats.
+  /// Features are checked when creating the pipeline instead.
+  pub const NATIVE_ALL: Self = Self::new(ValidationFlags::all(), Capabilities::all());
+
+  /// Allows the shader capabilities supported by all WebGPU implementations.
+  pub const WEBGPU: Self = Self::new(
+    ValidationFlags::all(),
+    Capabilities::CUBE_ARRAY_TEXTURES.union(Capabilities::MULTISAMPLED_SHADING),
+  );
+
+  /// Allows only the shader capabilities supported by downlevel backends like WebGL2.
+  pub const DOWNLEVEL: Self = Self::new(ValidationFlags::all(), Capabilities::empty());
+
+  pub const fn new(flags: ValidationFlags, capabilities: Capabilities) -> Self {
+    Self {
+      flags,
+      capabilities,
+    }
+  }
+}
+
+impl Default for WgslShaderValidation {
+  fn default() -> Self {
+    Self::NATIVE_ALL
+  }
+}
+
+/// A naga backend used to check that the shaders can be translated for a platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum WgslShaderBackend {
+  /// SPIR-V for Vulkan.
+  #[display(fmt = "SPIR-V")]
+  Spirv,
+
+  /// Metal Shading Language 2.4 for Metal.
+  #[display(fmt = "MSL")]
+  Msl,
+
+  /// HLSL shader model 5.1 for DX12.
+  #[display(fmt = "HLSL")]
+  Hlsl,
+
+  /// GLSL ES 3.0 for WebGL2 or GLSL ES 3.1 for compute shaders.
+  #[display(fmt = "GLSL")]
+  Glsl,
+}
+
+impl WgslShaderBackend {
+  pub const ALL: [Self; 4] = [Self::Spirv, Self::Msl, Self::Hlsl, Self::Glsl];
+}
+
+/// A variant of an entry point composed with additional shader defs.
+///
+/// Each permutation generates a submodule of the entry module like `pbr::skinned`
+/// and a variant of the entry module's `Permutation` enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WgslShaderPermutation {
+  /// The entry point path as passed to `add_entry_point`.
+  pub entry_point: String,
+  /// The name of the generated submodule in snake case like `skinned`.
+  pub name: String,
+  /// The shader defs to compose the entry point with.
+  pub shader_defs: Vec<(String, ShaderDefValue)>,
+}
+
+impl WgslShaderPermutation {
+  pub fn new(entry_point: impl Into<String>, name: impl Into<String>) -> Self {
+    Self {
+      entry_point: entry_point.into(),
+      name: name.into(),
+      shader_defs: Vec::new(),
+    }
+  }
+
+  /// Defines the shader def for `#ifdef` and `#ifndef` directives.
+  pub fn with_def(self, name: impl Into<String>) -> Self {
+    self.with_def_value(name, ShaderDefValue::Bool(true))
+  }
+
+  pub fn with_def_value(
+    mut self,
+    name: impl Into<String>,
+    value: ShaderDefValue,
+  ) -> Self {
+    self.shader_defs.push((name.into(), value));
+    self
+  }
 }
 
 /// A struct representing a directory to scan for additional source files.
@@ -124,10 +376,37 @@ pub struct WgslBindgenOption {
   #[builder(default)]
   pub shader_source_output_type: WgslShaderSourceOutputType,
 
+  /// Generates `reload_shader_module` functions for creating shader modules from the
+  /// sources on disk in debug builds and a `hot_reload::ShaderWatcher` for detecting changes.
+  /// This is only supported with [WgslShaderSourceOutputType::Composer].
+  #[builder(default = "false")]
+  pub hot_reload: bool,
+
+  /// The naga validation flags and capabilities used to validate the shaders.
+  /// Defaults to [WgslShaderValidation::NATIVE_ALL].
+  #[builder(default)]
+  pub validation: WgslShaderValidation,
+
+  /// The naga backends used to check that each entry module can be translated for the
+  /// platforms using them. Translation failures return a [WgslBindgenError::BackendTranslationError].
+  /// Defaults to no backends.
+  #[builder(default, setter(into, each(name = "add_backend_check", into)))]
+  pub backend_checks: Vec<WgslShaderBackend>,
+
+  /// Additional variants of entry points composed with different shader defs.
+  /// Structs and constants that are the same as in the entry module are reexported
+  /// instead of generated again.
+  #[builder(default, setter(into, each(name = "add_permutation", into)))]
+  pub permutations: Vec<WgslShaderPermutation>,
+
   /// A mapping operation for WGSL built-in types. This is used to map WGSL built-in types to their corresponding representations.
   #[builder(setter(custom))]
   pub wgsl_type_map: WgslTypeMap,
 
+  /// A prefix for the debug labels of generated wgpu objects like "triangle::BindGroup1". Defaults to `None`.
+  #[builder(default, setter(strip_option, into))]
+  pub label_prefix: Option<String>,
+
   /// The output file path for the generated Rust bindings. Defaults to `None`.
   #[builder(default, setter(strip_option, into))]
   pub output_file: Option<PathBuf>,
@@ -135,6 +414,20 @@ pub struct WgslBindgenOption {
   /// The additional set of directories to scan for source files.
   #[builder(default, setter(into, each(name = "additional_scan_dir", into)))]
   pub additional_scan_dirs: Vec<AdditionalScanDirectory>,
+
+  /// The uniform and storage buffer bindings that use dynamic offsets.
+  /// Bindings are qualified by the entry module like `"main::transforms"`
+  /// or by the import path for imported bindings like `"types::transforms"`.
+  /// The generated `set` methods for their bind groups take the offsets to use.
+  /// Runtime-sized bindings can't use dynamic offsets.
+  #[builder(default, setter(into, each(name = "add_dynamic_offset_binding", into)))]
+  pub dynamic_offset_bindings: Vec<String>,
+
+  /// Use `TypedBufferBinding<'a, T>` for buffer binding fields instead of `wgpu::BufferBinding<'a>`.
+  /// The bindings can only be created from a `TypedBuffer<T>` of the matching WGSL type. Defaults to `false`.
+  /// Buffer binding arrays and bytemuck structs ending in a runtime-sized array stay `wgpu::BufferBinding<'a>`.
+  #[builder(default = "false")]
+  pub typed_buffer_bindings: bool,
 }
 
 impl WgslBindgenOptionBuilder {
@@ -161,13 +454,44 @@ pub struct WGSLBindgen {
 
 impl WGSLBindgen {
   fn new(options: WgslBindgenOption) -> Result<Self, WgslBindgenError> {
-    let entry_points = options
+    let entry_points: Vec<_> = options
       .entry_points
       .iter()
       .cloned()
       .map(SourceFilePath::new)
       .collect();
 
+    for permutation in &options.permutations {
+      if !entry_points.contains(&SourceFilePath::new(&permutation.entry_point)) {
+        return Err(WgslBindgenError::UnknownPermutationEntryPoint {
+          name: permutation.name.clone(),
+          entry_point: permutation.entry_point.clone(),
+        });
+      }
+
+      // The name is used for the submodule and the camel case `Permutation` variant.
+      let is_ident = |name: &str| syn::parse_str::<syn::Ident>(name).is_ok();
+      let variant = permutation.name.to_camel();
+      if !is_ident(&permutation.name) || !is_ident(&variant) {
+        return Err(WgslBindgenError::InvalidPermutationName {
+          name: permutation.name.clone(),
+        });
+      }
+
+      let entry_point = SourceFilePath::new(&permutation.entry_point);
+      let conflicts = options.permutations.iter().any(|other| {
+        !std::ptr::eq(other, permutation)
+          && SourceFilePath::new(&other.entry_point) == entry_point
+          && other.name.to_camel() == variant
+      });
+      if conflicts || variant == "Default" {
+        return Err(WgslBindgenError::ConflictingPermutationName {
+          name: permutation.name.clone(),
+          entry_point: permutation.entry_point.clone(),
+        });
+      }
+    }
+
     let dependency_tree = DependencyTree::try_build(
       options.module_import_root.clone(),
       entry_points,
@@ -209,15 +533,14 @@ impl WGSLBindgen {
     hasher.finalize().to_string()
   }
 
-  fn generate_naga_module_for_entry(
-    entry: SourceWithFullDependenciesResult<'_>,
-  ) -> Result<WgslEntryResult, WgslBindgenError> {
-    let map_err = |err: ComposerError| WgslBindgenError::NagaModuleComposeError {
-      entry: entry.source_file.file_path.to_string(),
-      inner: err.inner,
-    };
+  fn generate_naga_module_for_entry<'a>(
+    entry: SourceWithFullDependenciesResult<'a>,
+    permutation: Option<&WgslShaderPermutation>,
+    validation: WgslShaderValidation,
+  ) -> Result<WgslEntryResult<'a>, WgslBindgenError> {
+    let entry_path = entry.source_file.file_path.to_string();
 
-    let mut composer = Composer::default();
+    let mut composer = Composer::default().with_capabilities(validation.capabilities);
     let source = entry.source_file;
 
     for dependency in entry.full_dependencies.iter() {
@@ -229,32 +552,134 @@ impl WGSLBindgen {
           as_name: dependency.module_name.as_ref().map(|name| name.to_string()),
           ..Default::default()
         })
-        .map_err(map_err)?;
+        // Release the mutable borrow of the composer before creating the error.
+        .map(|_| ())
+        .map_err(|err| compose_error(&entry_path, &composer, err))?;
+    }
+
+    // The defs compared in `#if` directives need a value to compose the module.
+    let mut shader_defs = default_shader_defs(&get_shader_defs(&entry));
+    if let Some(permutation) = permutation {
+      shader_defs.extend(permutation.shader_defs.iter().cloned());
     }
+    let descriptor = || NagaModuleDescriptor {
+      source: &source.content,
+      file_path: &entry_path,
+      shader_defs: shader_defs.clone(),
+      ..Default::default()
+    };
 
+    // naga_oil always validates with all flags, so validate with the configured flags instead.
+    composer.validate = false;
     let module = composer
-      .make_naga_module(NagaModuleDescriptor {
-        source: &source.content,
-        file_path: &source.file_path.to_string(),
-        ..Default::default()
-      })
-      .map_err(map_err)?;
+      .make_naga_module(descriptor())
+      .map_err(|err| compose_error(&entry_path, &composer, err))?;
+
+    let validator_result =
+      naga::valid::Validator::new(validation.flags, validation.capabilities)
+        .validate(&module);
+
+    let module_info = match validator_result {
+      Ok(module_info) => module_info,
+      Err(validation_err) => {
+        // Compose again with naga_oil's validation to map the spans to the source files.
+        composer.validate = true;
+        let err = match composer.make_naga_module(descriptor()) {
+          Err(err) => err,
+          Ok(_) => ComposerError {
+            inner: ComposerErrorInner::ShaderValidationError(validation_err),
+            source: ErrSource::Constructing {
+              path: entry_path.clone(),
+              source: source.content.clone(),
+              offset: 0,
+            },
+          },
+        };
+        return Err(compose_error(&entry_path, &composer, err));
+      }
+    };
+
+    let mut mod_name = source.file_path.file_prefix();
+    if let Some(permutation) = permutation {
+      mod_name = format!("{mod_name}::{}", permutation.name);
+    }
 
     Ok(WgslEntryResult {
-      mod_name: source.file_path.file_prefix(),
+      mod_name,
       naga_module: module,
       source_including_deps: entry,
+      permutation: permutation.cloned(),
+      module_info,
     })
   }
 
+  fn check_backends(&self, entry: &WgslEntryResult) -> Result<(), WgslBindgenError> {
+    // Report SPIR-V output errors as diagnostics instead of failing to generate the module.
+    let mut backends = self.options.backend_checks.clone();
+    if self.options.shader_source_output_type == WgslShaderSourceOutputType::SpirV
+      && !backends.contains(&WgslShaderBackend::Spirv)
+    {
+      backends.push(WgslShaderBackend::Spirv);
+    }
+    if backends.is_empty() {
+      return Ok(());
+    }
+
+    let source_file = entry.source_including_deps.source_file;
+    let errors: Vec<_> = backends
+      .iter()
+      .flat_map(|backend| {
+        check_backend(&entry.naga_module, &entry.module_info, *backend)
+          .into_iter()
+          .map(|err| BackendError {
+            backend: *backend,
+            span: err
+              .entry_point
+              .as_ref()
+              .and_then(|name| source_file.find_declaration(name, None)),
+            entry_point: err.entry_point,
+            message: err.message,
+            src: NamedSource::new(
+              source_file.file_path.to_string(),
+              source_file.content.clone(),
+            ),
+          })
+      })
+      .collect();
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(WgslBindgenError::BackendTranslationError {
+        entry: source_file.file_path.to_string(),
+        errors,
+      })
+    }
+  }
+
   pub fn generate_string(&self) -> Result<String, WgslBindgenError> {
     use std::fmt::Write;
-    let entry_results = self
+    let validation = self.options.validation;
+    let mut entry_results = Vec::new();
+    for entry in self
       .dependency_tree
       .get_source_files_with_full_dependencies()
-      .into_iter()
-      .map(Self::generate_naga_module_for_entry)
-      .collect::<Result<Vec<_>, _>>()?;
+    {
+      let permutations = self.options.permutations.iter().filter(|permutation| {
+        SourceFilePath::new(&permutation.entry_point) == entry.source_file.file_path
+      });
+      for permutation in std::iter::once(None).chain(permutations.map(Some)) {
+        entry_results.push(Self::generate_naga_module_for_entry(
+          entry.clone(),
+          permutation,
+          validation,
+        )?);
+      }
+    }
+
+    for entry in &entry_results {
+      self.check_backends(entry)?;
+    }
 
     let mut text = String::new();
 
@@ -1,7 +1,9 @@
 use miette::{IntoDiagnostic, Result};
 use pretty_assertions::assert_eq;
 use wgsl_bindgen::{
-  WgslBindgenOptionBuilder, GlamWgslTypeMap, WgslTypeSerializeStrategy,
+  CreateModuleError, GlamWgslTypeMap, WgslBindgenError, WgslBindgenOptionBuilder,
+  WgslShaderBackend, WgslShaderPermutation, WgslShaderSourceOutputType,
+  WgslShaderValidation, WgslTypeSerializeStrategy,
 };
 
 #[test]
@@ -42,6 +44,25 @@ fn test_main_bindgen() -> Result<()> {
   Ok(())
 }
 
+#[test]
+fn test_shared_bind_groups_bindgen() -> Result<()> {
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/shared/forward.wgsl")
+    .add_entry_point("tests/shaders/shared/depth.wgsl")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  let expected = include_str!("expected/bindgen_shared.out.rs");
+
+  assert_eq!(actual, expected);
+  Ok(())
+}
+
 #[test]
 #[ignore = "It doesn't like path symbols inside a nested type like array."]
 fn test_path_import() -> Result<()> {
@@ -57,3 +78,288 @@ fn test_path_import() -> Result<()> {
 
   Ok(())
 }
+
+#[test]
+fn test_unsupported_binding_error() -> Result<()> {
+  let result = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/errors/runtime_sized_binding_array.wgsl")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string();
+
+  let Err(WgslBindgenError::ModuleCreationError(error)) = result else {
+    panic!("expected a module creation error");
+  };
+  let CreateModuleError::RuntimeSizedBindingArray { name, src, span } = &error else {
+    panic!("expected a runtime-sized binding array error: {error}");
+  };
+
+  // The error points to the global variable declaration.
+  assert_eq!("textures", name);
+  assert_eq!(
+    Some("tests/shaders/errors/runtime_sized_binding_array.wgsl"),
+    src.as_ref().map(|s| s.name())
+  );
+  assert_eq!(Some((26, 8).into()), *span);
+  Ok(())
+}
+
+#[test]
+fn test_shader_defs_composer() -> Result<()> {
+  // Defs compared in `#if` directives use default values at build time.
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/shader_defs.wgsl")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .shader_source_output_type(WgslShaderSourceOutputType::Composer)
+    .output_file("tests/output/shader_defs.rs")
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub msaa_samples: i32,"));
+  assert!(actual.contains("pub shadows: bool,"));
+  assert!(actual.contains("shader_defs: shader_defs.to_shader_defs(),"));
+  assert!(actual.contains("if *shader_defs != ShaderDefs::default() {"));
+  Ok(())
+}
+
+#[test]
+fn test_hot_reload_composer() -> Result<()> {
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/basic/main.wgsl")
+    .additional_scan_dir((None, "tests/shaders/additional"))
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .shader_source_output_type(WgslShaderSourceOutputType::Composer)
+    .hot_reload(true)
+    .output_file("tests/output/hot_reload.rs")
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub mod hot_reload {"));
+  assert!(actual.contains(
+    r#"concat!(env!("CARGO_MANIFEST_DIR"), "/", "tests/shaders/basic/main.wgsl")"#
+  ));
+  assert!(actual.contains("let path = SHADER_PATHS[1];"));
+  assert!(actual.contains("pub fn reload_shader_module("));
+  Ok(())
+}
+
+#[test]
+fn test_permutations_bindgen() -> Result<()> {
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/permutations/mesh.wgsl")
+    .add_permutation(
+      WgslShaderPermutation::new("tests/shaders/permutations/mesh.wgsl", "skinned")
+        .with_def("SKINNED"),
+    )
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .shader_source_output_type(WgslShaderSourceOutputType::Composer)
+    .output_file("tests/output/permutations.rs")
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  let expected = include_str!("expected/bindgen_permutations.out.rs");
+
+  assert_eq!(actual, expected);
+  Ok(())
+}
+
+#[test]
+fn test_permutations_imported_items_bindgen() -> Result<()> {
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/permutations/lit.wgsl")
+    .add_permutation(
+      WgslShaderPermutation::new("tests/shaders/permutations/lit.wgsl", "shadows")
+        .with_def("SHADOWS"),
+    )
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .shader_source_output_type(WgslShaderSourceOutputType::Composer)
+    .output_file("tests/output/permutations_imports.rs")
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  let expected = include_str!("expected/bindgen_permutations_imports.out.rs");
+
+  assert_eq!(actual, expected);
+  Ok(())
+}
+
+#[test]
+fn test_unknown_permutation_entry_point() -> Result<()> {
+  let result = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/permutations/mesh.wgsl")
+    .add_permutation(WgslShaderPermutation::new("tests/shaders/missing.wgsl", "skinned"))
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .build();
+
+  assert!(matches!(result, Err(WgslBindgenError::UnknownPermutationEntryPoint { .. })));
+  Ok(())
+}
+
+#[test]
+fn test_invalid_permutation_names() -> Result<()> {
+  let build = |names: &[&str]| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point("tests/shaders/permutations/mesh.wgsl")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .wgsl_type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false);
+    for name in names {
+      builder.add_permutation(WgslShaderPermutation::new(
+        "tests/shaders/permutations/mesh.wgsl",
+        *name,
+      ));
+    }
+    builder.build()
+  };
+
+  for name in ["alpha-mask", "type", ""] {
+    assert!(matches!(
+      build(&[name]),
+      Err(WgslBindgenError::InvalidPermutationName { .. })
+    ));
+  }
+  assert!(matches!(
+    build(&["default"]),
+    Err(WgslBindgenError::ConflictingPermutationName { .. })
+  ));
+  assert!(matches!(
+    build(&["skinned", "skinned"]),
+    Err(WgslBindgenError::ConflictingPermutationName { .. })
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_spirv_bindgen() -> Result<()> {
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/spirv.wgsl")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .shader_source_output_type(WgslShaderSourceOutputType::SpirV)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  let expected = include_str!("expected/bindgen_spirv.out.rs");
+
+  assert_eq!(actual, expected);
+  Ok(())
+}
+
+#[test]
+fn test_naga_ir_bindgen() -> Result<()> {
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/spirv.wgsl")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .shader_source_output_type(WgslShaderSourceOutputType::NagaIr)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  let expected = include_str!("expected/bindgen_naga_ir.out.rs");
+
+  assert_eq!(actual, expected);
+  Ok(())
+}
+
+#[test]
+fn test_compose_error() -> Result<()> {
+  let result = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/errors/compose_error.wgsl")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string();
+
+  let Err(WgslBindgenError::NagaModuleComposeError { src, labels, .. }) = result else {
+    panic!("expected a compose error");
+  };
+
+  // The error points to the missing semicolon in the imported file.
+  assert_eq!("tests/shaders/errors/colors.wgsl", src.name());
+  assert_eq!(1, labels.len());
+  assert_eq!(Some("expected ';'"), labels[0].label());
+  assert_eq!(58, labels[0].offset());
+  Ok(())
+}
+
+#[test]
+fn test_validation_capabilities() -> Result<()> {
+  let mut builder = WgslBindgenOptionBuilder::default();
+  builder
+    .add_entry_point("tests/shaders/errors/push_constants.wgsl")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true);
+
+  // Push constants are allowed by default.
+  builder.build()?.generate_string()?;
+
+  let result = builder
+    .validation(WgslShaderValidation::WEBGPU)
+    .build()?
+    .generate_string();
+
+  let Err(WgslBindgenError::NagaModuleComposeError { src, labels, .. }) = result else {
+    panic!("expected a validation error");
+  };
+
+  assert_eq!("tests/shaders/errors/push_constants.wgsl", src.name());
+  assert_eq!(1, labels.len());
+  assert_eq!(0, labels[0].offset());
+  Ok(())
+}
+
+#[test]
+fn test_backend_checks() -> Result<()> {
+  let result = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/errors/storage_buffer_fragment.wgsl")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .wgsl_type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .backend_checks(WgslShaderBackend::ALL)
+    .build()?
+    .generate_string();
+
+  let Err(WgslBindgenError::BackendTranslationError { errors, .. }) = result else {
+    panic!("expected a backend translation error");
+  };
+
+  // WebGL2 doesn't support storage buffers.
+  assert_eq!(1, errors.len());
+  assert_eq!(WgslShaderBackend::Glsl, errors[0].backend);
+  assert_eq!(Some("fs_main"), errors[0].entry_point.as_deref());
+  assert_eq!(Some((81, 7).into()), errors[0].span);
+  Ok(())
+}
@@ -3,7 +3,7 @@ use std::sync::OnceLock;
 use indexmap::{indexmap, indexset, IndexMap};
 use miette::IntoDiagnostic;
 use pretty_assertions::assert_eq;
-use wgsl_bindgen::bevy_util::DependencyTree;
+use wgsl_bindgen::bevy_util::{DependencyTree, DependencyTreeError};
 use wgsl_bindgen::SourceFilePath;
 
 pub type SourceDependencyMap =
@@ -158,3 +158,63 @@ fn test_bevy_mesh_wgsl_dependency_order() {
     ]
   );
 }
+
+#[test]
+fn test_circular_import_error() {
+  let result = DependencyTree::try_build(
+    None,
+    vec![SourceFilePath::new(
+      "tests/shaders/circular/circular_a.wgsl",
+    )],
+    vec![],
+  );
+
+  let Err(DependencyTreeError::CircularImport { cycle, imports }) = result else {
+    panic!("expected a circular import error");
+  };
+
+  assert_eq!(
+    cycle,
+    vec![
+      SourceFilePath::new("tests/shaders/circular/circular_a.wgsl"),
+      SourceFilePath::new("tests/shaders/circular/circular_b.wgsl"),
+      SourceFilePath::new("tests/shaders/circular/circular_c.wgsl"),
+      SourceFilePath::new("tests/shaders/circular/circular_a.wgsl"),
+    ]
+  );
+
+  let imports = imports
+    .iter()
+    .map(|import| {
+      (
+        import.importer.to_str().unwrap(),
+        import.path.as_str(),
+        import.import_bit.offset(),
+      )
+    })
+    .collect::<Vec<_>>();
+
+  assert_eq!(
+    imports,
+    vec![
+      ("tests/shaders/circular/circular_a.wgsl", "circular_b", 0),
+      ("tests/shaders/circular/circular_b.wgsl", "circular_c", 0),
+      ("tests/shaders/circular/circular_c.wgsl", "circular_a", 0),
+    ]
+  );
+}
+
+#[test]
+fn test_deep_import_chain() {
+  let deptree = DependencyTree::try_build(
+    None,
+    vec![SourceFilePath::new(
+      "tests/shaders/deep_import_chain/level0.wgsl",
+    )],
+    vec![],
+  )
+  .into_diagnostic()
+  .unwrap();
+
+  assert_eq!(deptree.all_files_including_dependencies().len(), 32);
+}
@@ -12,32 +12,506 @@ mod _root {
         assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
     };
 }
+pub mod pass_ext {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    /// A render pass, compute pass, or render bundle encoder for setting bind groups.
+    pub trait SetBindGroup<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        );
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderPass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderPass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::ComputePass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::ComputePass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderBundleEncoder::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    /// A render pass, compute pass, or render bundle encoder for setting push constants.
+    /// Compute passes ignore the shader stages.
+    pub trait SetPushConstants {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        );
+    }
+    impl<'a> SetPushConstants for wgpu::RenderPass<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderPass::set_push_constants(self, stages, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::ComputePass<'a> {
+        fn set_push_constants(
+            &mut self,
+            _stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::ComputePass::set_push_constants(self, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::RenderBundleEncoder<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderBundleEncoder::set_push_constants(self, stages, offset, data);
+        }
+    }
+    /// A render pass or render bundle encoder for setting vertex buffers.
+    pub trait SetVertexBuffer<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>);
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderPass<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderPass::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderBundleEncoder::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+}
 pub mod bevy_pbr {
     #[allow(unused_imports)]
     use super::{_root, _root::*};
-    pub mod mesh_vertex_output {
+    pub mod mesh_bindings {
         #[allow(unused_imports)]
         use super::{_root, _root::*};
-        #[repr(C)]
-        #[derive(Debug, PartialEq, Clone, Copy)]
-        pub struct MeshVertexOutput {
-            pub world_position: glam::Vec4,
-            pub world_normal: glam::Vec3A,
+        pub mod bind_groups {
+            #[allow(unused_imports)]
+            use super::{_root, _root::*};
+            #[derive(Debug)]
+            pub struct BindGroup2(wgpu::BindGroup);
+            #[allow(non_snake_case)]
+            #[derive(Debug)]
+            pub struct BindGroupLayout2<'a> {
+                pub mesh: wgpu::BufferBinding<'a>,
+            }
+            const LAYOUT_DESCRIPTOR2: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                label: Some("bevy_pbr::mesh_bindings::BindGroup2::Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(144),
+                        },
+                        count: None,
+                    },
+                ],
+            };
+            /// The layout for creating bind groups with [BindGroup2::from_bindings_with_layout].
+            #[derive(Debug)]
+            pub struct Layout2(wgpu::BindGroupLayout);
+            impl Layout2 {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self(BindGroup2::get_bind_group_layout(device))
+                }
+            }
+            impl std::ops::Deref for Layout2 {
+                type Target = wgpu::BindGroupLayout;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+            impl BindGroup2 {
+                pub fn get_bind_group_layout(
+                    device: &wgpu::Device,
+                ) -> wgpu::BindGroupLayout {
+                    device.create_bind_group_layout(&LAYOUT_DESCRIPTOR2)
+                }
+                pub fn from_bindings(
+                    device: &wgpu::Device,
+                    bindings: BindGroupLayout2,
+                ) -> Self {
+                    Self::from_bindings_with_layout(
+                        device,
+                        &Layout2::new(device),
+                        bindings,
+                    )
+                }
+                pub fn from_bindings_with_layout(
+                    device: &wgpu::Device,
+                    layout: &Layout2,
+                    bindings: BindGroupLayout2,
+                ) -> Self {
+                    let bind_group = device
+                        .create_bind_group(
+                            &wgpu::BindGroupDescriptor {
+                                layout,
+                                entries: &[
+                                    wgpu::BindGroupEntry {
+                                        binding: 0,
+                                        resource: wgpu::BindingResource::Buffer(bindings.mesh),
+                                    },
+                                ],
+                                label: Some("bevy_pbr::mesh_bindings::BindGroup2"),
+                            },
+                        );
+                    Self(bind_group)
+                }
+                pub fn set<'a>(
+                    &'a self,
+                    pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                ) {
+                    pass.set_bind_group(2, &self.0, &[]);
+                }
+            }
         }
-        impl MeshVertexOutput {
-            pub fn new(world_position: glam::Vec4, world_normal: glam::Vec3A) -> Self {
-                Self {
-                    world_position,
-                    world_normal,
+    }
+    pub mod mesh_view_bindings {
+        #[allow(unused_imports)]
+        use super::{_root, _root::*};
+        pub mod bind_groups {
+            #[allow(unused_imports)]
+            use super::{_root, _root::*};
+            #[derive(Debug)]
+            pub struct BindGroup0(wgpu::BindGroup);
+            #[allow(non_snake_case)]
+            #[derive(Debug)]
+            pub struct BindGroupLayout0<'a> {
+                pub view: wgpu::BufferBinding<'a>,
+                pub lights: wgpu::BufferBinding<'a>,
+                pub point_lights: wgpu::BufferBinding<'a>,
+                pub cluster_light_index_lists: wgpu::BufferBinding<'a>,
+                pub cluster_offsets_and_counts: wgpu::BufferBinding<'a>,
+                pub point_shadow_textures: &'a wgpu::TextureView,
+                pub point_shadow_textures_sampler: &'a wgpu::Sampler,
+                pub directional_shadow_textures: &'a wgpu::TextureView,
+                pub directional_shadow_textures_sampler: &'a wgpu::Sampler,
+            }
+            const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                label: Some("bevy_pbr::mesh_view_bindings::BindGroup0::Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(416),
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(176),
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 6,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage {
+                                read_only: true,
+                            },
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(64),
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 7,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage {
+                                read_only: true,
+                            },
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(4),
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 8,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage {
+                                read_only: true,
+                            },
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(16),
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Depth,
+                            view_dimension: wgpu::TextureViewDimension::Cube,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(
+                            wgpu::SamplerBindingType::Comparison,
+                        ),
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 4,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Depth,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 5,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(
+                            wgpu::SamplerBindingType::Comparison,
+                        ),
+                        count: None,
+                    },
+                ],
+            };
+            /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+            #[derive(Debug)]
+            pub struct Layout0(wgpu::BindGroupLayout);
+            impl Layout0 {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self(BindGroup0::get_bind_group_layout(device))
+                }
+            }
+            impl std::ops::Deref for Layout0 {
+                type Target = wgpu::BindGroupLayout;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+            impl BindGroup0 {
+                pub fn get_bind_group_layout(
+                    device: &wgpu::Device,
+                ) -> wgpu::BindGroupLayout {
+                    device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+                }
+                pub fn from_bindings(
+                    device: &wgpu::Device,
+                    bindings: BindGroupLayout0,
+                ) -> Self {
+                    Self::from_bindings_with_layout(
+                        device,
+                        &Layout0::new(device),
+                        bindings,
+                    )
+                }
+                pub fn from_bindings_with_layout(
+                    device: &wgpu::Device,
+                    layout: &Layout0,
+                    bindings: BindGroupLayout0,
+                ) -> Self {
+                    let bind_group = device
+                        .create_bind_group(
+                            &wgpu::BindGroupDescriptor {
+                                layout,
+                                entries: &[
+                                    wgpu::BindGroupEntry {
+                                        binding: 0,
+                                        resource: wgpu::BindingResource::Buffer(bindings.view),
+                                    },
+                                    wgpu::BindGroupEntry {
+                                        binding: 1,
+                                        resource: wgpu::BindingResource::Buffer(bindings.lights),
+                                    },
+                                    wgpu::BindGroupEntry {
+                                        binding: 6,
+                                        resource: wgpu::BindingResource::Buffer(
+                                            bindings.point_lights,
+                                        ),
+                                    },
+                                    wgpu::BindGroupEntry {
+                                        binding: 7,
+                                        resource: wgpu::BindingResource::Buffer(
+                                            bindings.cluster_light_index_lists,
+                                        ),
+                                    },
+                                    wgpu::BindGroupEntry {
+                                        binding: 8,
+                                        resource: wgpu::BindingResource::Buffer(
+                                            bindings.cluster_offsets_and_counts,
+                                        ),
+                                    },
+                                    wgpu::BindGroupEntry {
+                                        binding: 2,
+                                        resource: wgpu::BindingResource::TextureView(
+                                            bindings.point_shadow_textures,
+                                        ),
+                                    },
+                                    wgpu::BindGroupEntry {
+                                        binding: 3,
+                                        resource: wgpu::BindingResource::Sampler(
+                                            bindings.point_shadow_textures_sampler,
+                                        ),
+                                    },
+                                    wgpu::BindGroupEntry {
+                                        binding: 4,
+                                        resource: wgpu::BindingResource::TextureView(
+                                            bindings.directional_shadow_textures,
+                                        ),
+                                    },
+                                    wgpu::BindGroupEntry {
+                                        binding: 5,
+                                        resource: wgpu::BindingResource::Sampler(
+                                            bindings.directional_shadow_textures_sampler,
+                                        ),
+                                    },
+                                ],
+                                label: Some("bevy_pbr::mesh_view_bindings::BindGroup0"),
+                            },
+                        );
+                    Self(bind_group)
+                }
+                pub fn set<'a>(
+                    &'a self,
+                    pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                ) {
+                    pass.set_bind_group(0, &self.0, &[]);
                 }
             }
         }
-        unsafe impl bytemuck::Zeroable for MeshVertexOutput {}
-        unsafe impl bytemuck::Pod for MeshVertexOutput {}
     }
     pub mod pbr {
         #[allow(unused_imports)]
         use super::{_root, _root::*};
+        pub mod bindings {
+            #[allow(unused_imports)]
+            use super::{_root, _root::*};
+            pub mod bind_groups {
+                #[allow(unused_imports)]
+                use super::{_root, _root::*};
+                #[derive(Debug)]
+                pub struct BindGroup1(wgpu::BindGroup);
+                #[allow(non_snake_case)]
+                #[derive(Debug)]
+                pub struct BindGroupLayout1<'a> {
+                    pub material: wgpu::BufferBinding<'a>,
+                }
+                const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                    label: Some("bevy_pbr::pbr::bindings::BindGroup1::Layout"),
+                    entries: &[
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: std::num::NonZeroU64::new(64),
+                            },
+                            count: None,
+                        },
+                    ],
+                };
+                /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+                #[derive(Debug)]
+                pub struct Layout1(wgpu::BindGroupLayout);
+                impl Layout1 {
+                    pub fn new(device: &wgpu::Device) -> Self {
+                        Self(BindGroup1::get_bind_group_layout(device))
+                    }
+                }
+                impl std::ops::Deref for Layout1 {
+                    type Target = wgpu::BindGroupLayout;
+                    fn deref(&self) -> &Self::Target {
+                        &self.0
+                    }
+                }
+                impl BindGroup1 {
+                    pub fn get_bind_group_layout(
+                        device: &wgpu::Device,
+                    ) -> wgpu::BindGroupLayout {
+                        device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
+                    }
+                    pub fn from_bindings(
+                        device: &wgpu::Device,
+                        bindings: BindGroupLayout1,
+                    ) -> Self {
+                        Self::from_bindings_with_layout(
+                            device,
+                            &Layout1::new(device),
+                            bindings,
+                        )
+                    }
+                    pub fn from_bindings_with_layout(
+                        device: &wgpu::Device,
+                        layout: &Layout1,
+                        bindings: BindGroupLayout1,
+                    ) -> Self {
+                        let bind_group = device
+                            .create_bind_group(
+                                &wgpu::BindGroupDescriptor {
+                                    layout,
+                                    entries: &[
+                                        wgpu::BindGroupEntry {
+                                            binding: 0,
+                                            resource: wgpu::BindingResource::Buffer(bindings.material),
+                                        },
+                                    ],
+                                    label: Some("bevy_pbr::pbr::bindings::BindGroup1"),
+                                },
+                            );
+                        Self(bind_group)
+                    }
+                    pub fn set<'a>(
+                        &'a self,
+                        pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                    ) {
+                        pass.set_bind_group(1, &self.0, &[]);
+                    }
+                }
+            }
+        }
         pub mod types {
             #[allow(unused_imports)]
             use super::{_root, _root::*};
@@ -132,6 +606,26 @@ pub mod bevy_pbr {
             pub const STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK: u32 = 128u32;
         }
     }
+    pub mod mesh_vertex_output {
+        #[allow(unused_imports)]
+        use super::{_root, _root::*};
+        #[repr(C)]
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        pub struct MeshVertexOutput {
+            pub world_position: glam::Vec4,
+            pub world_normal: glam::Vec3A,
+        }
+        impl MeshVertexOutput {
+            pub fn new(world_position: glam::Vec4, world_normal: glam::Vec3A) -> Self {
+                Self {
+                    world_position,
+                    world_normal,
+                }
+            }
+        }
+        unsafe impl bytemuck::Zeroable for MeshVertexOutput {}
+        unsafe impl bytemuck::Pod for MeshVertexOutput {}
+    }
     pub mod mesh_view_types {
         #[allow(unused_imports)]
         use super::{_root, _root::*};
@@ -568,295 +1062,30 @@ pub mod pbr {
     #[allow(unused_imports)]
     use super::{_root, _root::*};
     pub mod bind_groups {
-        #[derive(Debug)]
-        pub struct BindGroup0(wgpu::BindGroup);
-        #[allow(non_snake_case)]
-        #[derive(Debug)]
-        pub struct BindGroupLayout0<'a> {
-            pub view: wgpu::BufferBinding<'a>,
-            pub lights: wgpu::BufferBinding<'a>,
-            pub point_lights: wgpu::BufferBinding<'a>,
-            pub cluster_light_index_lists: wgpu::BufferBinding<'a>,
-            pub cluster_offsets_and_counts: wgpu::BufferBinding<'a>,
-            pub point_shadow_textures: &'a wgpu::TextureView,
-            pub point_shadow_textures_sampler: &'a wgpu::Sampler,
-            pub directional_shadow_textures: &'a wgpu::TextureView,
-            pub directional_shadow_textures_sampler: &'a wgpu::Sampler,
-        }
-        const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 6,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage {
-                            read_only: true,
-                        },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 7,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage {
-                            read_only: true,
-                        },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 8,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage {
-                            read_only: true,
-                        },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Depth,
-                        view_dimension: wgpu::TextureViewDimension::Cube,
-                        multisampled: false,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 3,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 4,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Depth,
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        multisampled: false,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 5,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
-                    count: None,
-                },
-            ],
+        pub use super::_root::bevy_pbr::mesh_view_bindings::bind_groups::{
+            BindGroup0, BindGroupLayout0, Layout0,
         };
-        impl BindGroup0 {
-            pub fn get_bind_group_layout(
-                device: &wgpu::Device,
-            ) -> wgpu::BindGroupLayout {
-                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
-            }
-            pub fn from_bindings(
-                device: &wgpu::Device,
-                bindings: BindGroupLayout0,
-            ) -> Self {
-                let bind_group_layout = device
-                    .create_bind_group_layout(&LAYOUT_DESCRIPTOR0);
-                let bind_group = device
-                    .create_bind_group(
-                        &wgpu::BindGroupDescriptor {
-                            layout: &bind_group_layout,
-                            entries: &[
-                                wgpu::BindGroupEntry {
-                                    binding: 0,
-                                    resource: wgpu::BindingResource::Buffer(bindings.view),
-                                },
-                                wgpu::BindGroupEntry {
-                                    binding: 1,
-                                    resource: wgpu::BindingResource::Buffer(bindings.lights),
-                                },
-                                wgpu::BindGroupEntry {
-                                    binding: 6,
-                                    resource: wgpu::BindingResource::Buffer(
-                                        bindings.point_lights,
-                                    ),
-                                },
-                                wgpu::BindGroupEntry {
-                                    binding: 7,
-                                    resource: wgpu::BindingResource::Buffer(
-                                        bindings.cluster_light_index_lists,
-                                    ),
-                                },
-                                wgpu::BindGroupEntry {
-                                    binding: 8,
-                                    resource: wgpu::BindingResource::Buffer(
-                                        bindings.cluster_offsets_and_counts,
-                                    ),
-                                },
-                                wgpu::BindGroupEntry {
-                                    binding: 2,
-                                    resource: wgpu::BindingResource::TextureView(
-                                        bindings.point_shadow_textures,
-                                    ),
-                                },
-                                wgpu::BindGroupEntry {
-                                    binding: 3,
-                                    resource: wgpu::BindingResource::Sampler(
-                                        bindings.point_shadow_textures_sampler,
-                                    ),
-                                },
-                                wgpu::BindGroupEntry {
-                                    binding: 4,
-                                    resource: wgpu::BindingResource::TextureView(
-                                        bindings.directional_shadow_textures,
-                                    ),
-                                },
-                                wgpu::BindGroupEntry {
-                                    binding: 5,
-                                    resource: wgpu::BindingResource::Sampler(
-                                        bindings.directional_shadow_textures_sampler,
-                                    ),
-                                },
-                            ],
-                            label: None,
-                        },
-                    );
-                Self(bind_group)
-            }
-            pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                render_pass.set_bind_group(0, &self.0, &[]);
-            }
-        }
-        #[derive(Debug)]
-        pub struct BindGroup1(wgpu::BindGroup);
-        #[allow(non_snake_case)]
-        #[derive(Debug)]
-        pub struct BindGroupLayout1<'a> {
-            pub material: wgpu::BufferBinding<'a>,
-        }
-        const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-            ],
+        pub use super::_root::bevy_pbr::pbr::bindings::bind_groups::{
+            BindGroup1, BindGroupLayout1, Layout1,
         };
-        impl BindGroup1 {
-            pub fn get_bind_group_layout(
-                device: &wgpu::Device,
-            ) -> wgpu::BindGroupLayout {
-                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
-            }
-            pub fn from_bindings(
-                device: &wgpu::Device,
-                bindings: BindGroupLayout1,
-            ) -> Self {
-                let bind_group_layout = device
-                    .create_bind_group_layout(&LAYOUT_DESCRIPTOR1);
-                let bind_group = device
-                    .create_bind_group(
-                        &wgpu::BindGroupDescriptor {
-                            layout: &bind_group_layout,
-                            entries: &[
-                                wgpu::BindGroupEntry {
-                                    binding: 0,
-                                    resource: wgpu::BindingResource::Buffer(bindings.material),
-                                },
-                            ],
-                            label: None,
-                        },
-                    );
-                Self(bind_group)
-            }
-            pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                render_pass.set_bind_group(1, &self.0, &[]);
-            }
-        }
-        #[derive(Debug)]
-        pub struct BindGroup2(wgpu::BindGroup);
-        #[allow(non_snake_case)]
+        pub use super::_root::bevy_pbr::mesh_bindings::bind_groups::{
+            BindGroup2, BindGroupLayout2, Layout2,
+        };
+        /// The bind group layouts for this module.
+        /// Create these once and reuse them for bind groups and pipeline layouts.
         #[derive(Debug)]
-        pub struct BindGroupLayout2<'a> {
-            pub mesh: wgpu::BufferBinding<'a>,
+        pub struct Layouts {
+            pub bind_group0: Layout0,
+            pub bind_group1: Layout1,
+            pub bind_group2: Layout2,
         }
-        const LAYOUT_DESCRIPTOR2: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-            ],
-        };
-        impl BindGroup2 {
-            pub fn get_bind_group_layout(
-                device: &wgpu::Device,
-            ) -> wgpu::BindGroupLayout {
-                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR2)
-            }
-            pub fn from_bindings(
-                device: &wgpu::Device,
-                bindings: BindGroupLayout2,
-            ) -> Self {
-                let bind_group_layout = device
-                    .create_bind_group_layout(&LAYOUT_DESCRIPTOR2);
-                let bind_group = device
-                    .create_bind_group(
-                        &wgpu::BindGroupDescriptor {
-                            layout: &bind_group_layout,
-                            entries: &[
-                                wgpu::BindGroupEntry {
-                                    binding: 0,
-                                    resource: wgpu::BindingResource::Buffer(bindings.mesh),
-                                },
-                            ],
-                            label: None,
-                        },
-                    );
-                Self(bind_group)
-            }
-            pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-                render_pass.set_bind_group(2, &self.0, &[]);
+        impl Layouts {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self {
+                    bind_group0: Layout0::new(device),
+                    bind_group1: Layout1::new(device),
+                    bind_group2: Layout2::new(device),
+                }
             }
         }
         #[derive(Debug, Copy, Clone)]
@@ -866,7 +1095,7 @@ pub mod pbr {
             pub bind_group2: &'a BindGroup2,
         }
         impl<'a> BindGroups<'a> {
-            pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
+            pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
                 self.bind_group0.set(pass);
                 self.bind_group1.set(pass);
                 self.bind_group2.set(pass);
@@ -874,7 +1103,7 @@ pub mod pbr {
         }
     }
     pub fn set_bind_groups<'a>(
-        pass: &mut wgpu::RenderPass<'a>,
+        pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
         bind_group0: &'a bind_groups::BindGroup0,
         bind_group1: &'a bind_groups::BindGroup1,
         bind_group2: &'a bind_groups::BindGroup2,
@@ -885,14 +1114,20 @@ pub mod pbr {
     }
     pub const ENTRY_FRAGMENT: &str = "fragment";
     pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+    }
+    pub fn create_pipeline_layout_with_layouts(
+        device: &wgpu::Device,
+        layouts: &bind_groups::Layouts,
+    ) -> wgpu::PipelineLayout {
         device
             .create_pipeline_layout(
                 &wgpu::PipelineLayoutDescriptor {
-                    label: None,
+                    label: Some("pbr::PipelineLayout"),
                     bind_group_layouts: &[
-                        &bind_groups::BindGroup0::get_bind_group_layout(device),
-                        &bind_groups::BindGroup1::get_bind_group_layout(device),
-                        &bind_groups::BindGroup2::get_bind_group_layout(device),
+                        &layouts.bind_group0,
+                        &layouts.bind_group1,
+                        &layouts.bind_group2,
                     ],
                     push_constant_ranges: &[],
                 },
@@ -902,7 +1137,7 @@ pub mod pbr {
         let source = std::borrow::Cow::Borrowed(SHADER_STRING);
         device
             .create_shader_module(wgpu::ShaderModuleDescriptor {
-                label: None,
+                label: Some("pbr::ShaderModule"),
                 source: wgpu::ShaderSource::Wgsl(source),
             })
     }
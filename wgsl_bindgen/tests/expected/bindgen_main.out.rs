@@ -12,116 +12,283 @@ mod _root {
         assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
     };
 }
-pub mod main {
+pub mod pass_ext {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    /// A render pass, compute pass, or render bundle encoder for setting bind groups.
+    pub trait SetBindGroup<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        );
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderPass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderPass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::ComputePass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::ComputePass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderBundleEncoder::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    /// A render pass, compute pass, or render bundle encoder for setting push constants.
+    /// Compute passes ignore the shader stages.
+    pub trait SetPushConstants {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        );
+    }
+    impl<'a> SetPushConstants for wgpu::RenderPass<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderPass::set_push_constants(self, stages, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::ComputePass<'a> {
+        fn set_push_constants(
+            &mut self,
+            _stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::ComputePass::set_push_constants(self, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::RenderBundleEncoder<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderBundleEncoder::set_push_constants(self, stages, offset, data);
+        }
+    }
+    /// A render pass or render bundle encoder for setting vertex buffers.
+    pub trait SetVertexBuffer<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>);
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderPass<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderPass::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderBundleEncoder::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+}
+pub mod bindings {
     #[allow(unused_imports)]
     use super::{_root, _root::*};
     pub mod bind_groups {
+        #[allow(unused_imports)]
+        use super::{_root, _root::*};
         #[derive(Debug)]
-        pub struct BindGroup0(wgpu::BindGroup);
+        pub struct BindGroup1(wgpu::BindGroup);
         #[allow(non_snake_case)]
         #[derive(Debug)]
-        pub struct BindGroupLayout0<'a> {
-            pub buffer: wgpu::BufferBinding<'a>,
+        pub struct BindGroupLayout1<'a> {
+            pub ONE: wgpu::BufferBinding<'a>,
         }
-        const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
+        const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some("bindings::BindGroup1::Layout"),
             entries: &[
                 wgpu::BindGroupLayoutEntry {
-                    binding: 0,
+                    binding: 11,
                     visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage {
-                            read_only: false,
-                        },
+                        ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(4),
                     },
                     count: None,
                 },
             ],
         };
-        impl BindGroup0 {
+        /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout1(wgpu::BindGroupLayout);
+        impl Layout1 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup1::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout1 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        impl BindGroup1 {
             pub fn get_bind_group_layout(
                 device: &wgpu::Device,
             ) -> wgpu::BindGroupLayout {
-                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
             }
             pub fn from_bindings(
                 device: &wgpu::Device,
-                bindings: BindGroupLayout0,
+                bindings: BindGroupLayout1,
+            ) -> Self {
+                Self::from_bindings_with_layout(device, &Layout1::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout1,
+                bindings: BindGroupLayout1,
             ) -> Self {
-                let bind_group_layout = device
-                    .create_bind_group_layout(&LAYOUT_DESCRIPTOR0);
                 let bind_group = device
                     .create_bind_group(
                         &wgpu::BindGroupDescriptor {
-                            layout: &bind_group_layout,
+                            layout,
                             entries: &[
                                 wgpu::BindGroupEntry {
-                                    binding: 0,
-                                    resource: wgpu::BindingResource::Buffer(bindings.buffer),
+                                    binding: 11,
+                                    resource: wgpu::BindingResource::Buffer(bindings.ONE),
                                 },
                             ],
-                            label: None,
+                            label: Some("bindings::BindGroup1"),
                         },
                     );
                 Self(bind_group)
             }
-            pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
-                render_pass.set_bind_group(0, &self.0, &[]);
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(1, &self.0, &[]);
             }
         }
+    }
+}
+pub mod main {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    pub mod bind_groups {
         #[derive(Debug)]
-        pub struct BindGroup1(wgpu::BindGroup);
+        pub struct BindGroup0(wgpu::BindGroup);
         #[allow(non_snake_case)]
         #[derive(Debug)]
-        pub struct BindGroupLayout1<'a> {
-            pub ONE: wgpu::BufferBinding<'a>,
+        pub struct BindGroupLayout0<'a> {
+            pub buffer: wgpu::BufferBinding<'a>,
         }
-        const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
+        const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some("main::BindGroup0::Layout"),
             entries: &[
                 wgpu::BindGroupLayoutEntry {
-                    binding: 11,
+                    binding: 0,
                     visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
+                        ty: wgpu::BufferBindingType::Storage {
+                            read_only: false,
+                        },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(4),
                     },
                     count: None,
                 },
             ],
         };
-        impl BindGroup1 {
+        /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout0(wgpu::BindGroupLayout);
+        impl Layout0 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup0::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout0 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        impl BindGroup0 {
             pub fn get_bind_group_layout(
                 device: &wgpu::Device,
             ) -> wgpu::BindGroupLayout {
-                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
+                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
             }
             pub fn from_bindings(
                 device: &wgpu::Device,
-                bindings: BindGroupLayout1,
+                bindings: BindGroupLayout0,
+            ) -> Self {
+                Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout0,
+                bindings: BindGroupLayout0,
             ) -> Self {
-                let bind_group_layout = device
-                    .create_bind_group_layout(&LAYOUT_DESCRIPTOR1);
                 let bind_group = device
                     .create_bind_group(
                         &wgpu::BindGroupDescriptor {
-                            layout: &bind_group_layout,
+                            layout,
                             entries: &[
                                 wgpu::BindGroupEntry {
-                                    binding: 11,
-                                    resource: wgpu::BindingResource::Buffer(bindings.ONE),
+                                    binding: 0,
+                                    resource: wgpu::BindingResource::Buffer(bindings.buffer),
                                 },
                             ],
-                            label: None,
+                            label: Some("main::BindGroup0"),
                         },
                     );
                 Self(bind_group)
             }
-            pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
-                render_pass.set_bind_group(1, &self.0, &[]);
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(0, &self.0, &[]);
+            }
+        }
+        pub use super::_root::bindings::bind_groups::{
+            BindGroup1, BindGroupLayout1, Layout1,
+        };
+        /// The bind group layouts for this module.
+        /// Create these once and reuse them for bind groups and pipeline layouts.
+        #[derive(Debug)]
+        pub struct Layouts {
+            pub bind_group0: Layout0,
+            pub bind_group1: Layout1,
+        }
+        impl Layouts {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self {
+                    bind_group0: Layout0::new(device),
+                    bind_group1: Layout1::new(device),
+                }
             }
         }
         #[derive(Debug, Copy, Clone)]
@@ -130,14 +297,14 @@ pub mod main {
             pub bind_group1: &'a BindGroup1,
         }
         impl<'a> BindGroups<'a> {
-            pub fn set(&self, pass: &mut wgpu::ComputePass<'a>) {
+            pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
                 self.bind_group0.set(pass);
                 self.bind_group1.set(pass);
             }
         }
     }
     pub fn set_bind_groups<'a>(
-        pass: &mut wgpu::ComputePass<'a>,
+        pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
         bind_group0: &'a bind_groups::BindGroup0,
         bind_group1: &'a bind_groups::BindGroup1,
     ) {
@@ -152,7 +319,7 @@ pub mod main {
             device
                 .create_compute_pipeline(
                     &wgpu::ComputePipelineDescriptor {
-                        label: Some("Compute Pipeline main"),
+                        label: Some("main::ComputePipeline::main"),
                         layout: Some(&layout),
                         module: &module,
                         entry_point: "main",
@@ -162,14 +329,17 @@ pub mod main {
     }
     pub const ENTRY_MAIN: &str = "main";
     pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+    }
+    pub fn create_pipeline_layout_with_layouts(
+        device: &wgpu::Device,
+        layouts: &bind_groups::Layouts,
+    ) -> wgpu::PipelineLayout {
         device
             .create_pipeline_layout(
                 &wgpu::PipelineLayoutDescriptor {
-                    label: None,
-                    bind_group_layouts: &[
-                        &bind_groups::BindGroup0::get_bind_group_layout(device),
-                        &bind_groups::BindGroup1::get_bind_group_layout(device),
-                    ],
+                    label: Some("main::PipelineLayout"),
+                    bind_group_layouts: &[&layouts.bind_group0, &layouts.bind_group1],
                     push_constant_ranges: &[],
                 },
             )
@@ -178,7 +348,7 @@ pub mod main {
         let source = std::borrow::Cow::Borrowed(SHADER_STRING);
         device
             .create_shader_module(wgpu::ShaderModuleDescriptor {
-                label: None,
+                label: Some("main::ShaderModule"),
                 source: wgpu::ShaderSource::Wgsl(source),
             })
     }
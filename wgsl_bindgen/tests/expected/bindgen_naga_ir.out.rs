@@ -0,0 +1,279 @@
+#[allow(unused)]
+mod _root {
+    pub use super::*;
+    const _: () = {
+        assert!(std::mem::size_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat3A > () == 48);
+        assert!(std::mem::align_of:: < glam::Mat3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat4 > () == 64);
+        assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
+    };
+}
+pub mod pass_ext {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    /// A render pass, compute pass, or render bundle encoder for setting bind groups.
+    pub trait SetBindGroup<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        );
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderPass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderPass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::ComputePass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::ComputePass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderBundleEncoder::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    /// A render pass, compute pass, or render bundle encoder for setting push constants.
+    /// Compute passes ignore the shader stages.
+    pub trait SetPushConstants {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        );
+    }
+    impl<'a> SetPushConstants for wgpu::RenderPass<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderPass::set_push_constants(self, stages, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::ComputePass<'a> {
+        fn set_push_constants(
+            &mut self,
+            _stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::ComputePass::set_push_constants(self, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::RenderBundleEncoder<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderBundleEncoder::set_push_constants(self, stages, offset, data);
+        }
+    }
+    /// A render pass or render bundle encoder for setting vertex buffers.
+    pub trait SetVertexBuffer<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>);
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderPass<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderPass::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderBundleEncoder::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+}
+pub mod spirv {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Uniforms {
+        /// size: 16, offset: 0x0, type: `vec4<f32>`
+        pub color: glam::Vec4,
+    }
+    impl Uniforms {
+        pub fn new(color: glam::Vec4) -> Self {
+            Self { color }
+        }
+    }
+    unsafe impl bytemuck::Zeroable for Uniforms {}
+    unsafe impl bytemuck::Pod for Uniforms {}
+    const _: () = {
+        assert!(std::mem::offset_of!(Uniforms, color) == 0);
+        assert!(std::mem::size_of:: < Uniforms > () == 16);
+    };
+    pub mod bind_groups {
+        #[derive(Debug)]
+        pub struct BindGroup0(wgpu::BindGroup);
+        #[allow(non_snake_case)]
+        #[derive(Debug)]
+        pub struct BindGroupLayout0<'a> {
+            pub uniforms: wgpu::BufferBinding<'a>,
+        }
+        const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some("spirv::BindGroup0::Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(16),
+                    },
+                    count: None,
+                },
+            ],
+        };
+        /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout0(wgpu::BindGroupLayout);
+        impl Layout0 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup0::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout0 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        impl BindGroup0 {
+            pub fn get_bind_group_layout(
+                device: &wgpu::Device,
+            ) -> wgpu::BindGroupLayout {
+                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+            }
+            pub fn from_bindings(
+                device: &wgpu::Device,
+                bindings: BindGroupLayout0,
+            ) -> Self {
+                Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout0,
+                bindings: BindGroupLayout0,
+            ) -> Self {
+                let bind_group = device
+                    .create_bind_group(
+                        &wgpu::BindGroupDescriptor {
+                            layout,
+                            entries: &[
+                                wgpu::BindGroupEntry {
+                                    binding: 0,
+                                    resource: wgpu::BindingResource::Buffer(bindings.uniforms),
+                                },
+                            ],
+                            label: Some("spirv::BindGroup0"),
+                        },
+                    );
+                Self(bind_group)
+            }
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(0, &self.0, &[]);
+            }
+        }
+        /// The bind group layouts for this module.
+        /// Create these once and reuse them for bind groups and pipeline layouts.
+        #[derive(Debug)]
+        pub struct Layouts {
+            pub bind_group0: Layout0,
+        }
+        impl Layouts {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self {
+                    bind_group0: Layout0::new(device),
+                }
+            }
+        }
+        #[derive(Debug, Copy, Clone)]
+        pub struct BindGroups<'a> {
+            pub bind_group0: &'a BindGroup0,
+        }
+        impl<'a> BindGroups<'a> {
+            pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                self.bind_group0.set(pass);
+            }
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+        bind_group0: &'a bind_groups::BindGroup0,
+    ) {
+        bind_group0.set(pass);
+    }
+    pub const ENTRY_FS_MAIN: &str = "fs_main";
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+    }
+    pub fn create_pipeline_layout_with_layouts(
+        device: &wgpu::Device,
+        layouts: &bind_groups::Layouts,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("spirv::PipelineLayout"),
+                    bind_group_layouts: &[&layouts.bind_group0],
+                    push_constant_ranges: &[],
+                },
+            )
+    }
+    /// The naga version used to serialize [SHADER_NAGA_IR].
+    pub const SHADER_NAGA_VERSION: &str = "0.19";
+    /// Deserializes the module included at build time.
+    /// This requires a `bincode` 1.x dependency and the `deserialize` feature of naga.
+    ///
+    /// # Panics
+    /// The module can only be deserialized by the naga version in [SHADER_NAGA_VERSION],
+    /// so this panics if wgpu uses a different naga version.
+    pub fn naga_module() -> wgpu::naga::Module {
+        bincode::deserialize(SHADER_NAGA_IR)
+            .unwrap_or_else(|e| {
+                panic!(
+                    "failed to deserialize the naga module from naga {SHADER_NAGA_VERSION}, which must match the naga version of wgpu: {e}"
+                )
+            })
+    }
+    pub fn create_shader_module(device: &wgpu::Device) -> wgpu::ShaderModule {
+        let source = std::borrow::Cow::Owned(naga_module());
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("spirv::ShaderModule"),
+                source: wgpu::ShaderSource::Naga(source),
+            })
+    }
+    const SHADER_NAGA_IR: &[u8] = b"\x02\0\0\0\0\0\0\0\0\x01\0\0\0\x02\0\0\0\x02\0\0\0\x04\x01\x08\0\0\0\0\0\0\0Uniforms\x07\0\0\0\x01\0\0\0\0\0\0\0\x01\x05\0\0\0\0\0\0\0color\x01\0\0\0\0\0\0\0\0\x10\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0\x01\x08\0\0\0\0\0\0\0uniforms\x03\0\0\0\x01\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0\x07\0\0\0\0\0\0\0fs_main\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\x07\0\0\0\0\0\0\0fs_main\0\0\0\0\0\0\0\0\x01\x01\0\0\0\x01\x01\0\0\0\0\0\0\0\0\x01\0\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\x03\0\0\0\0\0\0\0\t\0\0\0\x01\0\0\0\x05\0\0\0\x01\0\0\0\0\0\0\0\x0B\0\0\0\x02\0\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0\x03\0\0\0\x07\0\0\0\x01\x03\0\0\0";
+}
@@ -0,0 +1,1332 @@
+#[allow(unused)]
+mod _root {
+    pub use super::*;
+    const _: () = {
+        assert!(std::mem::size_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat3A > () == 48);
+        assert!(std::mem::align_of:: < glam::Mat3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat4 > () == 64);
+        assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
+    };
+}
+pub mod pass_ext {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    /// A render pass, compute pass, or render bundle encoder for setting bind groups.
+    pub trait SetBindGroup<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        );
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderPass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderPass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::ComputePass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::ComputePass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderBundleEncoder::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    /// A render pass, compute pass, or render bundle encoder for setting push constants.
+    /// Compute passes ignore the shader stages.
+    pub trait SetPushConstants {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        );
+    }
+    impl<'a> SetPushConstants for wgpu::RenderPass<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderPass::set_push_constants(self, stages, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::ComputePass<'a> {
+        fn set_push_constants(
+            &mut self,
+            _stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::ComputePass::set_push_constants(self, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::RenderBundleEncoder<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderBundleEncoder::set_push_constants(self, stages, offset, data);
+        }
+    }
+    /// A render pass or render bundle encoder for setting vertex buffers.
+    pub trait SetVertexBuffer<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>);
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderPass<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderPass::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderBundleEncoder::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+}
+pub mod module_layout {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    use wgpu::naga;
+    /// The type of a binding as it is used by the shader.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BindingKind {
+        Uniform { size: u32 },
+        Storage { read_only: bool, size: u32 },
+        Texture {
+            dim: naga::ImageDimension,
+            arrayed: bool,
+            sample_kind: naga::ScalarKind,
+            multisampled: bool,
+        },
+        DepthTexture { dim: naga::ImageDimension, arrayed: bool, multisampled: bool },
+        StorageTexture {
+            dim: naga::ImageDimension,
+            arrayed: bool,
+            format: naga::StorageFormat,
+            read: bool,
+            write: bool,
+        },
+        Sampler { comparison: bool },
+        AccelerationStructure,
+    }
+    /// The number of resources in a binding.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BindingCount {
+        Single,
+        /// A `binding_array` with a fixed size.
+        Array(u32),
+        /// A `binding_array` without a size.
+        RuntimeArray,
+    }
+    /// Describes the binding of a global variable.
+    /// Returns `None` for types that can't be used in bindings.
+    pub fn binding_kind(
+        module: &naga::Module,
+        global: &naga::GlobalVariable,
+    ) -> Option<(BindingKind, BindingCount)> {
+        let (inner, count) = match &module.types[global.ty].inner {
+            naga::TypeInner::BindingArray { base, size } => {
+                let count = match size {
+                    naga::ArraySize::Constant(size) => BindingCount::Array(size.get()),
+                    naga::ArraySize::Dynamic => BindingCount::RuntimeArray,
+                };
+                (&module.types[*base].inner, count)
+            }
+            inner => (inner, BindingCount::Single),
+        };
+        let kind = match (global.space, inner) {
+            (naga::AddressSpace::Uniform, _) => {
+                BindingKind::Uniform {
+                    size: inner.size(module.to_ctx()),
+                }
+            }
+            (naga::AddressSpace::Storage { access }, _) => {
+                BindingKind::Storage {
+                    read_only: !access.contains(naga::StorageAccess::STORE),
+                    size: inner.size(module.to_ctx()),
+                }
+            }
+            (_, naga::TypeInner::Image { dim, arrayed, class }) => {
+                match *class {
+                    naga::ImageClass::Sampled { kind, multi } => {
+                        BindingKind::Texture {
+                            dim: *dim,
+                            arrayed: *arrayed,
+                            sample_kind: kind,
+                            multisampled: multi,
+                        }
+                    }
+                    naga::ImageClass::Depth { multi } => {
+                        BindingKind::DepthTexture {
+                            dim: *dim,
+                            arrayed: *arrayed,
+                            multisampled: multi,
+                        }
+                    }
+                    naga::ImageClass::Storage { format, access } => {
+                        BindingKind::StorageTexture {
+                            dim: *dim,
+                            arrayed: *arrayed,
+                            format,
+                            read: access.contains(naga::StorageAccess::LOAD),
+                            write: access.contains(naga::StorageAccess::STORE),
+                        }
+                    }
+                }
+            }
+            (_, naga::TypeInner::Sampler { comparison }) => {
+                BindingKind::Sampler {
+                    comparison: *comparison,
+                }
+            }
+            (_, naga::TypeInner::AccelerationStructure) => {
+                BindingKind::AccelerationStructure
+            }
+            _ => return None,
+        };
+        Some((kind, count))
+    }
+    /// A binding recorded when generating the bindings.
+    #[derive(Debug)]
+    pub struct BindingLayout {
+        pub group: u32,
+        pub binding: u32,
+        pub name: &'static str,
+        pub kind: BindingKind,
+        pub count: BindingCount,
+    }
+    /// A struct recorded when generating the bindings.
+    #[derive(Debug)]
+    pub struct StructLayout {
+        pub name: &'static str,
+        /// The name in the composed module, which may be mangled by naga_oil.
+        pub naga_name: &'static str,
+        pub size: u32,
+        pub members: &'static [MemberLayout],
+    }
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct MemberLayout {
+        pub name: &'static str,
+        pub offset: u32,
+        pub size: u32,
+    }
+    /// A difference between a module and the layouts of the generated code.
+    #[derive(Debug)]
+    pub enum LayoutError {
+        MissingBinding { group: u32, binding: u32, name: &'static str },
+        UnexpectedBinding { group: u32, binding: u32, name: Option<String> },
+        BindingType {
+            group: u32,
+            binding: u32,
+            name: &'static str,
+            expected: (BindingKind, BindingCount),
+            actual: Option<(BindingKind, BindingCount)>,
+        },
+        MissingStruct { name: &'static str },
+        StructSize { name: &'static str, expected: u32, actual: u32 },
+        StructMembers { name: &'static str, expected: usize, actual: usize },
+        MemberLayout {
+            name: &'static str,
+            member: &'static str,
+            expected_offset: u32,
+            expected_size: u32,
+            actual: Option<(u32, u32)>,
+        },
+    }
+    impl std::fmt::Display for LayoutError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::MissingBinding { group, binding, name } => {
+                    write!(
+                        f,
+                        "binding `{name}` at @group({group}) @binding({binding}) is missing from the module"
+                    )
+                }
+                Self::UnexpectedBinding { group, binding, name } => {
+                    write!(
+                        f,
+                        "binding `{}` at @group({group}) @binding({binding}) is not in the generated bindings",
+                        name.as_deref().unwrap_or_default()
+                    )
+                }
+                Self::BindingType { group, binding, name, expected, actual } => {
+                    write!(
+                        f,
+                        "binding `{name}` at @group({group}) @binding({binding}) changed from {expected:?} to "
+                    )?;
+                    match actual {
+                        Some(actual) => write!(f, "{actual:?}"),
+                        None => write!(f, "an unsupported type"),
+                    }
+                }
+                Self::MissingStruct { name } => {
+                    write!(f, "struct `{name}` is missing from the module")
+                }
+                Self::StructSize { name, expected, actual } => {
+                    write!(
+                        f,
+                        "struct `{name}` changed size from {expected} to {actual} bytes"
+                    )
+                }
+                Self::StructMembers { name, expected, actual } => {
+                    write!(
+                        f, "struct `{name}` changed from {expected} to {actual} members"
+                    )
+                }
+                Self::MemberLayout {
+                    name,
+                    member,
+                    expected_offset,
+                    expected_size,
+                    actual,
+                } => {
+                    write!(
+                        f,
+                        "member `{name}.{member}` changed from offset {expected_offset} and size {expected_size} to "
+                    )?;
+                    match actual {
+                        Some((offset, size)) => {
+                            write!(f, "offset {offset} and size {size}")
+                        }
+                        None => write!(f, "missing"),
+                    }
+                }
+            }
+        }
+    }
+    impl std::error::Error for LayoutError {}
+    pub fn verify_module_layout(
+        module: &wgpu::naga::Module,
+        bindings: &[BindingLayout],
+        structs: &[StructLayout],
+    ) -> Result<(), LayoutError> {
+        for (_, global) in module.global_variables.iter() {
+            let Some(resource) = &global.binding else {
+                continue;
+            };
+            let (group, binding) = (resource.group, resource.binding);
+            let Some(expected) = bindings
+                .iter()
+                .find(|b| b.group == group && b.binding == binding) else {
+                return Err(LayoutError::UnexpectedBinding {
+                    group,
+                    binding,
+                    name: global.name.clone(),
+                });
+            };
+            let actual = binding_kind(module, global);
+            if actual != Some((expected.kind, expected.count)) {
+                return Err(LayoutError::BindingType {
+                    group,
+                    binding,
+                    name: expected.name,
+                    expected: (expected.kind, expected.count),
+                    actual,
+                });
+            }
+        }
+        for expected in bindings {
+            let found = module
+                .global_variables
+                .iter()
+                .any(|(_, g)| {
+                    g.binding
+                        .as_ref()
+                        .is_some_and(|b| {
+                            b.group == expected.group && b.binding == expected.binding
+                        })
+                });
+            if !found {
+                return Err(LayoutError::MissingBinding {
+                    group: expected.group,
+                    binding: expected.binding,
+                    name: expected.name,
+                });
+            }
+        }
+        for expected in structs {
+            let inner = module
+                .types
+                .iter()
+                .find(|(_, ty)| ty.name.as_deref() == Some(expected.naga_name))
+                .map(|(_, ty)| &ty.inner);
+            let Some(wgpu::naga::TypeInner::Struct { members, span }) = inner else {
+                return Err(LayoutError::MissingStruct {
+                    name: expected.name,
+                });
+            };
+            if *span != expected.size {
+                return Err(LayoutError::StructSize {
+                    name: expected.name,
+                    expected: expected.size,
+                    actual: *span,
+                });
+            }
+            if members.len() != expected.members.len() {
+                return Err(LayoutError::StructMembers {
+                    name: expected.name,
+                    expected: expected.members.len(),
+                    actual: members.len(),
+                });
+            }
+            for member in expected.members {
+                let actual = members
+                    .iter()
+                    .find(|m| m.name.as_deref() == Some(member.name))
+                    .map(|m| (m.offset, module.types[m.ty].inner.size(module.to_ctx())));
+                if actual != Some((member.offset, member.size)) {
+                    return Err(LayoutError::MemberLayout {
+                        name: expected.name,
+                        member: member.name,
+                        expected_offset: member.offset,
+                        expected_size: member.size,
+                        actual,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+pub mod mesh {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct VertexInput {
+        pub position: glam::Vec3A,
+        pub joints: [u32; 4],
+    }
+    impl VertexInput {
+        pub fn new(position: glam::Vec3A, joints: [u32; 4]) -> Self {
+            Self { position, joints }
+        }
+    }
+    unsafe impl bytemuck::Zeroable for VertexInput {}
+    unsafe impl bytemuck::Pod for VertexInput {}
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Camera {
+        /// size: 64, offset: 0x0, type: `mat4x4<f32>`
+        pub view_proj: glam::Mat4,
+    }
+    impl Camera {
+        pub fn new(view_proj: glam::Mat4) -> Self {
+            Self { view_proj }
+        }
+    }
+    unsafe impl bytemuck::Zeroable for Camera {}
+    unsafe impl bytemuck::Pod for Camera {}
+    const _: () = {
+        assert!(std::mem::offset_of!(Camera, view_proj) == 0);
+        assert!(std::mem::size_of:: < Camera > () == 64);
+    };
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Mesh {
+        /// size: 64, offset: 0x0, type: `mat4x4<f32>`
+        pub model: glam::Mat4,
+    }
+    impl Mesh {
+        pub fn new(model: glam::Mat4) -> Self {
+            Self { model }
+        }
+    }
+    unsafe impl bytemuck::Zeroable for Mesh {}
+    unsafe impl bytemuck::Pod for Mesh {}
+    const _: () = {
+        assert!(std::mem::offset_of!(Mesh, model) == 0);
+        assert!(std::mem::size_of:: < Mesh > () == 64);
+    };
+    pub mod bind_groups {
+        #[derive(Debug)]
+        pub struct BindGroup0(wgpu::BindGroup);
+        #[allow(non_snake_case)]
+        #[derive(Debug)]
+        pub struct BindGroupLayout0<'a> {
+            pub camera: wgpu::BufferBinding<'a>,
+        }
+        const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some("mesh::BindGroup0::Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(64),
+                    },
+                    count: None,
+                },
+            ],
+        };
+        /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout0(wgpu::BindGroupLayout);
+        impl Layout0 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup0::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout0 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        impl BindGroup0 {
+            pub fn get_bind_group_layout(
+                device: &wgpu::Device,
+            ) -> wgpu::BindGroupLayout {
+                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+            }
+            pub fn from_bindings(
+                device: &wgpu::Device,
+                bindings: BindGroupLayout0,
+            ) -> Self {
+                Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout0,
+                bindings: BindGroupLayout0,
+            ) -> Self {
+                let bind_group = device
+                    .create_bind_group(
+                        &wgpu::BindGroupDescriptor {
+                            layout,
+                            entries: &[
+                                wgpu::BindGroupEntry {
+                                    binding: 0,
+                                    resource: wgpu::BindingResource::Buffer(bindings.camera),
+                                },
+                            ],
+                            label: Some("mesh::BindGroup0"),
+                        },
+                    );
+                Self(bind_group)
+            }
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(0, &self.0, &[]);
+            }
+        }
+        #[derive(Debug)]
+        pub struct BindGroup1(wgpu::BindGroup);
+        #[allow(non_snake_case)]
+        #[derive(Debug)]
+        pub struct BindGroupLayout1<'a> {
+            pub mesh: wgpu::BufferBinding<'a>,
+        }
+        const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some("mesh::BindGroup1::Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(64),
+                    },
+                    count: None,
+                },
+            ],
+        };
+        /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout1(wgpu::BindGroupLayout);
+        impl Layout1 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup1::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout1 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        impl BindGroup1 {
+            pub fn get_bind_group_layout(
+                device: &wgpu::Device,
+            ) -> wgpu::BindGroupLayout {
+                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
+            }
+            pub fn from_bindings(
+                device: &wgpu::Device,
+                bindings: BindGroupLayout1,
+            ) -> Self {
+                Self::from_bindings_with_layout(device, &Layout1::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout1,
+                bindings: BindGroupLayout1,
+            ) -> Self {
+                let bind_group = device
+                    .create_bind_group(
+                        &wgpu::BindGroupDescriptor {
+                            layout,
+                            entries: &[
+                                wgpu::BindGroupEntry {
+                                    binding: 0,
+                                    resource: wgpu::BindingResource::Buffer(bindings.mesh),
+                                },
+                            ],
+                            label: Some("mesh::BindGroup1"),
+                        },
+                    );
+                Self(bind_group)
+            }
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(1, &self.0, &[]);
+            }
+        }
+        /// The bind group layouts for this module.
+        /// Create these once and reuse them for bind groups and pipeline layouts.
+        #[derive(Debug)]
+        pub struct Layouts {
+            pub bind_group0: Layout0,
+            pub bind_group1: Layout1,
+        }
+        impl Layouts {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self {
+                    bind_group0: Layout0::new(device),
+                    bind_group1: Layout1::new(device),
+                }
+            }
+        }
+        #[derive(Debug, Copy, Clone)]
+        pub struct BindGroups<'a> {
+            pub bind_group0: &'a BindGroup0,
+            pub bind_group1: &'a BindGroup1,
+        }
+        impl<'a> BindGroups<'a> {
+            pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                self.bind_group0.set(pass);
+                self.bind_group1.set(pass);
+            }
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+        bind_group0: &'a bind_groups::BindGroup0,
+        bind_group1: &'a bind_groups::BindGroup1,
+    ) {
+        bind_group0.set(pass);
+        bind_group1.set(pass);
+    }
+    impl VertexInput {
+        pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 2] = [
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x3,
+                offset: std::mem::offset_of!(VertexInput, position) as u64,
+                shader_location: 0,
+            },
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Uint32x4,
+                offset: std::mem::offset_of!(VertexInput, joints) as u64,
+                shader_location: 1,
+            },
+        ];
+        pub const fn vertex_buffer_layout(
+            step_mode: wgpu::VertexStepMode,
+        ) -> wgpu::VertexBufferLayout<'static> {
+            wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<VertexInput>() as u64,
+                step_mode,
+                attributes: &VertexInput::VERTEX_ATTRIBUTES,
+            }
+        }
+    }
+    pub const ENTRY_VS_MAIN: &str = "vs_main";
+    #[derive(Debug)]
+    pub struct VertexEntry<const N: usize> {
+        entry_point: &'static str,
+        buffers: [wgpu::VertexBufferLayout<'static>; N],
+    }
+    pub fn vertex_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a VertexEntry<N>,
+    ) -> wgpu::VertexState<'a> {
+        wgpu::VertexState {
+            module,
+            entry_point: entry.entry_point,
+            buffers: &entry.buffers,
+        }
+    }
+    pub fn vs_main_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<1> {
+        VertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
+        }
+    }
+    pub fn set_vertex_buffers<'a>(
+        pass: &mut impl _root::pass_ext::SetVertexBuffer<'a>,
+        vertex_input: wgpu::BufferSlice<'a>,
+    ) {
+        pass.set_vertex_buffer(0, vertex_input);
+    }
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+    }
+    pub fn create_pipeline_layout_with_layouts(
+        device: &wgpu::Device,
+        layouts: &bind_groups::Layouts,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("mesh::PipelineLayout"),
+                    bind_group_layouts: &[&layouts.bind_group0, &layouts.bind_group1],
+                    push_constant_ranges: &[],
+                },
+            )
+    }
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct ShaderDefs {
+        pub skinned: bool,
+    }
+    impl ShaderDefs {
+        pub fn to_shader_defs(
+            &self,
+        ) -> std::collections::HashMap<String, naga_oil::compose::ShaderDefValue> {
+            let mut shader_defs = std::collections::HashMap::new();
+            if self.skinned {
+                shader_defs
+                    .insert(
+                        "SKINNED".to_owned(),
+                        naga_oil::compose::ShaderDefValue::Bool(true),
+                    );
+            }
+            shader_defs
+        }
+    }
+    pub fn init_composer() -> naga_oil::compose::Composer {
+        #[allow(unused_mut)]
+        let mut composer = naga_oil::compose::Composer::default()
+            .with_capabilities(wgpu::naga::valid::Capabilities::all());
+        composer
+    }
+    pub fn make_naga_module(
+        composer: &mut naga_oil::compose::Composer,
+        shader_defs: &ShaderDefs,
+    ) -> wgpu::naga::Module {
+        composer
+            .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                source: include_str!("../shaders/permutations/mesh.wgsl"),
+                file_path: "../shaders/permutations/mesh.wgsl",
+                shader_defs: shader_defs.to_shader_defs(),
+                ..Default::default()
+            })
+            .expect("failed to build naga module")
+    }
+    pub fn naga_module_to_string(module: &wgpu::naga::Module) -> String {
+        let info = wgpu::naga::valid::Validator::new(
+                wgpu::naga::valid::ValidationFlags::empty(),
+                wgpu::naga::valid::Capabilities::all(),
+            )
+            .validate(&module);
+        let info = info.unwrap();
+        wgpu::naga::back::wgsl::write_string(
+                &module,
+                &info,
+                wgpu::naga::back::wgsl::WriterFlags::empty(),
+            )
+            .expect("failed to convert naga module to source")
+    }
+    /// Composes the shader module with the shader defs.
+    ///
+    /// # Panics
+    /// In debug builds, if the shader defs change the bindings or struct layouts
+    /// of the generated code, which are generated from `ShaderDefs::default()`.
+    pub fn create_shader_module(
+        device: &wgpu::Device,
+        shader_defs: &ShaderDefs,
+    ) -> wgpu::ShaderModule {
+        let mut composer = init_composer();
+        let module = make_naga_module(&mut composer, shader_defs);
+        #[cfg(debug_assertions)]
+        if *shader_defs != ShaderDefs::default() {
+            if let Err(e) = verify_module_layout(&module) {
+                panic!(
+                    "shader defs {shader_defs:?} don't match the generated bindings: {e}"
+                );
+            }
+        }
+        let source = naga_module_to_string(&module);
+        let source = std::borrow::Cow::Owned(source);
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("mesh::ShaderModule"),
+                source: wgpu::ShaderSource::Wgsl(source),
+            })
+    }
+    /// Checks that the bindings and struct layouts of `module` match the generated code.
+    /// This catches changes to shaders composed at runtime that would require regenerating the bindings.
+    pub fn verify_module_layout(
+        module: &wgpu::naga::Module,
+    ) -> Result<(), _root::module_layout::LayoutError> {
+        const BINDINGS: &[_root::module_layout::BindingLayout] = &[
+            _root::module_layout::BindingLayout {
+                group: 0,
+                binding: 0,
+                name: "camera",
+                kind: _root::module_layout::BindingKind::Uniform {
+                    size: 64,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+            _root::module_layout::BindingLayout {
+                group: 1,
+                binding: 0,
+                name: "mesh",
+                kind: _root::module_layout::BindingKind::Uniform {
+                    size: 64,
+                },
+                count: _root::module_layout::BindingCount::Single,
+            },
+        ];
+        const STRUCTS: &[_root::module_layout::StructLayout] = &[
+            _root::module_layout::StructLayout {
+                name: "Camera",
+                naga_name: "Camera",
+                size: 64,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "view_proj",
+                        offset: 0,
+                        size: 64,
+                    },
+                ],
+            },
+            _root::module_layout::StructLayout {
+                name: "Mesh",
+                naga_name: "Mesh",
+                size: 64,
+                members: &[
+                    _root::module_layout::MemberLayout {
+                        name: "model",
+                        offset: 0,
+                        size: 64,
+                    },
+                ],
+            },
+        ];
+        _root::module_layout::verify_module_layout(module, BINDINGS, STRUCTS)
+    }
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Permutation {
+        Default,
+        Skinned,
+    }
+    impl Permutation {
+        pub const ALL: [Self; 2] = [Self::Default, Self::Skinned];
+        pub fn create_shader_module(
+            self,
+            device: &wgpu::Device,
+            shader_defs: &ShaderDefs,
+        ) -> wgpu::ShaderModule {
+            match self {
+                Self::Default => create_shader_module(device, shader_defs),
+                Self::Skinned => skinned::create_shader_module(device, shader_defs),
+            }
+        }
+        pub fn create_pipeline_layout(
+            self,
+            device: &wgpu::Device,
+        ) -> wgpu::PipelineLayout {
+            match self {
+                Self::Default => create_pipeline_layout(device),
+                Self::Skinned => skinned::create_pipeline_layout(device),
+            }
+        }
+    }
+    pub mod skinned {
+        #[allow(unused_imports)]
+        use super::{_root, _root::*};
+        pub use super::VertexInput;
+        pub use super::Camera;
+        #[repr(C, align(16))]
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        pub struct Mesh {
+            /// size: 64, offset: 0x0, type: `mat4x4<f32>`
+            pub model: glam::Mat4,
+            /// size: 4, offset: 0x40, type: `u32`
+            pub joint_count: u32,
+            pub _pad_joint_count: [u8; 0x10 - core::mem::size_of::<u32>()],
+        }
+        impl Mesh {
+            pub fn new(model: glam::Mat4, joint_count: u32) -> Self {
+                Self {
+                    model,
+                    joint_count,
+                    _pad_joint_count: [0; 0x10 - core::mem::size_of::<u32>()],
+                }
+            }
+        }
+        unsafe impl bytemuck::Zeroable for Mesh {}
+        unsafe impl bytemuck::Pod for Mesh {}
+        const _: () = {
+            assert!(std::mem::offset_of!(Mesh, model) == 0);
+            assert!(std::mem::offset_of!(Mesh, joint_count) == 64);
+            assert!(std::mem::size_of:: < Mesh > () == 80);
+        };
+        #[repr(C)]
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        pub struct MeshInit {
+            pub model: glam::Mat4,
+            pub joint_count: u32,
+        }
+        impl MeshInit {
+            pub const fn const_into(&self) -> Mesh {
+                Mesh {
+                    model: self.model,
+                    joint_count: self.joint_count,
+                    _pad_joint_count: [0; 0x10 - core::mem::size_of::<u32>()],
+                }
+            }
+        }
+        impl From<MeshInit> for Mesh {
+            fn from(data: MeshInit) -> Self {
+                data.const_into()
+            }
+        }
+        pub mod bind_groups {
+            #[derive(Debug)]
+            pub struct BindGroup0(wgpu::BindGroup);
+            #[allow(non_snake_case)]
+            #[derive(Debug)]
+            pub struct BindGroupLayout0<'a> {
+                pub camera: wgpu::BufferBinding<'a>,
+            }
+            const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                label: Some("mesh::skinned::BindGroup0::Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(64),
+                        },
+                        count: None,
+                    },
+                ],
+            };
+            /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+            #[derive(Debug)]
+            pub struct Layout0(wgpu::BindGroupLayout);
+            impl Layout0 {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self(BindGroup0::get_bind_group_layout(device))
+                }
+            }
+            impl std::ops::Deref for Layout0 {
+                type Target = wgpu::BindGroupLayout;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+            impl BindGroup0 {
+                pub fn get_bind_group_layout(
+                    device: &wgpu::Device,
+                ) -> wgpu::BindGroupLayout {
+                    device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+                }
+                pub fn from_bindings(
+                    device: &wgpu::Device,
+                    bindings: BindGroupLayout0,
+                ) -> Self {
+                    Self::from_bindings_with_layout(
+                        device,
+                        &Layout0::new(device),
+                        bindings,
+                    )
+                }
+                pub fn from_bindings_with_layout(
+                    device: &wgpu::Device,
+                    layout: &Layout0,
+                    bindings: BindGroupLayout0,
+                ) -> Self {
+                    let bind_group = device
+                        .create_bind_group(
+                            &wgpu::BindGroupDescriptor {
+                                layout,
+                                entries: &[
+                                    wgpu::BindGroupEntry {
+                                        binding: 0,
+                                        resource: wgpu::BindingResource::Buffer(bindings.camera),
+                                    },
+                                ],
+                                label: Some("mesh::skinned::BindGroup0"),
+                            },
+                        );
+                    Self(bind_group)
+                }
+                pub fn set<'a>(
+                    &'a self,
+                    pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                ) {
+                    pass.set_bind_group(0, &self.0, &[]);
+                }
+            }
+            #[derive(Debug)]
+            pub struct BindGroup1(wgpu::BindGroup);
+            #[allow(non_snake_case)]
+            #[derive(Debug)]
+            pub struct BindGroupLayout1<'a> {
+                pub mesh: wgpu::BufferBinding<'a>,
+                pub joint_matrices: wgpu::BufferBinding<'a>,
+            }
+            const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+                label: Some("mesh::skinned::BindGroup1::Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(80),
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage {
+                                read_only: true,
+                            },
+                            has_dynamic_offset: false,
+                            min_binding_size: std::num::NonZeroU64::new(64),
+                        },
+                        count: None,
+                    },
+                ],
+            };
+            /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+            #[derive(Debug)]
+            pub struct Layout1(wgpu::BindGroupLayout);
+            impl Layout1 {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self(BindGroup1::get_bind_group_layout(device))
+                }
+            }
+            impl std::ops::Deref for Layout1 {
+                type Target = wgpu::BindGroupLayout;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+            impl BindGroup1 {
+                pub fn get_bind_group_layout(
+                    device: &wgpu::Device,
+                ) -> wgpu::BindGroupLayout {
+                    device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
+                }
+                pub fn from_bindings(
+                    device: &wgpu::Device,
+                    bindings: BindGroupLayout1,
+                ) -> Self {
+                    Self::from_bindings_with_layout(
+                        device,
+                        &Layout1::new(device),
+                        bindings,
+                    )
+                }
+                pub fn from_bindings_with_layout(
+                    device: &wgpu::Device,
+                    layout: &Layout1,
+                    bindings: BindGroupLayout1,
+                ) -> Self {
+                    let bind_group = device
+                        .create_bind_group(
+                            &wgpu::BindGroupDescriptor {
+                                layout,
+                                entries: &[
+                                    wgpu::BindGroupEntry {
+                                        binding: 0,
+                                        resource: wgpu::BindingResource::Buffer(bindings.mesh),
+                                    },
+                                    wgpu::BindGroupEntry {
+                                        binding: 1,
+                                        resource: wgpu::BindingResource::Buffer(
+                                            bindings.joint_matrices,
+                                        ),
+                                    },
+                                ],
+                                label: Some("mesh::skinned::BindGroup1"),
+                            },
+                        );
+                    Self(bind_group)
+                }
+                pub fn set<'a>(
+                    &'a self,
+                    pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                ) {
+                    pass.set_bind_group(1, &self.0, &[]);
+                }
+            }
+            /// The bind group layouts for this module.
+            /// Create these once and reuse them for bind groups and pipeline layouts.
+            #[derive(Debug)]
+            pub struct Layouts {
+                pub bind_group0: Layout0,
+                pub bind_group1: Layout1,
+            }
+            impl Layouts {
+                pub fn new(device: &wgpu::Device) -> Self {
+                    Self {
+                        bind_group0: Layout0::new(device),
+                        bind_group1: Layout1::new(device),
+                    }
+                }
+            }
+            #[derive(Debug, Copy, Clone)]
+            pub struct BindGroups<'a> {
+                pub bind_group0: &'a BindGroup0,
+                pub bind_group1: &'a BindGroup1,
+            }
+            impl<'a> BindGroups<'a> {
+                pub fn set(
+                    &self,
+                    pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+                ) {
+                    self.bind_group0.set(pass);
+                    self.bind_group1.set(pass);
+                }
+            }
+        }
+        pub fn set_bind_groups<'a>(
+            pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+            bind_group0: &'a bind_groups::BindGroup0,
+            bind_group1: &'a bind_groups::BindGroup1,
+        ) {
+            bind_group0.set(pass);
+            bind_group1.set(pass);
+        }
+        pub const ENTRY_VS_MAIN: &str = "vs_main";
+        #[derive(Debug)]
+        pub struct VertexEntry<const N: usize> {
+            entry_point: &'static str,
+            buffers: [wgpu::VertexBufferLayout<'static>; N],
+        }
+        pub fn vertex_state<'a, const N: usize>(
+            module: &'a wgpu::ShaderModule,
+            entry: &'a VertexEntry<N>,
+        ) -> wgpu::VertexState<'a> {
+            wgpu::VertexState {
+                module,
+                entry_point: entry.entry_point,
+                buffers: &entry.buffers,
+            }
+        }
+        pub fn vs_main_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<1> {
+            VertexEntry {
+                entry_point: ENTRY_VS_MAIN,
+                buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
+            }
+        }
+        pub fn set_vertex_buffers<'a>(
+            pass: &mut impl _root::pass_ext::SetVertexBuffer<'a>,
+            vertex_input: wgpu::BufferSlice<'a>,
+        ) {
+            pass.set_vertex_buffer(0, vertex_input);
+        }
+        pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+            create_pipeline_layout_with_layouts(
+                device,
+                &bind_groups::Layouts::new(device),
+            )
+        }
+        pub fn create_pipeline_layout_with_layouts(
+            device: &wgpu::Device,
+            layouts: &bind_groups::Layouts,
+        ) -> wgpu::PipelineLayout {
+            device
+                .create_pipeline_layout(
+                    &wgpu::PipelineLayoutDescriptor {
+                        label: Some("mesh::skinned::PipelineLayout"),
+                        bind_group_layouts: &[
+                            &layouts.bind_group0,
+                            &layouts.bind_group1,
+                        ],
+                        push_constant_ranges: &[],
+                    },
+                )
+        }
+        pub fn init_composer() -> naga_oil::compose::Composer {
+            #[allow(unused_mut)]
+            let mut composer = naga_oil::compose::Composer::default()
+                .with_capabilities(wgpu::naga::valid::Capabilities::all());
+            composer
+        }
+        pub fn make_naga_module(
+            composer: &mut naga_oil::compose::Composer,
+            shader_defs: &super::ShaderDefs,
+        ) -> wgpu::naga::Module {
+            composer
+                .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                    source: include_str!("../shaders/permutations/mesh.wgsl"),
+                    file_path: "../shaders/permutations/mesh.wgsl",
+                    shader_defs: {
+                        let mut shader_defs = shader_defs.to_shader_defs();
+                        shader_defs
+                            .insert(
+                                "SKINNED".to_owned(),
+                                naga_oil::compose::ShaderDefValue::Bool(true),
+                            );
+                        shader_defs
+                    },
+                    ..Default::default()
+                })
+                .expect("failed to build naga module")
+        }
+        pub fn naga_module_to_string(module: &wgpu::naga::Module) -> String {
+            let info = wgpu::naga::valid::Validator::new(
+                    wgpu::naga::valid::ValidationFlags::empty(),
+                    wgpu::naga::valid::Capabilities::all(),
+                )
+                .validate(&module);
+            let info = info.unwrap();
+            wgpu::naga::back::wgsl::write_string(
+                    &module,
+                    &info,
+                    wgpu::naga::back::wgsl::WriterFlags::empty(),
+                )
+                .expect("failed to convert naga module to source")
+        }
+        /// Composes the shader module with the shader defs.
+        ///
+        /// # Panics
+        /// In debug builds, if the shader defs change the bindings or struct layouts
+        /// of the generated code, which are generated from `ShaderDefs::default()`.
+        pub fn create_shader_module(
+            device: &wgpu::Device,
+            shader_defs: &super::ShaderDefs,
+        ) -> wgpu::ShaderModule {
+            let mut composer = init_composer();
+            let module = make_naga_module(&mut composer, shader_defs);
+            #[cfg(debug_assertions)]
+            if *shader_defs != super::ShaderDefs::default() {
+                if let Err(e) = verify_module_layout(&module) {
+                    panic!(
+                        "shader defs {shader_defs:?} don't match the generated bindings: {e}"
+                    );
+                }
+            }
+            let source = naga_module_to_string(&module);
+            let source = std::borrow::Cow::Owned(source);
+            device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("mesh::skinned::ShaderModule"),
+                    source: wgpu::ShaderSource::Wgsl(source),
+                })
+        }
+        /// Checks that the bindings and struct layouts of `module` match the generated code.
+        /// This catches changes to shaders composed at runtime that would require regenerating the bindings.
+        pub fn verify_module_layout(
+            module: &wgpu::naga::Module,
+        ) -> Result<(), _root::module_layout::LayoutError> {
+            const BINDINGS: &[_root::module_layout::BindingLayout] = &[
+                _root::module_layout::BindingLayout {
+                    group: 0,
+                    binding: 0,
+                    name: "camera",
+                    kind: _root::module_layout::BindingKind::Uniform {
+                        size: 64,
+                    },
+                    count: _root::module_layout::BindingCount::Single,
+                },
+                _root::module_layout::BindingLayout {
+                    group: 1,
+                    binding: 0,
+                    name: "mesh",
+                    kind: _root::module_layout::BindingKind::Uniform {
+                        size: 80,
+                    },
+                    count: _root::module_layout::BindingCount::Single,
+                },
+                _root::module_layout::BindingLayout {
+                    group: 1,
+                    binding: 1,
+                    name: "joint_matrices",
+                    kind: _root::module_layout::BindingKind::Storage {
+                        read_only: true,
+                        size: 64,
+                    },
+                    count: _root::module_layout::BindingCount::Single,
+                },
+            ];
+            const STRUCTS: &[_root::module_layout::StructLayout] = &[
+                _root::module_layout::StructLayout {
+                    name: "Camera",
+                    naga_name: "Camera",
+                    size: 64,
+                    members: &[
+                        _root::module_layout::MemberLayout {
+                            name: "view_proj",
+                            offset: 0,
+                            size: 64,
+                        },
+                    ],
+                },
+                _root::module_layout::StructLayout {
+                    name: "Mesh",
+                    naga_name: "Mesh",
+                    size: 80,
+                    members: &[
+                        _root::module_layout::MemberLayout {
+                            name: "model",
+                            offset: 0,
+                            size: 64,
+                        },
+                        _root::module_layout::MemberLayout {
+                            name: "joint_count",
+                            offset: 64,
+                            size: 4,
+                        },
+                    ],
+                },
+            ];
+            _root::module_layout::verify_module_layout(module, BINDINGS, STRUCTS)
+        }
+    }
+}
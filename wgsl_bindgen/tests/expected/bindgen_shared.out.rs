@@ -0,0 +1,537 @@
+#[allow(unused)]
+mod _root {
+    pub use super::*;
+    const _: () = {
+        assert!(std::mem::size_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat3A > () == 48);
+        assert!(std::mem::align_of:: < glam::Mat3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat4 > () == 64);
+        assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
+    };
+}
+pub mod pass_ext {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    /// A render pass, compute pass, or render bundle encoder for setting bind groups.
+    pub trait SetBindGroup<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        );
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderPass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderPass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::ComputePass<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::ComputePass::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    impl<'a> SetBindGroup<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_bind_group(
+            &mut self,
+            index: u32,
+            bind_group: &'a wgpu::BindGroup,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            wgpu::RenderBundleEncoder::set_bind_group(self, index, bind_group, offsets);
+        }
+    }
+    /// A render pass, compute pass, or render bundle encoder for setting push constants.
+    /// Compute passes ignore the shader stages.
+    pub trait SetPushConstants {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        );
+    }
+    impl<'a> SetPushConstants for wgpu::RenderPass<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderPass::set_push_constants(self, stages, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::ComputePass<'a> {
+        fn set_push_constants(
+            &mut self,
+            _stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::ComputePass::set_push_constants(self, offset, data);
+        }
+    }
+    impl<'a> SetPushConstants for wgpu::RenderBundleEncoder<'a> {
+        fn set_push_constants(
+            &mut self,
+            stages: wgpu::ShaderStages,
+            offset: u32,
+            data: &[u8],
+        ) {
+            wgpu::RenderBundleEncoder::set_push_constants(self, stages, offset, data);
+        }
+    }
+    /// A render pass or render bundle encoder for setting vertex buffers.
+    pub trait SetVertexBuffer<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>);
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderPass<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderPass::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+    impl<'a> SetVertexBuffer<'a> for wgpu::RenderBundleEncoder<'a> {
+        fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: wgpu::BufferSlice<'a>) {
+            wgpu::RenderBundleEncoder::set_vertex_buffer(self, slot, buffer_slice);
+        }
+    }
+}
+pub mod camera {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Camera {
+        /// size: 64, offset: 0x0, type: `mat4x4<f32>`
+        pub view_proj: glam::Mat4,
+        /// size: 16, offset: 0x40, type: `vec4<f32>`
+        pub position: glam::Vec4,
+    }
+    impl Camera {
+        pub fn new(view_proj: glam::Mat4, position: glam::Vec4) -> Self {
+            Self { view_proj, position }
+        }
+    }
+    unsafe impl bytemuck::Zeroable for Camera {}
+    unsafe impl bytemuck::Pod for Camera {}
+    const _: () = {
+        assert!(std::mem::offset_of!(Camera, view_proj) == 0);
+        assert!(std::mem::offset_of!(Camera, position) == 64);
+        assert!(std::mem::size_of:: < Camera > () == 80);
+    };
+    pub mod bind_groups {
+        #[allow(unused_imports)]
+        use super::{_root, _root::*};
+        #[derive(Debug)]
+        pub struct BindGroup0(wgpu::BindGroup);
+        #[allow(non_snake_case)]
+        #[derive(Debug)]
+        pub struct BindGroupLayout0<'a> {
+            pub camera: wgpu::BufferBinding<'a>,
+        }
+        const LAYOUT_DESCRIPTOR0: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some("camera::BindGroup0::Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(80),
+                    },
+                    count: None,
+                },
+            ],
+        };
+        /// The layout for creating bind groups with [BindGroup0::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout0(wgpu::BindGroupLayout);
+        impl Layout0 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup0::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout0 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        impl BindGroup0 {
+            pub fn get_bind_group_layout(
+                device: &wgpu::Device,
+            ) -> wgpu::BindGroupLayout {
+                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR0)
+            }
+            pub fn from_bindings(
+                device: &wgpu::Device,
+                bindings: BindGroupLayout0,
+            ) -> Self {
+                Self::from_bindings_with_layout(device, &Layout0::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout0,
+                bindings: BindGroupLayout0,
+            ) -> Self {
+                let bind_group = device
+                    .create_bind_group(
+                        &wgpu::BindGroupDescriptor {
+                            layout,
+                            entries: &[
+                                wgpu::BindGroupEntry {
+                                    binding: 0,
+                                    resource: wgpu::BindingResource::Buffer(bindings.camera),
+                                },
+                            ],
+                            label: Some("camera::BindGroup0"),
+                        },
+                    );
+                Self(bind_group)
+            }
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(0, &self.0, &[]);
+            }
+        }
+    }
+}
+pub mod forward {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    pub mod bind_groups {
+        pub use super::_root::camera::bind_groups::{
+            BindGroup0, BindGroupLayout0, Layout0,
+        };
+        #[derive(Debug)]
+        pub struct BindGroup1(wgpu::BindGroup);
+        #[allow(non_snake_case)]
+        #[derive(Debug)]
+        pub struct BindGroupLayout1<'a> {
+            pub color_texture: &'a wgpu::TextureView,
+            pub color_sampler: &'a wgpu::Sampler,
+        }
+        const LAYOUT_DESCRIPTOR1: wgpu::BindGroupLayoutDescriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some("forward::BindGroup1::Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float {
+                            filterable: true,
+                        },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        };
+        /// The layout for creating bind groups with [BindGroup1::from_bindings_with_layout].
+        #[derive(Debug)]
+        pub struct Layout1(wgpu::BindGroupLayout);
+        impl Layout1 {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self(BindGroup1::get_bind_group_layout(device))
+            }
+        }
+        impl std::ops::Deref for Layout1 {
+            type Target = wgpu::BindGroupLayout;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        impl BindGroup1 {
+            pub fn get_bind_group_layout(
+                device: &wgpu::Device,
+            ) -> wgpu::BindGroupLayout {
+                device.create_bind_group_layout(&LAYOUT_DESCRIPTOR1)
+            }
+            pub fn from_bindings(
+                device: &wgpu::Device,
+                bindings: BindGroupLayout1,
+            ) -> Self {
+                Self::from_bindings_with_layout(device, &Layout1::new(device), bindings)
+            }
+            pub fn from_bindings_with_layout(
+                device: &wgpu::Device,
+                layout: &Layout1,
+                bindings: BindGroupLayout1,
+            ) -> Self {
+                let bind_group = device
+                    .create_bind_group(
+                        &wgpu::BindGroupDescriptor {
+                            layout,
+                            entries: &[
+                                wgpu::BindGroupEntry {
+                                    binding: 0,
+                                    resource: wgpu::BindingResource::TextureView(
+                                        bindings.color_texture,
+                                    ),
+                                },
+                                wgpu::BindGroupEntry {
+                                    binding: 1,
+                                    resource: wgpu::BindingResource::Sampler(
+                                        bindings.color_sampler,
+                                    ),
+                                },
+                            ],
+                            label: Some("forward::BindGroup1"),
+                        },
+                    );
+                Self(bind_group)
+            }
+            pub fn set<'a>(
+                &'a self,
+                pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>,
+            ) {
+                pass.set_bind_group(1, &self.0, &[]);
+            }
+        }
+        /// The bind group layouts for this module.
+        /// Create these once and reuse them for bind groups and pipeline layouts.
+        #[derive(Debug)]
+        pub struct Layouts {
+            pub bind_group0: Layout0,
+            pub bind_group1: Layout1,
+        }
+        impl Layouts {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self {
+                    bind_group0: Layout0::new(device),
+                    bind_group1: Layout1::new(device),
+                }
+            }
+        }
+        #[derive(Debug, Copy, Clone)]
+        pub struct BindGroups<'a> {
+            pub bind_group0: &'a BindGroup0,
+            pub bind_group1: &'a BindGroup1,
+        }
+        impl<'a> BindGroups<'a> {
+            pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                self.bind_group0.set(pass);
+                self.bind_group1.set(pass);
+            }
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+        bind_group0: &'a bind_groups::BindGroup0,
+        bind_group1: &'a bind_groups::BindGroup1,
+    ) {
+        bind_group0.set(pass);
+        bind_group1.set(pass);
+    }
+    pub const ENTRY_VS_MAIN: &str = "vs_main";
+    pub const ENTRY_FS_MAIN: &str = "fs_main";
+    #[derive(Debug)]
+    pub struct VertexEntry<const N: usize> {
+        entry_point: &'static str,
+        buffers: [wgpu::VertexBufferLayout<'static>; N],
+    }
+    pub fn vertex_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a VertexEntry<N>,
+    ) -> wgpu::VertexState<'a> {
+        wgpu::VertexState {
+            module,
+            entry_point: entry.entry_point,
+            buffers: &entry.buffers,
+        }
+    }
+    pub fn vs_main_entry() -> VertexEntry<0> {
+        VertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [],
+        }
+    }
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+    }
+    pub fn create_pipeline_layout_with_layouts(
+        device: &wgpu::Device,
+        layouts: &bind_groups::Layouts,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("forward::PipelineLayout"),
+                    bind_group_layouts: &[&layouts.bind_group0, &layouts.bind_group1],
+                    push_constant_ranges: &[],
+                },
+            )
+    }
+    pub fn create_shader_module(device: &wgpu::Device) -> wgpu::ShaderModule {
+        let source = std::borrow::Cow::Borrowed(SHADER_STRING);
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("forward::ShaderModule"),
+                source: wgpu::ShaderSource::Wgsl(source),
+            })
+    }
+    const SHADER_STRING: &'static str = r#"
+struct CameraX_naga_oil_mod_XMNQW2ZLSMEX {
+    view_proj: mat4x4<f32>,
+    position: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@group(0) @binding(0) 
+var<uniform> cameraX_naga_oil_mod_XMNQW2ZLSMEX: CameraX_naga_oil_mod_XMNQW2ZLSMEX;
+@group(1) @binding(0) 
+var color_texture: texture_2d<f32>;
+@group(1) @binding(1) 
+var color_sampler: sampler;
+
+@vertex 
+fn vs_main(@location(0) position: vec3<f32>, @location(1) uv: vec2<f32>) -> VertexOutput {
+    var out: VertexOutput;
+
+    let _e4 = cameraX_naga_oil_mod_XMNQW2ZLSMEX.view_proj;
+    out.clip_position = (_e4 * vec4<f32>(position, 1f));
+    out.uv = uv;
+    let _e11 = out;
+    return _e11;
+}
+
+@fragment 
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let _e4 = textureSample(color_texture, color_sampler, in.uv);
+    return _e4;
+}
+"#;
+}
+pub mod depth {
+    #[allow(unused_imports)]
+    use super::{_root, _root::*};
+    pub mod bind_groups {
+        pub use super::_root::camera::bind_groups::{
+            BindGroup0, BindGroupLayout0, Layout0,
+        };
+        /// The bind group layouts for this module.
+        /// Create these once and reuse them for bind groups and pipeline layouts.
+        #[derive(Debug)]
+        pub struct Layouts {
+            pub bind_group0: Layout0,
+        }
+        impl Layouts {
+            pub fn new(device: &wgpu::Device) -> Self {
+                Self {
+                    bind_group0: Layout0::new(device),
+                }
+            }
+        }
+        #[derive(Debug, Copy, Clone)]
+        pub struct BindGroups<'a> {
+            pub bind_group0: &'a BindGroup0,
+        }
+        impl<'a> BindGroups<'a> {
+            pub fn set(&self, pass: &mut impl super::_root::pass_ext::SetBindGroup<'a>) {
+                self.bind_group0.set(pass);
+            }
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut impl _root::pass_ext::SetBindGroup<'a>,
+        bind_group0: &'a bind_groups::BindGroup0,
+    ) {
+        bind_group0.set(pass);
+    }
+    pub const ENTRY_VS_MAIN: &str = "vs_main";
+    pub const ENTRY_FS_MAIN: &str = "fs_main";
+    #[derive(Debug)]
+    pub struct VertexEntry<const N: usize> {
+        entry_point: &'static str,
+        buffers: [wgpu::VertexBufferLayout<'static>; N],
+    }
+    pub fn vertex_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a VertexEntry<N>,
+    ) -> wgpu::VertexState<'a> {
+        wgpu::VertexState {
+            module,
+            entry_point: entry.entry_point,
+            buffers: &entry.buffers,
+        }
+    }
+    pub fn vs_main_entry() -> VertexEntry<0> {
+        VertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [],
+        }
+    }
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        create_pipeline_layout_with_layouts(device, &bind_groups::Layouts::new(device))
+    }
+    pub fn create_pipeline_layout_with_layouts(
+        device: &wgpu::Device,
+        layouts: &bind_groups::Layouts,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("depth::PipelineLayout"),
+                    bind_group_layouts: &[&layouts.bind_group0],
+                    push_constant_ranges: &[],
+                },
+            )
+    }
+    pub fn create_shader_module(device: &wgpu::Device) -> wgpu::ShaderModule {
+        let source = std::borrow::Cow::Borrowed(SHADER_STRING);
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("depth::ShaderModule"),
+                source: wgpu::ShaderSource::Wgsl(source),
+            })
+    }
+    const SHADER_STRING: &'static str = r#"
+struct CameraX_naga_oil_mod_XMNQW2ZLSMEX {
+    view_proj: mat4x4<f32>,
+    position: vec4<f32>,
+}
+
+@group(0) @binding(0) 
+var<uniform> cameraX_naga_oil_mod_XMNQW2ZLSMEX: CameraX_naga_oil_mod_XMNQW2ZLSMEX;
+
+@vertex 
+fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+    let _e2 = cameraX_naga_oil_mod_XMNQW2ZLSMEX.view_proj;
+    return (_e2 * vec4<f32>(position, 1f));
+}
+
+@fragment 
+fn fs_main(@builtin(position) position_1: vec4<f32>) -> @location(0) vec4<f32> {
+    let _e4 = cameraX_naga_oil_mod_XMNQW2ZLSMEX.position;
+    let distance = length((position_1.xyz - _e4.xyz));
+    return vec4(distance);
+}
+"#;
+}
@@ -0,0 +1,5 @@
+#import circular_b
+
+fn a() -> f32 {
+  return circular_b::b();
+}
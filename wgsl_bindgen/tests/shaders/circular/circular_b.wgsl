@@ -0,0 +1,5 @@
+#import circular_c
+
+fn b() -> f32 {
+  return circular_c::c();
+}
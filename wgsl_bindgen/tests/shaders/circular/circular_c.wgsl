@@ -0,0 +1,5 @@
+#import circular_a
+
+fn c() -> f32 {
+  return circular_a::a();
+}
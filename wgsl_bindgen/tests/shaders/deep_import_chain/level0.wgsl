@@ -0,0 +1,3 @@
+#import level1
+
+fn level0() {}
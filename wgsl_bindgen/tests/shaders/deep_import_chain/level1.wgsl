@@ -0,0 +1,3 @@
+#import level2
+
+fn level1() {}
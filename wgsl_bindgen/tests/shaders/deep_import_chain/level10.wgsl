@@ -0,0 +1,3 @@
+#import level11
+
+fn level10() {}
@@ -0,0 +1,3 @@
+#import level12
+
+fn level11() {}
@@ -0,0 +1,3 @@
+#import level13
+
+fn level12() {}
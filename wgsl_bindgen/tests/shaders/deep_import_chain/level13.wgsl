@@ -0,0 +1,3 @@
+#import level14
+
+fn level13() {}
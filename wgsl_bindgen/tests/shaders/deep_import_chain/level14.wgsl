@@ -0,0 +1,3 @@
+#import level15
+
+fn level14() {}
@@ -0,0 +1,3 @@
+#import level16
+
+fn level15() {}
@@ -0,0 +1,3 @@
+#import level17
+
+fn level16() {}
@@ -0,0 +1,3 @@
+#import level18
+
+fn level17() {}
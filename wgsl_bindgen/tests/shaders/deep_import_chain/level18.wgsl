@@ -0,0 +1,3 @@
+#import level19
+
+fn level18() {}
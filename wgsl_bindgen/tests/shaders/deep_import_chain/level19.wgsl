@@ -0,0 +1,3 @@
+#import level20
+
+fn level19() {}
@@ -0,0 +1,3 @@
+#import level3
+
+fn level2() {}
@@ -0,0 +1,3 @@
+#import level21
+
+fn level20() {}
@@ -0,0 +1,3 @@
+#import level22
+
+fn level21() {}
@@ -0,0 +1,3 @@
+#import level23
+
+fn level22() {}
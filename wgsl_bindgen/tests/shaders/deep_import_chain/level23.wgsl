@@ -0,0 +1,3 @@
+#import level24
+
+fn level23() {}
@@ -0,0 +1,3 @@
+#import level25
+
+fn level24() {}
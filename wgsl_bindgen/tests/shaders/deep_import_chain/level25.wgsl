@@ -0,0 +1,3 @@
+#import level26
+
+fn level25() {}
@@ -0,0 +1,3 @@
+#import level27
+
+fn level26() {}
@@ -0,0 +1,3 @@
+#import level28
+
+fn level27() {}
@@ -0,0 +1,3 @@
+#import level29
+
+fn level28() {}
@@ -0,0 +1,3 @@
+#import level30
+
+fn level29() {}
@@ -0,0 +1,3 @@
+#import level4
+
+fn level3() {}
@@ -0,0 +1,3 @@
+#import level31
+
+fn level30() {}
@@ -0,0 +1 @@
+fn level31() {}
@@ -0,0 +1,3 @@
+#import level5
+
+fn level4() {}
@@ -0,0 +1,3 @@
+#import level6
+
+fn level5() {}
@@ -0,0 +1,3 @@
+#import level7
+
+fn level6() {}
@@ -0,0 +1,3 @@
+#import level8
+
+fn level7() {}
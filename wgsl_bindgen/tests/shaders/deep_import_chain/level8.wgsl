@@ -0,0 +1,3 @@
+#import level9
+
+fn level8() {}
@@ -0,0 +1,3 @@
+#import level10
+
+fn level9() {}
@@ -0,0 +1,3 @@
+fn red() -> vec4<f32> {
+  return vec4(1.0, 0.0, 0.0, 1.0)
+}
@@ -0,0 +1,6 @@
+#import colors;
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+  return colors::red();
+}
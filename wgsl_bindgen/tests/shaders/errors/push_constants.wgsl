@@ -0,0 +1,6 @@
+var<push_constant> scale: f32;
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+  return vec4(scale);
+}
@@ -0,0 +1,7 @@
+@group(0) @binding(0)
+var textures: binding_array<texture_2d<f32>>;
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+  return textureLoad(textures[0], vec2(0), 0);
+}
@@ -0,0 +1,7 @@
+@group(0) @binding(0)
+var<storage, read> colors: array<vec4<f32>>;
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+  return colors[0];
+}
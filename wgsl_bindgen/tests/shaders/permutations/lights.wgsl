@@ -0,0 +1,10 @@
+struct Light {
+  color: vec4<f32>,
+#ifdef SHADOWS
+  view_proj: mat4x4<f32>,
+#endif
+};
+
+struct Ambient {
+  color: vec4<f32>,
+};
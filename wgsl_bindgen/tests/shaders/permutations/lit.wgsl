@@ -0,0 +1,26 @@
+#import lights::{Light, Ambient}
+
+struct Material {
+  base_color: vec4<f32>,
+  ambient: Ambient,
+};
+
+struct Scene {
+  light: Light,
+  exposure: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> scene: Scene;
+
+@group(0) @binding(1)
+var<uniform> material: Material;
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+  var color = scene.light.color * material.base_color + material.ambient.color;
+#ifdef SHADOWS
+  color = scene.light.view_proj * color;
+#endif
+  return color * scene.exposure;
+}
@@ -0,0 +1,36 @@
+struct VertexInput {
+  @location(0) position: vec3<f32>,
+  @location(1) joints: vec4<u32>,
+};
+
+struct Camera {
+  view_proj: mat4x4<f32>,
+};
+
+struct Mesh {
+  model: mat4x4<f32>,
+#ifdef SKINNED
+  joint_count: u32,
+#endif
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+@group(1) @binding(0)
+var<uniform> mesh: Mesh;
+
+#ifdef SKINNED
+@group(1) @binding(1)
+var<storage, read> joint_matrices: array<mat4x4<f32>>;
+#endif
+
+@vertex
+fn vs_main(input: VertexInput) -> @builtin(position) vec4<f32> {
+#ifdef SKINNED
+  let model = joint_matrices[input.joints.x] * mesh.model;
+#else
+  let model = mesh.model;
+#endif
+  return camera.view_proj * model * vec4(input.position, 1.0);
+}
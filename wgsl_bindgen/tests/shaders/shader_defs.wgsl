@@ -0,0 +1,10 @@
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+#if MSAA_SAMPLES > 1
+  return vec4(1.0);
+#else ifdef SHADOWS
+  return vec4(0.5);
+#else
+  return vec4(0.0);
+#endif
+}
@@ -0,0 +1,12 @@
+#import camera;
+
+@vertex
+fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+  return camera::camera.view_proj * vec4(position, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+  let distance = length(position.xyz - camera::camera.position.xyz);
+  return vec4(distance);
+}
@@ -0,0 +1,11 @@
+struct Uniforms {
+  color: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+  return uniforms.color;
+}